@@ -0,0 +1,430 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, Ident, LitStr, Path, Type, Visibility};
+
+pub fn derive_component(input: &DeriveInput) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "generic components are not supported",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ContractComponent` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "`ContractComponent` can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut parameters = None;
+    let mut context = None;
+    let mut encoder = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("component"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("parameters") {
+                parameters = Some(meta.value()?.parse::<Type>()?);
+            } else if meta.path.is_ident("context") {
+                context = Some(meta.value()?.parse::<Type>()?);
+            } else if meta.path.is_ident("contract") {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("encoder") {
+                        encoder = Some(meta.value()?.parse::<Path>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("argument not allowed, must be: `encoder`"))
+                    }
+                })?;
+            } else {
+                return Err(meta.error(
+                    "argument not allowed, must be one of: `parameters`, `context`, `contract`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    let Some(parameters) = parameters else {
+        return Err(syn::Error::new(
+            input.span(),
+            "missing `#[component(parameters = ...)]` attribute",
+        ));
+    };
+    let context = context
+        .unwrap_or_else(|| syn::parse_quote!(::freenet_stdlib::contract_composition::NoContext));
+
+    let fields = named
+        .named
+        .iter()
+        .map(ComponentField::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let component = Component {
+        name: &input.ident,
+        vis: &input.vis,
+        summary: format_ident!("{}Summary", input.ident),
+        delta: format_ident!("{}Delta", input.ident),
+        parameters,
+        context,
+        fields,
+    };
+    let composite_types = component.gen_composite_types();
+    let component_impl = component.gen_component_impl(encoder.as_ref());
+    Ok(quote! {
+        #composite_types
+        #component_impl
+    })
+}
+
+enum MergeStrategy {
+    Merge,
+    Replace,
+}
+
+struct ComponentField {
+    name: Ident,
+    vis: Visibility,
+    ty: Type,
+    /// Field of the parent parameters holding the parameters of this child.
+    params: Option<Ident>,
+    merge: MergeStrategy,
+}
+
+impl ComponentField {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut params = None;
+        let mut merge = MergeStrategy::Merge;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("component"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("params") {
+                    params = Some(meta.value()?.parse::<Ident>()?);
+                } else if meta.path.is_ident("merge") {
+                    let strategy = meta.value()?.parse::<LitStr>()?;
+                    merge =
+                        match strategy.value().as_str() {
+                            "merge" => MergeStrategy::Merge,
+                            "replace" => MergeStrategy::Replace,
+                            _ => return Err(syn::Error::new(
+                                strategy.span(),
+                                "unknown merge strategy, must be one of: \"merge\", \"replace\"",
+                            )),
+                        };
+                } else {
+                    return Err(
+                        meta.error("argument not allowed, must be one of: `params`, `merge`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(Self {
+            name: field.ident.clone().expect("named field"),
+            vis: field.vis.clone(),
+            ty: field.ty.clone(),
+            params,
+            merge,
+        })
+    }
+
+    /// Expression for the parameters of this child, given the parent's `parameters`.
+    fn params(&self) -> TokenStream {
+        let ty = &self.ty;
+        match &self.params {
+            Some(field) => quote!(&parameters.#field),
+            None => quote! {
+                &<<#ty as ::freenet_stdlib::contract_composition::ContractComponent>::Parameters
+                    as ::core::convert::From<_>>::from(parameters)
+            },
+        }
+    }
+
+    /// Function combining two values of the associated type `assoc` of this child.
+    fn combine(&self, assoc: &str) -> TokenStream {
+        let ty = &self.ty;
+        let assoc = format_ident!("{}", assoc);
+        let value =
+            quote!(<#ty as ::freenet_stdlib::contract_composition::ContractComponent>::#assoc);
+        match self.merge {
+            MergeStrategy::Merge => quote! {
+                <#value as ::freenet_stdlib::contract_composition::Mergeable<#value>>::merge
+            },
+            MergeStrategy::Replace => quote! {
+                ::freenet_stdlib::contract_composition::replace::<#value>
+            },
+        }
+    }
+}
+
+struct Component<'a> {
+    name: &'a Ident,
+    vis: &'a Visibility,
+    summary: Ident,
+    delta: Ident,
+    parameters: Type,
+    context: Type,
+    fields: Vec<ComponentField>,
+}
+
+impl Component<'_> {
+    fn gen_composite_types(&self) -> TokenStream {
+        let Self {
+            name,
+            vis,
+            summary,
+            delta,
+            ..
+        } = self;
+        let field_names: Vec<_> = self.fields.iter().map(|f| &f.name).collect();
+        let field_vis: Vec<_> = self.fields.iter().map(|f| &f.vis).collect();
+        let summary_types = self.fields.iter().map(|f| {
+            let ty = &f.ty;
+            quote!(<#ty as ::freenet_stdlib::contract_composition::ContractComponent>::Summary)
+        });
+        let delta_types = self.fields.iter().map(|f| {
+            let ty = &f.ty;
+            quote!(<#ty as ::freenet_stdlib::contract_composition::ContractComponent>::Delta)
+        });
+        let combine_summary = self.fields.iter().map(|f| f.combine("Summary"));
+        let combine_delta = self.fields.iter().map(|f| f.combine("Delta"));
+        let summary_doc =
+            format!("Summary of a [`{name}`], with the summary of each of its components.");
+        let delta_doc = format!(
+            "Delta of a [`{name}`], with the delta of each of its components which changed."
+        );
+
+        quote! {
+            #[doc = #summary_doc]
+            #[derive(
+                ::core::clone::Clone,
+                ::core::default::Default,
+                ::freenet_stdlib::prelude::serde::Serialize,
+                ::freenet_stdlib::prelude::serde::Deserialize,
+            )]
+            #[serde(crate = "::freenet_stdlib::prelude::serde")]
+            #vis struct #summary {
+                #(#field_vis #field_names: ::core::option::Option<#summary_types>,)*
+            }
+
+            #[doc = #delta_doc]
+            #[derive(
+                ::core::clone::Clone,
+                ::core::default::Default,
+                ::freenet_stdlib::prelude::serde::Serialize,
+                ::freenet_stdlib::prelude::serde::Deserialize,
+            )]
+            #[serde(crate = "::freenet_stdlib::prelude::serde")]
+            #vis struct #delta {
+                #(#field_vis #field_names: ::core::option::Option<#delta_types>,)*
+            }
+
+            impl #delta {
+                /// Whether none of the components changed.
+                pub fn is_empty(&self) -> bool {
+                    true #(&& self.#field_names.is_none())*
+                }
+            }
+
+            impl ::freenet_stdlib::contract_composition::Mergeable<#summary> for #summary {
+                fn merge(&mut self, other: #summary) {
+                    #(::freenet_stdlib::contract_composition::merge_field(
+                        &mut self.#field_names,
+                        other.#field_names,
+                        #combine_summary,
+                    );)*
+                }
+            }
+
+            impl ::freenet_stdlib::contract_composition::Mergeable<#delta> for #delta {
+                fn merge(&mut self, other: #delta) {
+                    #(::freenet_stdlib::contract_composition::merge_field(
+                        &mut self.#field_names,
+                        other.#field_names,
+                        #combine_delta,
+                    );)*
+                }
+            }
+
+            impl<'a> ::core::convert::From<&'a #name> for #summary {
+                fn from(_: &'a #name) -> Self {
+                    ::core::default::Default::default()
+                }
+            }
+        }
+    }
+
+    fn gen_component_impl(&self, encoder: Option<&Path>) -> TokenStream {
+        let Self {
+            name,
+            summary,
+            delta,
+            parameters,
+            context,
+            ..
+        } = self;
+        let contract_attr = encoder
+            .map(|encoder| quote!(#[::freenet_stdlib::prelude::contract(encoder = #encoder)]));
+
+        let verify = self.fields.iter().map(|f| {
+            let (field, ty, params) = (&f.name, &f.ty, f.params());
+            quote! {
+                match ::freenet_stdlib::contract_composition::ContractComponent::verify::<#ty, Self>(
+                    &self.#field,
+                    #params,
+                    self,
+                    related,
+                )? {
+                    ::freenet_stdlib::prelude::ValidateResult::Valid => {}
+                    other => return ::core::result::Result::Ok(other),
+                }
+            }
+        });
+
+        let merge = self.fields.iter().map(|f| {
+            let (field, ty, params) = (&f.name, &f.ty, f.params());
+            quote! {{
+                use ::freenet_stdlib::contract_composition::TypedUpdateData as Update;
+                let sub_update: ::core::option::Option<Update<#ty>> = match update {
+                    Update::RelatedState { state } => ::core::option::Option::Some(Update::RelatedState {
+                        state: ::core::clone::Clone::clone(&state.#field),
+                    }),
+                    Update::RelatedDelta { delta } => delta
+                        .#field
+                        .clone()
+                        .map(|delta| Update::RelatedDelta { delta }),
+                    Update::RelatedStateAndDelta { state, delta } => {
+                        let state = ::core::clone::Clone::clone(&state.#field);
+                        ::core::option::Option::Some(match delta.#field.clone() {
+                            ::core::option::Option::Some(delta) => {
+                                Update::RelatedStateAndDelta { state, delta }
+                            }
+                            ::core::option::Option::None => Update::RelatedState { state },
+                        })
+                    }
+                };
+                if let ::core::option::Option::Some(sub_update) = sub_update {
+                    match ::freenet_stdlib::contract_composition::ContractComponent::merge(
+                        &mut self.#field,
+                        #params,
+                        &sub_update,
+                        related,
+                    ) {
+                        ::freenet_stdlib::typed_contract::MergeResult::Success => {}
+                        other => return other,
+                    }
+                }
+            }}
+        });
+
+        let summarize = self.fields.iter().map(|f| {
+            let (field, params, combine) = (&f.name, f.params(), f.combine("Summary"));
+            quote! {{
+                let mut collected = ::freenet_stdlib::contract_composition::Collected::new(#combine);
+                ::freenet_stdlib::contract_composition::ContractComponent::summarize(
+                    &self.#field,
+                    #params,
+                    &mut collected,
+                )?;
+                this.#field = collected.into_inner();
+            }}
+        });
+
+        // unchanged components are left out, so an up to date peer gets an empty delta
+        let deltas = self.fields.iter().map(|f| {
+            let (field, ty, params) = (&f.name, &f.ty, f.params());
+            quote! {
+                #field: match &summary.#field {
+                    ::core::option::Option::Some(summary) => {
+                        let delta = ::freenet_stdlib::contract_composition::ContractComponent::delta(
+                            &self.#field,
+                            #params,
+                            summary,
+                        )?;
+                        if <#ty as ::freenet_stdlib::contract_composition::ContractComponent>::is_unchanged(&delta) {
+                            ::core::option::Option::None
+                        } else {
+                            ::core::option::Option::Some(delta)
+                        }
+                    }
+                    ::core::option::Option::None => ::core::option::Option::None,
+                },
+            }
+        });
+
+        quote! {
+            #contract_attr
+            impl ::freenet_stdlib::contract_composition::ContractComponent for #name {
+                type Context = #context;
+                type Parameters = #parameters;
+                type Delta = #delta;
+                type Summary = #summary;
+
+                fn verify<Child, Ctx>(
+                    &self,
+                    parameters: &Self::Parameters,
+                    _context: &Ctx,
+                    related: &::freenet_stdlib::prelude::RelatedContractsContainer,
+                ) -> ::core::result::Result<
+                    ::freenet_stdlib::prelude::ValidateResult,
+                    ::freenet_stdlib::prelude::ContractError,
+                >
+                where
+                    Child: ::freenet_stdlib::contract_composition::ContractComponent,
+                    Self::Context: for<'x> ::core::convert::From<&'x Ctx>,
+                {
+                    #(#verify)*
+                    ::core::result::Result::Ok(::freenet_stdlib::prelude::ValidateResult::Valid)
+                }
+
+                fn merge(
+                    &mut self,
+                    parameters: &Self::Parameters,
+                    update: &::freenet_stdlib::contract_composition::TypedUpdateData<Self>,
+                    related: &::freenet_stdlib::prelude::RelatedContractsContainer,
+                ) -> ::freenet_stdlib::typed_contract::MergeResult {
+                    #(#merge)*
+                    ::freenet_stdlib::typed_contract::MergeResult::Success
+                }
+
+                fn summarize<ParentSummary>(
+                    &self,
+                    parameters: &Self::Parameters,
+                    summary: &mut ParentSummary,
+                ) -> ::core::result::Result<(), ::freenet_stdlib::prelude::ContractError>
+                where
+                    ParentSummary: ::freenet_stdlib::contract_composition::Mergeable<Self::Summary>,
+                {
+                    let mut this = <#summary as ::core::default::Default>::default();
+                    #(#summarize)*
+                    ::freenet_stdlib::contract_composition::Mergeable::merge(summary, this);
+                    ::core::result::Result::Ok(())
+                }
+
+                fn delta(
+                    &self,
+                    parameters: &Self::Parameters,
+                    summary: &Self::Summary,
+                ) -> ::core::result::Result<Self::Delta, ::freenet_stdlib::prelude::ContractError> {
+                    ::core::result::Result::Ok(#delta {
+                        #(#deltas)*
+                    })
+                }
+
+                fn is_unchanged(delta: &Self::Delta) -> bool {
+                    delta.is_empty()
+                }
+            }
+        }
+    }
+}
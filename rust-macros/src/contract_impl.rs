@@ -92,10 +92,35 @@ pub(crate) fn contract_ffi_impl(
     let mut impl_trait = ImplTrait {
         type_name,
         children: vec![],
+        query: false,
     };
 
     if let ContractType::Raw = c_type {
-        let ffi = impl_trait.gen_extern_functions();
+        impl_trait.query = input
+            .items
+            .iter()
+            .any(|item| matches!(item, ImplItem::Fn(f) if f.sig.ident == "query_state"));
+        let ffi = impl_trait.gen_extern_functions(quote!(Raw));
+        return quote! {
+            #input
+            #ffi
+        }
+        .into();
+    }
+
+    if let ContractType::Owned = c_type {
+        if !args.args.is_empty() {
+            return quote_spanned! {
+                attr_span =>
+                compile_error!("owner controlled contracts do not take arguments");
+            }
+            .into();
+        }
+        // the contract exported is the signed wrapper, which always uses bincode
+        let inner = &impl_trait.type_name;
+        impl_trait.type_name =
+            syn::parse_quote!(::freenet_stdlib::owned_contract::OwnedState<#inner>);
+        let ffi = impl_trait.gen_extern_functions(quote!(Bincode));
         return quote! {
             #input
             #ffi
@@ -151,6 +176,16 @@ pub(crate) fn contract_ffi_impl(
                     .into();
                 }
             },
+            syn::Meta::Path(path) if path.is_ident("query") => {
+                if impl_trait.query {
+                    return quote_spanned! {
+                        path.span() =>
+                        compile_error!("`query` specified more than once");
+                    }
+                    .into();
+                }
+                impl_trait.query = true;
+            }
             syn::Meta::NameValue(MetaNameValue {
                 path,
                 value: syn::Expr::Path(type_path),
@@ -196,7 +231,7 @@ pub(crate) fn contract_ffi_impl(
             quote!()
         };
         let contract_iface = impl_trait.gen_typed_contract_iface();
-        let ffi = impl_trait.gen_extern_functions();
+        let ffi = impl_trait.gen_extern_functions(abi_encoder(encoder));
         return quote! {
             #input
             #serialization_adapter
@@ -276,8 +311,15 @@ pub(crate) fn contract_ffi_impl(
     impl_trait.children = children;
 
     if let ContractType::Composable = c_type {
+        if impl_trait.query {
+            return quote_spanned! {
+                attr_span =>
+                compile_error!("`query` is only supported by typed contracts");
+            }
+            .into();
+        }
         let contract_iface = impl_trait.gen_composer_contract_iface(encoder);
-        let ffi = impl_trait.gen_extern_functions();
+        let ffi = impl_trait.gen_extern_functions(abi_encoder(Some(encoder)));
         let serialization_adapter = impl_trait.gen_serialization_adapter(&asoc_types, encoder);
         return quote! {
             #input
@@ -303,9 +345,29 @@ pub(crate) fn contract_ffi_impl(
     }
 }
 
+/// Maps the `encoder` argument to the `AbiEncoder` advertised in the ABI descriptor.
+fn abi_encoder(encoder: Option<&syn::Path>) -> TokenStream {
+    let Some(encoder) = encoder else {
+        // the `EncodingAdapter` impl is hand written, so the encoder can't be known
+        return quote!(Custom);
+    };
+    match encoder
+        .segments
+        .last()
+        .map(|s| s.ident.to_string())
+        .as_deref()
+    {
+        Some("BincodeEncoder") => quote!(Bincode),
+        Some("JsonEncoder") => quote!(Json),
+        _ => quote!(Custom),
+    }
+}
+
 struct ImplTrait {
     type_name: TypePath,
     children: Vec<TypePath>,
+    /// Whether the contract answers read-only queries, exporting `query_state`.
+    query: bool,
 }
 
 impl ImplTrait {
@@ -338,6 +400,22 @@ impl ImplTrait {
 
     fn gen_typed_contract_iface(&self) -> TokenStream {
         let type_name = &self.type_name;
+        let query_fn = if self.query {
+            quote! {
+                fn query_state(
+                    parameters: ::freenet_stdlib::prelude::Parameters<'static>,
+                    state: ::freenet_stdlib::prelude::State<'static>,
+                    query: ::freenet_stdlib::prelude::StateQuery<'static>,
+                ) -> ::core::result::Result<
+                    ::freenet_stdlib::prelude::StateQueryResult<'static>,
+                    ::freenet_stdlib::prelude::ContractError,
+                > {
+                    ::freenet_stdlib::typed_contract::inner_query_state::<#type_name>(parameters, state, query)
+                }
+            }
+        } else {
+            quote!()
+        };
         quote! {
             impl ::freenet_stdlib::prelude::ContractInterface for #type_name {
                 fn validate_state(
@@ -382,6 +460,8 @@ impl ImplTrait {
                 > {
                     ::freenet_stdlib::typed_contract::inner_state_delta::<#type_name>(parameters, state, summary)
                 }
+
+                #query_fn
             }
         }
     }
@@ -397,12 +477,7 @@ impl ImplTrait {
                     <#type_name as ::freenet_stdlib::contract_composition::ContractComponent>::Context,
                 >(parameters.clone(), state.clone(), related.clone())? {
                     ::freenet_stdlib::prelude::ValidateResult::Valid => {}
-                    ::freenet_stdlib::prelude::ValidateResult::Invalid => {
-                        return ::core::result::Result::Ok(::freenet_stdlib::prelude::ValidateResult::Invalid)
-                    }
-                    ::freenet_stdlib::prelude::ValidateResult::RequestRelated(req) => {
-                        return ::core::result::Result::Ok(::freenet_stdlib::prelude::ValidateResult::RequestRelated(req))
-                    }
+                    other => return ::core::result::Result::Ok(other),
                 }
             }
         });
@@ -421,6 +496,35 @@ impl ImplTrait {
             }}
         });
 
+        // without children the component validates and merges its own fields, as the
+        // ones implemented with `#[derive(ContractComponent)]` do
+        let (validate_state_body, update_state_body) = if self.children.is_empty() {
+            (
+                quote! {
+                    ::freenet_stdlib::contract_composition::from_bytes::inner_validate_component::<
+                        #type_name,
+                    >(parameters, state, related)
+                },
+                quote! {
+                    ::freenet_stdlib::contract_composition::from_bytes::inner_update_component::<
+                        #type_name,
+                    >(parameters, state, data)
+                },
+            )
+        } else {
+            (
+                quote! {
+                    #(#validate_state_impl)*
+                    ::core::result::Result::Ok(::freenet_stdlib::prelude::ValidateResult::Valid)
+                },
+                quote! {
+                    let mut final_update = state;
+                    #(#update_state_impl)*
+                    Ok(::freenet_stdlib::prelude::UpdateModification::valid(final_update))
+                },
+            )
+        };
+
         quote! {
             impl ::freenet_stdlib::prelude::ContractInterface for #type_name {
                 fn validate_state(
@@ -431,8 +535,7 @@ impl ImplTrait {
                     ::freenet_stdlib::prelude::ValidateResult,
                     ::freenet_stdlib::prelude::ContractError,
                 > {
-                    #(#validate_state_impl)*
-                    ::core::result::Result::Ok(::freenet_stdlib::prelude::ValidateResult::Valid)
+                    #validate_state_body
                 }
 
                 fn update_state(
@@ -443,9 +546,7 @@ impl ImplTrait {
                     ::freenet_stdlib::prelude::UpdateModification<'static>,
                     ::freenet_stdlib::prelude::ContractError,
                 > {
-                    let mut final_update = state;
-                    #(#update_state_impl)*
-                    Ok(::freenet_stdlib::prelude::UpdateModification::valid(final_update))
+                    #update_state_body
                 }
 
                 fn summarize_state(
@@ -476,6 +577,9 @@ impl ImplTrait {
                     let delta = ::freenet_stdlib::contract_composition::from_bytes::inner_state_delta::<
                         #type_name,
                     >(parameters.clone(), state.clone(), summary.clone())?;
+                    if <#type_name as ::freenet_stdlib::contract_composition::ContractComponent>::is_unchanged(&delta) {
+                        return Ok(::freenet_stdlib::prelude::StateDelta::from(::std::vec::Vec::new()));
+                    }
                     let serializable_delta = <#type_name as ::freenet_stdlib::prelude::EncodingAdapter>::Delta::from(delta);
                     let encoded_delta = #encoder::serialize(&serializable_delta)?;
                     Ok(encoded_delta.into())
@@ -484,16 +588,45 @@ impl ImplTrait {
         }
     }
 
-    fn gen_extern_functions(&self) -> TokenStream {
+    fn gen_extern_functions(&self, encoder: TokenStream) -> TokenStream {
+        let abi_descriptor_fn = self.gen_abi_descriptor_fn(encoder);
         let validate_state_fn = self.gen_validate_state_fn();
         let update_fn = self.gen_update_state_fn();
         let summarize_fn = self.gen_summarize_state_fn();
         let get_delta_fn = self.gen_get_state_delta();
+        let query_fn = if self.query {
+            self.gen_query_state_fn()
+        } else {
+            quote!()
+        };
         quote! {
+            #abi_descriptor_fn
             #validate_state_fn
             #update_fn
             #summarize_fn
             #get_delta_fn
+            #query_fn
+        }
+    }
+
+    fn gen_abi_descriptor_fn(&self, encoder: TokenStream) -> TokenStream {
+        let ret = self.ffi_ret_type();
+        let features = if self.query {
+            quote!(::freenet_stdlib::memory::wasm_interface::abi_features::QUERY)
+        } else {
+            quote!(0)
+        };
+        quote! {
+            #[no_mangle]
+            #[cfg(feature = "freenet-main-contract")]
+            pub extern "C" fn __frnt__contract_abi() -> #ret {
+                static ABI_DESCRIPTOR: ::freenet_stdlib::memory::wasm_interface::AbiDescriptor =
+                    ::freenet_stdlib::memory::wasm_interface::AbiDescriptor::current(
+                        ::freenet_stdlib::memory::wasm_interface::AbiEncoder::#encoder,
+                    )
+                    .with_features(#features);
+                ABI_DESCRIPTOR.as_raw()
+            }
         }
     }
 
@@ -544,4 +677,16 @@ impl ImplTrait {
             }
         }
     }
+
+    fn gen_query_state_fn(&self) -> TokenStream {
+        let type_name = &self.type_name;
+        let ret = self.ffi_ret_type();
+        quote! {
+            #[no_mangle]
+            #[cfg(feature = "freenet-main-contract")]
+            pub extern "C" fn query_state(parameters: i64, state: i64, query: i64) -> #ret {
+                ::freenet_stdlib::memory::wasm_interface::inner_query_state::<#type_name>(parameters, state, query)
+            }
+        }
+    }
 }
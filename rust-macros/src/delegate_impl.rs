@@ -1,15 +1,63 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{ItemImpl, Type, TypePath};
+use syn::{spanned::Spanned, ItemImpl, MetaNameValue, Type, TypePath};
 
-pub fn ffi_impl_wrap(item: &ItemImpl) -> TokenStream {
+use crate::AttributeArgs;
+
+pub fn ffi_impl_wrap(item: &ItemImpl, args: &AttributeArgs) -> syn::Result<TokenStream> {
     let type_name = match &*item.self_ty {
         Type::Path(p) => p.clone(),
         _ => panic!(),
     };
+
+    let mut encoder = None;
+    for m in args.args.iter() {
+        match m {
+            syn::Meta::NameValue(MetaNameValue {
+                path,
+                value: syn::Expr::Path(type_path),
+                ..
+            }) if path.get_ident().map(|id| id == "encoder").unwrap_or(false) => {
+                if encoder.is_some() {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        "only one encoder protocol can be specified",
+                    ));
+                }
+                encoder = Some(&type_path.path);
+            }
+            other => return Err(syn::Error::new(other.span(), "argument not allowed")),
+        }
+    }
+
+    let typed = item
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+        .map(|segment| segment.ident == "TypedDelegate")
+        .unwrap_or(false);
+
     let s = ImplStruct { type_name };
     let process_fn = s.gen_process_fn();
-    quote!(#process_fn)
+    if !typed {
+        if encoder.is_some() {
+            return Err(syn::Error::new(
+                args.args.span(),
+                "encoder can only be specified for `TypedDelegate` implementations",
+            ));
+        }
+        return Ok(quote!(#process_fn));
+    }
+
+    let serialization_adapter = encoder
+        .map(|encoder| s.gen_serialization_adapter(encoder))
+        .unwrap_or_default();
+    let delegate_iface = s.gen_typed_delegate_iface();
+    Ok(quote! {
+        #serialization_adapter
+        #delegate_iface
+        #process_fn
+    })
 }
 
 struct ImplStruct {
@@ -21,6 +69,37 @@ impl ImplStruct {
         quote!(i64)
     }
 
+    fn gen_serialization_adapter(&self, encoder: &syn::Path) -> TokenStream {
+        let type_name = &self.type_name;
+        quote! {
+            impl ::freenet_stdlib::typed_delegate::DelegateEncodingAdapter for #type_name {
+                type ParametersEncoder = #encoder<<Self as ::freenet_stdlib::typed_delegate::TypedDelegate>::Parameters>;
+                type RequestEncoder = #encoder<<Self as ::freenet_stdlib::typed_delegate::TypedDelegate>::Request>;
+                type ResponseEncoder = #encoder<<Self as ::freenet_stdlib::typed_delegate::TypedDelegate>::Response>;
+                type ContextEncoder = #encoder<<Self as ::freenet_stdlib::typed_delegate::TypedDelegate>::Context>;
+            }
+        }
+    }
+
+    fn gen_typed_delegate_iface(&self) -> TokenStream {
+        let type_name = &self.type_name;
+        quote! {
+            impl ::freenet_stdlib::prelude::DelegateInterface for #type_name {
+                fn process(
+                    ctx: &mut ::freenet_stdlib::prelude::DelegateCtx,
+                    parameters: ::freenet_stdlib::prelude::Parameters<'static>,
+                    origin: ::core::option::Option<::freenet_stdlib::prelude::MessageOrigin>,
+                    message: ::freenet_stdlib::prelude::InboundDelegateMsg,
+                ) -> ::core::result::Result<
+                    ::std::vec::Vec<::freenet_stdlib::prelude::OutboundDelegateMsg>,
+                    ::freenet_stdlib::prelude::DelegateError,
+                > {
+                    ::freenet_stdlib::typed_delegate::inner_process::<#type_name>(ctx, parameters, origin, message)
+                }
+            }
+        }
+    }
+
     fn gen_process_fn(&self) -> TokenStream {
         let type_name = &self.type_name;
         let ret = self.ffi_ret_type();
@@ -70,6 +149,14 @@ impl ImplStruct {
                 // before calling this function, so the host functions are available.
                 let mut ctx = unsafe { ::freenet_stdlib::prelude::DelegateCtx::__new() };
 
+                #[cfg(feature = "trace")]
+                let result = ::freenet_stdlib::delegate_trace::record::<#type_name>(
+                    &mut ctx,
+                    parameters,
+                    origin,
+                    inbound
+                );
+                #[cfg(not(feature = "trace"))]
                 let result = <#type_name as ::freenet_stdlib::prelude::DelegateInterface>::process(
                     &mut ctx,
                     parameters,
@@ -1,9 +1,10 @@
 use quote::{quote, quote_spanned};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{ItemImpl, Meta, Token};
+use syn::{DeriveInput, ItemImpl, Meta, Token};
 
 pub(crate) mod common;
+mod component_derive;
 mod contract_impl;
 mod delegate_impl;
 
@@ -36,9 +37,13 @@ enum ContractType {
     Raw,
     Typed,
     Composable,
+    Owned,
 }
 
 /// Generate the necessary code for the WASM runtime to interact with your contract ergonomically and safely.
+///
+/// A `ContractInterface` impl which defines `query_state` also gets it exported. Implementations
+/// of `TypedContract` opt in with `#[contract(query)]`, which requires a `QueryableContract` impl.
 #[proc_macro_attribute]
 pub fn contract(
     args: proc_macro::TokenStream,
@@ -58,6 +63,7 @@ pub fn contract(
                 "ContractInterface" => ContractType::Raw,
                 "TypedContract" => ContractType::Typed,
                 "ContractComponent" => ContractType::Composable,
+                "OwnerControlled" => ContractType::Owned,
                 _ => {
                     return proc_macro::TokenStream::from(quote_spanned! {
                         segment.ident.span() =>
@@ -74,15 +80,21 @@ pub fn contract(
     }
 }
 
-/// Generate the necessary code for the WASM runtime to interact with your contract ergonomically and safely.
+/// Generate the necessary code for the WASM runtime to interact with your delegate ergonomically and safely.
+///
+/// Implementations of `TypedDelegate` also get their `DelegateInterface` generated, and
+/// with `#[delegate(encoder = BincodeEncoder)]` (or `JsonEncoder`) their `DelegateEncodingAdapter`.
 #[proc_macro_attribute]
 pub fn delegate(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let _args = syn::parse_macro_input!(args as AttributeArgs);
+    let args = syn::parse_macro_input!(args as AttributeArgs);
     let input = syn::parse_macro_input!(input as ItemImpl);
-    let output = delegate_impl::ffi_impl_wrap(&input);
+    let output = match delegate_impl::ffi_impl_wrap(&input, &args) {
+        Ok(output) => output,
+        Err(err) => return err.into_compile_error().into(),
+    };
     // println!("{}", quote!(#input));
     // println!("{output}");
     proc_macro::TokenStream::from(quote! {
@@ -90,3 +102,30 @@ pub fn delegate(
         #output
     })
 }
+
+/// Derive `ContractComponent` for a struct whose fields are all contract components.
+///
+/// Generates the `<Name>Summary` and `<Name>Delta` structs, with an optional summary and
+/// delta per field, their `Mergeable` impls, and a `ContractComponent` impl verifying,
+/// merging, summarizing and diffing each field in declaration order. Fields whose delta
+/// is unchanged, as told by `ContractComponent::is_unchanged`, are `None` in the delta.
+///
+/// Container attributes, in `#[component(...)]`:
+/// - `parameters = Type`: the `Parameters` of the component (required).
+/// - `context = Type`: the `Context` of the component, `NoContext` by default.
+/// - `contract(encoder = BincodeEncoder)`: also export the component as the contract of
+///   the crate, as `#[contract]` does.
+///
+/// Field attributes, in `#[component(...)]`:
+/// - `params = field`: the field of the parent parameters holding the parameters of this
+///   child. Otherwise they are converted with `From<&Parameters>`.
+/// - `merge = "merge"` (default) or `merge = "replace"`: how two summaries or deltas of
+///   this child are combined, through its `Mergeable` impl or by keeping the newer one.
+#[proc_macro_derive(ContractComponent, attributes(component))]
+pub fn contract_component(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match component_derive::derive_component(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
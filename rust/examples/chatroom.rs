@@ -1,8 +1,9 @@
-#[cfg(feature = "unstable")]
 mod example {
 
     use freenet_macros::contract;
-    use freenet_stdlib::contract_composition::{ComponentParameter, ContractComponent, Mergeable};
+    use freenet_stdlib::contract_composition::{
+        ComponentParameter, ContractComponent, KeyedDelta, Mergeable,
+    };
     use freenet_stdlib::typed_contract::BincodeEncoder;
     use serde::{Deserialize, Serialize};
 
@@ -41,6 +42,12 @@ mod example {
         }
     }
 
+    impl<'x, T> From<&'x dependency_2::ChatDelta> for KeyedDelta<usize, T, dependency_2::ChatDelta> {
+        fn from(_: &'x dependency_2::ChatDelta) -> Self {
+            unimplemented!()
+        }
+    }
+
     #[derive(Serialize, Deserialize)]
     pub struct ChatRoomSummary;
 
@@ -1,4 +1,4 @@
-#[cfg(feature = "unstable")]
+#[allow(dead_code)]
 mod example {
 
     mod parent {
@@ -7,13 +7,23 @@ mod example {
         // children would be like a different dependency crate which implements composable types
         use super::children::{self, *};
 
-        use freenet_stdlib::{contract_composition::*, prelude::*, typed_contract::MergeResult};
+        use freenet_stdlib::{contract_composition::*, crdt::GCounter, prelude::*};
         use serde::{Deserialize, Serialize};
 
-        #[derive(Serialize, Deserialize)]
+        /// Derives the component impl, `ParentContractSummary` and `ParentContractDelta`,
+        /// and exports it as the contract of the crate.
+        #[derive(Serialize, Deserialize, Clone, ContractComponent)]
+        #[component(
+            parameters = ParentContractParams,
+            contract(encoder = BincodeEncoder)
+        )]
         pub struct ParentContract {
+            #[component(params = contract_b_0_params)]
             contract_b_0: ChildComponent,
+            #[component(params = contract_b_1_params, merge = "replace")]
             contract_b_1: ChildComponent,
+            // replicated types from `crdt` don't take parameters
+            visits: GCounter,
         }
 
         #[derive(Serialize, Deserialize)]
@@ -27,157 +37,6 @@ mod example {
             }
         }
 
-        #[derive(Serialize, Deserialize)]
-        pub struct ParentContractSummary;
-        impl<'a> From<&'a ParentContract> for ParentContractSummary {
-            fn from(_: &'a ParentContract) -> Self {
-                unimplemented!()
-            }
-        }
-        impl<'a> From<&'a ParentContractSummary> for ChildComponentSummary {
-            fn from(_: &'a ParentContractSummary) -> Self {
-                unimplemented!()
-            }
-        }
-        impl Mergeable<ChildComponentSummary> for ParentContractSummary {
-            fn merge(&mut self, _: ChildComponentSummary) {
-                unimplemented!()
-            }
-        }
-        impl Mergeable<ParentContractSummary> for ParentContractSummary {
-            fn merge(&mut self, _: ParentContractSummary) {
-                unimplemented!()
-            }
-        }
-
-        #[derive(Serialize, Deserialize)]
-        pub struct ParentContractDelta {
-            contract_b_0: ChildComponentDelta,
-            contract_b_1: ChildComponentDelta,
-        }
-        impl<'a> From<&'a ParentContract> for ChildComponent {
-            fn from(_: &'a ParentContract) -> Self {
-                unimplemented!()
-            }
-        }
-        impl<'a> From<&'a ParentContractDelta> for ChildComponentDelta {
-            fn from(_: &'a ParentContractDelta) -> Self {
-                unimplemented!()
-            }
-        }
-        impl<'a> From<&'a ParentContractParams> for ChildComponentParams {
-            fn from(_: &'a ParentContractParams) -> Self {
-                unimplemented!()
-            }
-        }
-
-        #[contract(children(ChildComponent, ChildComponent), encoder = BincodeEncoder)]
-        // todo: this impl block would be derived ideally, we can have a derive macro
-        // in the struct where the associated types need to be specified
-        impl ContractComponent for ParentContract {
-            type Context = NoContext;
-            type Parameters = ParentContractParams;
-            type Delta = ParentContractDelta;
-            type Summary = ParentContractSummary;
-
-            fn verify<Child, Ctx>(
-                &self,
-                parameters: &Self::Parameters,
-                _ctx: &Ctx,
-                related: &RelatedContractsContainer,
-            ) -> Result<ValidateResult, ContractError>
-            where
-                Child: ContractComponent,
-                Self::Context: for<'x> From<&'x Ctx>,
-            {
-                <ChildComponent as ContractComponent>::verify::<ChildComponent, _>(
-                    &self.contract_b_0,
-                    &<ChildComponent as ContractComponent>::Parameters::from(parameters),
-                    self,
-                    related,
-                )?;
-                Ok(ValidateResult::Valid)
-            }
-
-            fn merge(
-                &mut self,
-                parameters: &Self::Parameters,
-                update_data: &TypedUpdateData<Self>,
-                related: &RelatedContractsContainer,
-            ) -> MergeResult {
-                {
-                    let sub_update: TypedUpdateData<ChildComponent> =
-                        TypedUpdateData::from_other(update_data);
-                    match freenet_stdlib::contract_composition::ContractComponent::merge(
-                        &mut self.contract_b_0,
-                        &parameters.into(),
-                        &sub_update,
-                        related,
-                    ) {
-                        MergeResult::Success => {}
-                        MergeResult::RequestRelated(req) => {
-                            return MergeResult::RequestRelated(req)
-                        }
-                        MergeResult::Error(e) => return MergeResult::Error(e),
-                    }
-                }
-                {
-                    let sub_update: TypedUpdateData<ChildComponent> =
-                        TypedUpdateData::from_other(update_data);
-                    match freenet_stdlib::contract_composition::ContractComponent::merge(
-                        &mut self.contract_b_1,
-                        &parameters.into(),
-                        &sub_update,
-                        related,
-                    ) {
-                        MergeResult::Success => {}
-                        MergeResult::RequestRelated(req) => {
-                            return MergeResult::RequestRelated(req)
-                        }
-                        MergeResult::Error(e) => return MergeResult::Error(e),
-                    }
-                }
-                MergeResult::Success
-            }
-
-            fn summarize<ParentSummary>(
-                &self,
-                parameters: &Self::Parameters,
-                summary: &mut ParentSummary,
-            ) -> Result<(), ContractError>
-            where
-                ParentSummary: Mergeable<<Self as ContractComponent>::Summary>,
-            {
-                // todo: probably need ParentSummary to impl From<&Self>?
-                let mut this_summary = ParentContractSummary;
-                self.contract_b_0
-                    .summarize(&parameters.into(), &mut this_summary)?;
-                self.contract_b_1
-                    .summarize(&parameters.into(), &mut this_summary)?;
-                summary.merge(this_summary);
-                Ok(())
-            }
-
-            fn delta(
-                &self,
-                parameters: &Self::Parameters,
-                summary: &Self::Summary,
-            ) -> Result<Self::Delta, ContractError> {
-                // todo: this impl may be probematic to derive, specially getting the return type
-                // maybe requires adding an other transformation bound
-                let contract_b_0 = self
-                    .contract_b_0
-                    .delta(&parameters.into(), &summary.into())?;
-                let contract_b_1 = self
-                    .contract_b_0
-                    .delta(&parameters.into(), &summary.into())?;
-                Ok(ParentContractDelta {
-                    contract_b_0,
-                    contract_b_1,
-                })
-            }
-        }
-
         impl<'x> From<&'x ParentContract> for children::PubKey {
             fn from(_: &'x ParentContract) -> Self {
                 children::PubKey
@@ -195,7 +54,7 @@ mod example {
         };
         use serde::{Deserialize, Serialize};
 
-        #[derive(Serialize, Deserialize)]
+        #[derive(Serialize, Deserialize, Clone)]
         pub struct ChildComponent {}
 
         #[derive(Serialize, Deserialize, Clone)]
@@ -209,12 +68,20 @@ mod example {
             }
         }
 
-        #[derive(Serialize, Deserialize)]
+        #[derive(Serialize, Deserialize, Clone)]
         pub struct ChildComponentSummary;
 
-        #[derive(Serialize, Deserialize)]
+        impl Mergeable<ChildComponentSummary> for ChildComponentSummary {
+            fn merge(&mut self, _: ChildComponentSummary) {}
+        }
+
+        #[derive(Serialize, Deserialize, Clone)]
         pub struct ChildComponentDelta;
 
+        impl Mergeable<ChildComponentDelta> for ChildComponentDelta {
+            fn merge(&mut self, _: ChildComponentDelta) {}
+        }
+
         pub struct PubKey;
 
         impl From<ChildComponentParams> for PubKey {
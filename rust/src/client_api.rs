@@ -135,6 +135,7 @@ impl WsApiError {
             &mut builder,
             &ErrorArgs {
                 msg: Some(msg_offset),
+                ..Default::default()
             },
         );
         let res = HostResponse::create(
@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
@@ -40,6 +41,8 @@ impl WebApi {
         let eh = Rc::new(RefCell::new(error_handler.clone()));
         let result_handler = Rc::new(RefCell::new(result_handler));
         let reassembly = Rc::new(RefCell::new(super::streaming::ReassemblyBuffer::new()));
+        // Headers of the streams whose chunks carry raw bytes, keyed by stream id.
+        let mut raw_streams = HashMap::new();
 
         let onmessage_callback = Closure::<dyn FnMut(_)>::new(move |e: MessageEvent| {
             // Binary frames arrive as ArrayBuffer (`binaryType` is set in
@@ -61,7 +64,7 @@ impl WebApi {
             };
             let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
 
-            use super::client_events::HostResponse;
+            use super::client_events::{HostResponse, StreamContent};
 
             let response: HostResult = match bincode::deserialize(&bytes) {
                 Ok(val) => val,
@@ -75,10 +78,15 @@ impl WebApi {
             };
 
             match response {
-                Ok(HostResponse::StreamHeader { .. }) => {
+                Ok(HostResponse::StreamHeader {
+                    stream_id, content, ..
+                }) => {
                     // StreamHeader is metadata only — the following StreamChunks
                     // will be reassembled transparently by the ReassemblyBuffer.
                     // Browser incremental streaming is not yet supported.
+                    if matches!(content, StreamContent::GetRange { .. }) {
+                        raw_streams.insert(stream_id, content);
+                    }
                 }
                 Ok(HostResponse::StreamChunk {
                     stream_id,
@@ -94,6 +102,16 @@ impl WebApi {
                         .receive_chunk(stream_id, index, total, data);
                     match outcome {
                         Ok(Some(complete)) => {
+                            let complete = match raw_streams.remove(&stream_id) {
+                                Some(content) => match content.raw_response(complete) {
+                                    Ok(response) => {
+                                        result_handler.borrow_mut()(Ok(response));
+                                        return;
+                                    }
+                                    Err(complete) => complete,
+                                },
+                                None => complete,
+                            };
                             let inner: HostResult = match bincode::deserialize(&complete) {
                                 Ok(val) => val,
                                 Err(err) => {
@@ -109,6 +127,7 @@ impl WebApi {
                         Ok(None) => (), // more chunks needed
                         Err(e) => {
                             reassembly.borrow_mut().remove_stream(stream_id);
+                            raw_streams.remove(&stream_id);
                             eh.borrow_mut()(Error::ConnectionError(serde_json::json!({
                                 "error": format!("{e}"),
                                 "source": "streaming reassembly"
@@ -24,17 +24,22 @@ use crate::generated::common::{
     WasmContractV1Args,
 };
 use crate::generated::host_response::{
-    finish_host_response_buffer, ClientResponse as FbsClientResponse, ClientResponseArgs,
-    ContextUpdated as FbsContextUpdated, ContextUpdatedArgs,
-    ContractResponse as FbsContractResponse, ContractResponseArgs, ContractResponseType,
-    DelegateKey as FbsDelegateKey, DelegateKeyArgs, DelegateResponse as FbsDelegateResponse,
-    DelegateResponseArgs, GetResponse as FbsGetResponse, GetResponseArgs,
-    HostResponse as FbsHostResponse, HostResponseArgs, HostResponseType, NotFound as FbsNotFound,
-    NotFoundArgs, Ok as FbsOk, OkArgs, OutboundDelegateMsg as FbsOutboundDelegateMsg,
-    OutboundDelegateMsgArgs, OutboundDelegateMsgType, PutResponse as FbsPutResponse,
-    PutResponseArgs, RequestUserInput as FbsRequestUserInput, RequestUserInputArgs,
+    finish_host_response_buffer, CancelTimer as FbsCancelTimer, CancelTimerArgs,
+    ClientResponse as FbsClientResponse, ClientResponseArgs, ContextUpdated as FbsContextUpdated,
+    ContextUpdatedArgs, ContractResponse as FbsContractResponse, ContractResponseArgs,
+    ContractResponseType, DelegateKey as FbsDelegateKey, DelegateKeyArgs,
+    DelegateResponse as FbsDelegateResponse, DelegateResponseArgs,
+    GetMetadataResponse as FbsGetMetadataResponse, GetMetadataResponseArgs,
+    GetRangeResponse as FbsGetRangeResponse, GetRangeResponseArgs, GetResponse as FbsGetResponse,
+    GetResponseArgs, HostResponse as FbsHostResponse, HostResponseArgs, HostResponseType,
+    NotFound as FbsNotFound, NotFoundArgs, Ok as FbsOk, OkArgs,
+    OutboundDelegateMsg as FbsOutboundDelegateMsg, OutboundDelegateMsgArgs,
+    OutboundDelegateMsgType, PutResponse as FbsPutResponse, PutResponseArgs,
+    QueryResponse as FbsQueryResponse, QueryResponseArgs, RequestUserInput as FbsRequestUserInput,
+    RequestUserInputArgs, ScheduleTimer as FbsScheduleTimer, ScheduleTimerArgs,
     StreamChunk as FbsHostStreamChunk, StreamChunkArgs as FbsHostStreamChunkArgs,
     UpdateNotification as FbsUpdateNotification, UpdateNotificationArgs,
+    UpdatePreconditionFailed as FbsUpdatePreconditionFailed, UpdatePreconditionFailedArgs,
     UpdateResponse as FbsUpdateResponse, UpdateResponseArgs,
 };
 use crate::prelude::ContractContainer::Wasm;
@@ -43,10 +48,11 @@ use crate::prelude::UpdateData::{
     Delta, RelatedDelta, RelatedState, RelatedStateAndDelta, State, StateAndDelta,
 };
 use crate::{
-    delegate_interface::{DelegateKey, InboundDelegateMsg, OutboundDelegateMsg},
+    delegate_interface::{DelegateKey, InboundDelegateMsg, OutboundDelegateMsg, TimerDeadline},
     prelude::{
-        ContractInstanceId, ContractKey, DelegateContainer, Parameters, RelatedContracts,
-        SecretsId, StateSummary, UpdateData, WrappedState,
+        ContractInstanceId, ContractKey, DelegateContainer, InvalidReason, Parameters,
+        RelatedContracts, SecretsId, StateQuery, StateQueryResult, StateSummary, UpdateData,
+        WrappedState,
     },
     versioning::ContractContainer,
 };
@@ -63,16 +69,22 @@ impl ClientError {
         use crate::generated::host_response::{Error, ErrorArgs};
         let mut builder = flatbuffers::FlatBufferBuilder::new();
         let msg_offset = builder.create_string(&self.to_string());
+        let reason = self.reason();
+        let field_offset = reason
+            .and_then(|reason| reason.field.as_deref())
+            .map(|field| builder.create_string(field));
         let err_offset = Error::create(
             &mut builder,
             &ErrorArgs {
                 msg: Some(msg_offset),
+                code: reason.map_or(0, |reason| reason.code.as_u32()),
+                field: field_offset,
             },
         );
         let host_response_offset = FbsHostResponse::create(
             &mut builder,
             &HostResponseArgs {
-                response_type: HostResponseType::Ok,
+                response_type: HostResponseType::Error,
                 response: Some(err_offset.as_union_value()),
             },
         );
@@ -83,6 +95,14 @@ impl ClientError {
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
     }
+
+    /// The reason a contract rejected the request, if that is why it failed.
+    pub fn reason(&self) -> Option<&InvalidReason> {
+        match &*self.kind {
+            ErrorKind::RequestError(RequestError::ContractError(err)) => err.reason(),
+            _ => None,
+        }
+    }
 }
 
 impl From<ErrorKind> for ClientError {
@@ -211,6 +231,16 @@ pub enum ContractError {
     MissingContract {
         key: crate::contract_interface::ContractInstanceId,
     },
+    #[error("put rejected for contract {key}, {reason}")]
+    PutRejected {
+        key: ContractKey,
+        reason: InvalidReason,
+    },
+    #[error("update rejected for contract {key}, {reason}")]
+    UpdateRejected {
+        key: ContractKey,
+        reason: InvalidReason,
+    },
 }
 
 impl ContractError {
@@ -241,6 +271,24 @@ impl ContractError {
             cause: Self::INVALID_PUT.into(),
         }
     }
+
+    /// The contract rejected the state of a put for `reason`.
+    pub fn put_rejected(key: ContractKey, reason: InvalidReason) -> Self {
+        Self::PutRejected { key, reason }
+    }
+
+    /// The contract rejected an update for `reason`.
+    pub fn update_rejected(key: ContractKey, reason: InvalidReason) -> Self {
+        Self::UpdateRejected { key, reason }
+    }
+
+    /// The reason the contract rejected the request, if it did.
+    pub fn reason(&self) -> Option<&InvalidReason> {
+        match self {
+            Self::PutRejected { reason, .. } | Self::UpdateRejected { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
 }
 
 /// A request from a client application to the host.
@@ -298,25 +346,39 @@ impl ClientRequest<'_> {
                             blocking_subscribe,
                         }
                     }
-                    ContractRequest::Update { key, data } => {
+                    ContractRequest::Update {
+                        key,
+                        data,
+                        precondition,
+                    } => {
                         let data = data.into_owned();
-                        ContractRequest::Update { key, data }
+                        ContractRequest::Update {
+                            key,
+                            data,
+                            precondition,
+                        }
                     }
                     ContractRequest::Get {
                         key,
                         return_contract_code,
                         subscribe,
                         blocking_subscribe,
+                        read,
                     } => ContractRequest::Get {
                         key,
                         return_contract_code,
                         subscribe,
                         blocking_subscribe,
+                        read,
                     },
                     ContractRequest::Subscribe { key, summary } => ContractRequest::Subscribe {
                         key,
                         summary: summary.map(StateSummary::into_owned),
                     },
+                    ContractRequest::Query { key, query } => ContractRequest::Query {
+                        key,
+                        query: query.into_owned(),
+                    },
                 };
                 owned.into()
             }
@@ -425,6 +487,10 @@ pub enum ContractRequest<'a> {
         key: ContractKey,
         #[serde(borrow)]
         data: UpdateData<'a>,
+        /// If set, the update is only applied while the current state meets it.
+        /// Otherwise the host answers with [`ContractResponse::UpdatePreconditionFailed`].
+        #[serde(default)]
+        precondition: Option<UpdatePrecondition>,
     },
     /// Fetch the current state from a contract corresponding to the provided key.
     Get {
@@ -439,6 +505,9 @@ pub enum ContractRequest<'a> {
         /// Only meaningful when `subscribe` is true.
         #[serde(default)]
         blocking_subscribe: bool,
+        /// Which part of the state to return, the whole state by default.
+        #[serde(default)]
+        read: StateRead,
     },
     /// Subscribe to the changes in a given contract. Implicitly starts a get operation
     /// if the contract is not present yet.
@@ -447,6 +516,14 @@ pub enum ContractRequest<'a> {
         key: ContractInstanceId,
         summary: Option<StateSummary<'a>>,
     },
+    /// Ask the contract a read-only question about its current state, answered with
+    /// [`ContractResponse::QueryResponse`]. The state is not modified.
+    Query {
+        /// Instance ID of the contract.
+        key: ContractInstanceId,
+        /// Query in the format defined by the contract.
+        query: StateQuery<'a>,
+    },
 }
 
 impl ContractRequest<'_> {
@@ -465,29 +542,144 @@ impl ContractRequest<'_> {
                 subscribe,
                 blocking_subscribe,
             },
-            Self::Update { key, data } => ContractRequest::Update {
+            Self::Update {
+                key,
+                data,
+                precondition,
+            } => ContractRequest::Update {
                 key,
                 data: data.into_owned(),
+                precondition,
             },
             Self::Get {
                 key,
                 return_contract_code: fetch_contract,
                 subscribe,
                 blocking_subscribe,
+                read,
             } => ContractRequest::Get {
                 key,
                 return_contract_code: fetch_contract,
                 subscribe,
                 blocking_subscribe,
+                read,
             },
             Self::Subscribe { key, summary } => ContractRequest::Subscribe {
                 key,
                 summary: summary.map(StateSummary::into_owned),
             },
+            Self::Query { key, query } => ContractRequest::Query {
+                key,
+                query: query.into_owned(),
+            },
+        }
+    }
+}
+
+/// Condition the current state of a contract must meet for a [`ContractRequest::Update`]
+/// to be applied, allowing compare-and-swap updates between concurrent clients.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum UpdatePrecondition {
+    /// The contract summarizes its current state to exactly this summary.
+    Summary(#[serde(deserialize_with = "StateSummary::deser_state_summary")] StateSummary<'static>),
+    /// The BLAKE3 hash of the current state is exactly this one.
+    StateHash([u8; 32]),
+}
+
+impl UpdatePrecondition {
+    /// Expects the current state to be exactly `state`.
+    pub fn state_hash(state: &[u8]) -> Self {
+        Self::StateHash(*blake3::hash(state).as_bytes())
+    }
+
+    /// Checks the precondition against the current `state`.
+    ///
+    /// `summarize` is only called for [`UpdatePrecondition::Summary`], so hosts don't run
+    /// the contract to check a hash.
+    pub fn is_met<E>(
+        &self,
+        state: &[u8],
+        summarize: impl FnOnce() -> Result<StateSummary<'static>, E>,
+    ) -> Result<bool, E> {
+        match self {
+            Self::Summary(expected) => Ok(summarize()? == *expected),
+            Self::StateHash(expected) => Ok(blake3::hash(state).as_bytes() == expected),
+        }
+    }
+
+    /// The precondition of the same kind which the current `state` meets, to report
+    /// back in [`ContractResponse::UpdatePreconditionFailed`].
+    pub fn current<E>(
+        &self,
+        state: &[u8],
+        summarize: impl FnOnce() -> Result<StateSummary<'static>, E>,
+    ) -> Result<Self, E> {
+        match self {
+            Self::Summary(_) => Ok(Self::Summary(summarize()?)),
+            Self::StateHash(_) => Ok(Self::state_hash(state)),
+        }
+    }
+}
+
+/// Part of the contract state a [`ContractRequest::Get`] asks for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum StateRead {
+    /// The whole state, answered with [`ContractResponse::GetResponse`].
+    #[default]
+    Full,
+    /// Only the size and summary of the state, answered with
+    /// [`ContractResponse::GetMetadataResponse`].
+    MetadataOnly,
+    /// `len` bytes of the state starting at `offset`, answered with
+    /// [`ContractResponse::GetRangeResponse`] or streamed as
+    /// [`StreamContent::GetRange`]. The range is clamped to the state.
+    Range { offset: u64, len: u64 },
+}
+
+impl StateRead {
+    /// The bytes of a state of `state_len` bytes covered by this read.
+    pub fn byte_range(&self, state_len: usize) -> std::ops::Range<usize> {
+        match *self {
+            Self::Full => 0..state_len,
+            Self::MetadataOnly => 0..0,
+            Self::Range { offset, len } => {
+                let start = usize::try_from(offset).map_or(state_len, |o| o.min(state_len));
+                let len = usize::try_from(len).unwrap_or(usize::MAX);
+                start..start.saturating_add(len).min(state_len)
+            }
         }
     }
 }
 
+/// Metadata of a contract state, returned for [`StateRead::MetadataOnly`].
+///
+/// The code hash of the contract is part of the key in the response.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct StateMetadata {
+    /// Size of the state in bytes.
+    pub size: u64,
+    /// Summary of the state, if the host computed one.
+    #[serde(deserialize_with = "deser_opt_state_summary")]
+    pub summary: Option<StateSummary<'static>>,
+}
+
+impl StateMetadata {
+    pub fn new(size: u64, summary: Option<StateSummary<'static>>) -> Self {
+        Self { size, summary }
+    }
+}
+
+fn deser_opt_state_summary<'de, D>(deser: D) -> Result<Option<StateSummary<'static>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let summary: Option<StateSummary<'de>> = Deserialize::deserialize(deser)?;
+    Ok(summary.map(StateSummary::into_owned))
+}
+
 impl<'a> From<ContractRequest<'a>> for ClientRequest<'a> {
     fn from(op: ContractRequest<'a>) -> Self {
         ClientRequest::ContractOp(op)
@@ -511,11 +703,26 @@ impl<'a> TryFromFbs<&FbsContractRequest<'a>> for ContractRequest<'a> {
                     let fetch_contract = get.fetch_contract();
                     let subscribe = get.subscribe();
                     let blocking_subscribe = get.blocking_subscribe();
+                    // A zero `range_len` means no range was requested.
+                    let read = match (get.metadata_only(), get.range_len()) {
+                        (false, 0) => StateRead::Full,
+                        (true, 0) => StateRead::MetadataOnly,
+                        (false, len) => StateRead::Range {
+                            offset: get.range_offset(),
+                            len,
+                        },
+                        (true, _) => {
+                            return Err(WsApiError::deserialization(
+                                "Get.metadata_only and Get.range_len are mutually exclusive".into(),
+                            ));
+                        }
+                    };
                     ContractRequest::Get {
                         key,
                         return_contract_code: fetch_contract,
                         subscribe,
                         blocking_subscribe,
+                        read,
                     }
                 }
                 ContractRequestType::Put => {
@@ -538,7 +745,35 @@ impl<'a> TryFromFbs<&FbsContractRequest<'a>> for ContractRequest<'a> {
                     let update = request.contract_request_as_update().unwrap();
                     let key = ContractKey::try_decode_fbs(&update.key())?;
                     let data = UpdateData::try_decode_fbs(&update.data())?.into_owned();
-                    ContractRequest::Update { key, data }
+                    // The TypeScript object API writes every vector, empty when unset, so
+                    // an empty one counts as absent.
+                    let expected_summary = update.expected_summary().filter(|v| !v.is_empty());
+                    let expected_state_hash =
+                        update.expected_state_hash().filter(|v| !v.is_empty());
+                    let precondition = match (expected_summary, expected_state_hash) {
+                        (None, None) => None,
+                        (Some(summary), None) => Some(UpdatePrecondition::Summary(
+                            StateSummary::from(summary.bytes().to_vec()),
+                        )),
+                        (None, Some(hash)) => Some(UpdatePrecondition::StateHash(
+                            crate::client_api::fixed_size_field(
+                                "Update.expected_state_hash",
+                                hash.bytes(),
+                            )?,
+                        )),
+                        (Some(_), Some(_)) => {
+                            return Err(WsApiError::deserialization(
+                                "Update.expected_summary and Update.expected_state_hash are \
+                                 mutually exclusive"
+                                    .into(),
+                            ));
+                        }
+                    };
+                    ContractRequest::Update {
+                        key,
+                        data,
+                        precondition,
+                    }
                 }
                 ContractRequestType::Subscribe => {
                     let subscribe = request.contract_request_as_subscribe().unwrap();
@@ -553,6 +788,16 @@ impl<'a> TryFromFbs<&FbsContractRequest<'a>> for ContractRequest<'a> {
                         .map(|summary_data| StateSummary::from(summary_data.bytes()));
                     ContractRequest::Subscribe { key, summary }
                 }
+                ContractRequestType::Query => {
+                    let query = request.contract_request_as_query().unwrap();
+                    let fbs_key = query.key();
+                    let key = crate::contract_interface::key::instance_id_from_fbs(
+                        "ContractKey.instance.data",
+                        fbs_key.instance().data().bytes(),
+                    )?;
+                    let query = StateQuery::from(query.query().bytes());
+                    ContractRequest::Query { key, query }
+                }
                 // Reachable, not `unreachable!()`: the generated flatbuffers
                 // verifier accepts any unknown union discriminant (`_ => Ok(())`)
                 // and the union type field is a raw `u8` a client can set freely,
@@ -664,6 +909,13 @@ impl Display for ClientRequest<'_> {
                 ContractRequest::Subscribe { key, .. } => {
                     write!(f, "ContractRequest::Subscribe for `{key}`")
                 }
+                ContractRequest::Query { key, query } => {
+                    write!(
+                        f,
+                        "ContractRequest::Query for `{key}` ({} bytes)",
+                        query.size()
+                    )
+                }
             },
             ClientRequest::DelegateOp(op) => match op {
                 DelegateRequest::ApplicationMessages { key, inbound, .. } => {
@@ -712,6 +964,15 @@ impl<'a> TryFromFbs<&FbsDelegateRequest<'a>> for DelegateRequest<'a> {
                         .iter()
                         .map(|msg| InboundDelegateMsg::try_decode_fbs(&msg))
                         .collect::<Result<Vec<_>, _>>()?;
+                    // firings come from the host only, a client must not forge one
+                    if inbound
+                        .iter()
+                        .any(|msg| matches!(msg, InboundDelegateMsg::TimerFired(_)))
+                    {
+                        return Err(WsApiError::deserialization(
+                            "TimerFired is delivered by the host only".into(),
+                        ));
+                    }
                     DelegateRequest::ApplicationMessages {
                         key,
                         params,
@@ -796,6 +1057,7 @@ pub enum HostResponse<T = WrappedState> {
 
 /// Describes what kind of response is being streamed.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[non_exhaustive]
 pub enum StreamContent {
     /// A streamed GetResponse — the large state is delivered via StreamChunks.
     GetResponse {
@@ -804,6 +1066,39 @@ pub enum StreamContent {
     },
     /// Raw binary stream (future use).
     Raw,
+    /// A [`StateRead::Range`] read. Unlike the other kinds, the chunks carry the
+    /// state bytes themselves, starting at `offset` of a state of `total_size` bytes.
+    ///
+    /// An interrupted download can be resumed by requesting the range starting at
+    /// `offset` plus the bytes received so far.
+    GetRange {
+        key: ContractKey,
+        offset: u64,
+        total_size: u64,
+    },
+}
+
+impl StreamContent {
+    /// The response the reassembled `data` of a stream of this kind stands for, if
+    /// its chunks carry raw bytes. Otherwise `data` is a serialized
+    /// [`HostResult`](super::HostResult) and is handed back unchanged.
+    pub fn raw_response(&self, data: Vec<u8>) -> Result<HostResponse, Vec<u8>> {
+        match *self {
+            Self::GetRange {
+                key,
+                offset,
+                total_size,
+            } => Ok(HostResponse::ContractResponse(
+                ContractResponse::GetRangeResponse {
+                    key,
+                    offset,
+                    total_size,
+                    data: data.into(),
+                },
+            )),
+            Self::GetResponse { .. } | Self::Raw => Err(data),
+        }
+    }
 }
 
 type Peer = String;
@@ -1091,6 +1386,216 @@ impl HostResponse {
                     finish_host_response_buffer(&mut builder, response_offset);
                     Ok(builder.finished_data().to_vec())
                 }
+                ContractResponse::UpdatePreconditionFailed { key, current } => {
+                    let instance_data = builder.create_vector(key.as_bytes());
+                    let instance_offset = FbsContractInstanceId::create(
+                        &mut builder,
+                        &ContractInstanceIdArgs {
+                            data: Some(instance_data),
+                        },
+                    );
+
+                    let code = Some(builder.create_vector(&key.code_hash().0));
+
+                    let key_offset = FbsContractKey::create(
+                        &mut builder,
+                        &ContractKeyArgs {
+                            instance: Some(instance_offset),
+                            code,
+                        },
+                    );
+
+                    let (current_summary, current_state_hash) = match current {
+                        UpdatePrecondition::Summary(summary) => {
+                            (Some(builder.create_vector(&summary.into_bytes())), None)
+                        }
+                        UpdatePrecondition::StateHash(hash) => {
+                            (None, Some(builder.create_vector(&hash)))
+                        }
+                    };
+
+                    let failed_offset = FbsUpdatePreconditionFailed::create(
+                        &mut builder,
+                        &UpdatePreconditionFailedArgs {
+                            key: Some(key_offset),
+                            current_summary,
+                            current_state_hash,
+                        },
+                    );
+
+                    let contract_response_offset = FbsContractResponse::create(
+                        &mut builder,
+                        &ContractResponseArgs {
+                            contract_response: Some(failed_offset.as_union_value()),
+                            contract_response_type: ContractResponseType::UpdatePreconditionFailed,
+                        },
+                    );
+
+                    let response_offset = FbsHostResponse::create(
+                        &mut builder,
+                        &HostResponseArgs {
+                            response: Some(contract_response_offset.as_union_value()),
+                            response_type: HostResponseType::ContractResponse,
+                        },
+                    );
+
+                    finish_host_response_buffer(&mut builder, response_offset);
+                    Ok(builder.finished_data().to_vec())
+                }
+                ContractResponse::GetMetadataResponse { key, metadata } => {
+                    let instance_data = builder.create_vector(key.as_bytes());
+                    let instance_offset = FbsContractInstanceId::create(
+                        &mut builder,
+                        &ContractInstanceIdArgs {
+                            data: Some(instance_data),
+                        },
+                    );
+
+                    let code = Some(builder.create_vector(&key.code_hash().0));
+
+                    let key_offset = FbsContractKey::create(
+                        &mut builder,
+                        &ContractKeyArgs {
+                            instance: Some(instance_offset),
+                            code,
+                        },
+                    );
+
+                    let summary = metadata
+                        .summary
+                        .map(|summary| builder.create_vector(&summary.into_bytes()));
+
+                    let metadata_offset = FbsGetMetadataResponse::create(
+                        &mut builder,
+                        &GetMetadataResponseArgs {
+                            key: Some(key_offset),
+                            state_size: metadata.size,
+                            summary,
+                        },
+                    );
+
+                    let contract_response_offset = FbsContractResponse::create(
+                        &mut builder,
+                        &ContractResponseArgs {
+                            contract_response: Some(metadata_offset.as_union_value()),
+                            contract_response_type: ContractResponseType::GetMetadataResponse,
+                        },
+                    );
+
+                    let response_offset = FbsHostResponse::create(
+                        &mut builder,
+                        &HostResponseArgs {
+                            response: Some(contract_response_offset.as_union_value()),
+                            response_type: HostResponseType::ContractResponse,
+                        },
+                    );
+
+                    finish_host_response_buffer(&mut builder, response_offset);
+                    Ok(builder.finished_data().to_vec())
+                }
+                ContractResponse::GetRangeResponse {
+                    key,
+                    offset,
+                    total_size,
+                    data,
+                } => {
+                    let instance_data = builder.create_vector(key.as_bytes());
+                    let instance_offset = FbsContractInstanceId::create(
+                        &mut builder,
+                        &ContractInstanceIdArgs {
+                            data: Some(instance_data),
+                        },
+                    );
+
+                    let code = Some(builder.create_vector(&key.code_hash().0));
+
+                    let key_offset = FbsContractKey::create(
+                        &mut builder,
+                        &ContractKeyArgs {
+                            instance: Some(instance_offset),
+                            code,
+                        },
+                    );
+
+                    let data = builder.create_vector(&data);
+
+                    let range_offset = FbsGetRangeResponse::create(
+                        &mut builder,
+                        &GetRangeResponseArgs {
+                            key: Some(key_offset),
+                            offset,
+                            total_size,
+                            data: Some(data),
+                        },
+                    );
+
+                    let contract_response_offset = FbsContractResponse::create(
+                        &mut builder,
+                        &ContractResponseArgs {
+                            contract_response: Some(range_offset.as_union_value()),
+                            contract_response_type: ContractResponseType::GetRangeResponse,
+                        },
+                    );
+
+                    let response_offset = FbsHostResponse::create(
+                        &mut builder,
+                        &HostResponseArgs {
+                            response: Some(contract_response_offset.as_union_value()),
+                            response_type: HostResponseType::ContractResponse,
+                        },
+                    );
+
+                    finish_host_response_buffer(&mut builder, response_offset);
+                    Ok(builder.finished_data().to_vec())
+                }
+                ContractResponse::QueryResponse { key, result } => {
+                    let instance_data = builder.create_vector(key.as_bytes());
+                    let instance_offset = FbsContractInstanceId::create(
+                        &mut builder,
+                        &ContractInstanceIdArgs {
+                            data: Some(instance_data),
+                        },
+                    );
+
+                    let code = Some(builder.create_vector(&key.code_hash().0));
+
+                    let key_offset = FbsContractKey::create(
+                        &mut builder,
+                        &ContractKeyArgs {
+                            instance: Some(instance_offset),
+                            code,
+                        },
+                    );
+
+                    let result_data = builder.create_vector(&result.into_bytes());
+
+                    let query_response_offset = FbsQueryResponse::create(
+                        &mut builder,
+                        &QueryResponseArgs {
+                            key: Some(key_offset),
+                            result: Some(result_data),
+                        },
+                    );
+
+                    let contract_response_offset = FbsContractResponse::create(
+                        &mut builder,
+                        &ContractResponseArgs {
+                            contract_response: Some(query_response_offset.as_union_value()),
+                            contract_response_type: ContractResponseType::QueryResponse,
+                        },
+                    );
+
+                    let response_offset = FbsHostResponse::create(
+                        &mut builder,
+                        &HostResponseArgs {
+                            response: Some(contract_response_offset.as_union_value()),
+                            response_type: HostResponseType::ContractResponse,
+                        },
+                    );
+
+                    finish_host_response_buffer(&mut builder, response_offset);
+                    Ok(builder.finished_data().to_vec())
+                }
                 ContractResponse::UpdateResponse { key, summary } => {
                     let instance_data = builder.create_vector(key.as_bytes());
                     let instance_offset = FbsContractInstanceId::create(
@@ -1638,6 +2143,50 @@ impl HostResponse {
                             "SendDelegateMessage reached client serialization - this is a bug"
                         );
                     }
+                    OutboundDelegateMsg::ScheduleTimer(timer) => {
+                        let (deadline_ms, absolute) = match timer.deadline {
+                            TimerDeadline::After(delay) => {
+                                (i64::try_from(delay.as_millis()).unwrap_or(i64::MAX), false)
+                            }
+                            TimerDeadline::At(instant) => (instant.timestamp_millis(), true),
+                        };
+                        let payload_data = builder.create_vector(&timer.payload);
+                        let context_data = builder.create_vector(timer.context.as_ref());
+                        let timer_offset = FbsScheduleTimer::create(
+                            &mut builder,
+                            &ScheduleTimerArgs {
+                                timer_id: timer.timer_id.as_u64(),
+                                deadline_ms,
+                                absolute,
+                                payload: Some(payload_data),
+                                context: Some(context_data),
+                            },
+                        );
+                        let msg = FbsOutboundDelegateMsg::create(
+                            &mut builder,
+                            &OutboundDelegateMsgArgs {
+                                inbound_type: OutboundDelegateMsgType::ScheduleTimer,
+                                inbound: Some(timer_offset.as_union_value()),
+                            },
+                        );
+                        messages.push(msg);
+                    }
+                    OutboundDelegateMsg::CancelTimer(timer_id) => {
+                        let cancel_offset = FbsCancelTimer::create(
+                            &mut builder,
+                            &CancelTimerArgs {
+                                timer_id: timer_id.as_u64(),
+                            },
+                        );
+                        let msg = FbsOutboundDelegateMsg::create(
+                            &mut builder,
+                            &OutboundDelegateMsgArgs {
+                                inbound_type: OutboundDelegateMsgType::CancelTimer,
+                                inbound: Some(cancel_offset.as_union_value()),
+                            },
+                        );
+                        messages.push(msg);
+                    }
                 });
                 let messages_offset = builder.create_vector(&messages);
                 let delegate_response_offset = FbsDelegateResponse::create(
@@ -1727,6 +2276,27 @@ impl Display for HostResponse {
                 ContractResponse::SubscribeResponse { key, .. } => {
                     f.write_fmt(format_args!("subscribe response for `{key}`"))
                 }
+                ContractResponse::QueryResponse { key, .. } => {
+                    f.write_fmt(format_args!("query response for `{key}`"))
+                }
+                ContractResponse::UpdatePreconditionFailed { key, .. } => {
+                    f.write_fmt(format_args!("update precondition failed for `{key}`"))
+                }
+                ContractResponse::GetMetadataResponse { key, metadata } => {
+                    f.write_fmt(format_args!(
+                        "get metadata response for `{key}` ({} bytes)",
+                        metadata.size
+                    ))
+                }
+                ContractResponse::GetRangeResponse {
+                    key,
+                    offset,
+                    total_size,
+                    data,
+                } => f.write_fmt(format_args!(
+                    "get range response for `{key}` ({} bytes at {offset} of {total_size})",
+                    data.len()
+                )),
                 ContractResponse::NotFound { instance_id } => {
                     f.write_fmt(format_args!("not found for `{instance_id}`"))
                 }
@@ -1784,6 +2354,33 @@ pub enum ContractResponse<T = WrappedState> {
         /// The instance ID that was searched for.
         instance_id: ContractInstanceId,
     },
+    /// Answer of the contract to a [`ContractRequest::Query`].
+    QueryResponse {
+        key: ContractKey,
+        #[serde(deserialize_with = "StateQueryResult::deser_state_query_result")]
+        result: StateQueryResult<'static>,
+    },
+    /// The precondition of a [`ContractRequest::Update`] was not met, so the update was
+    /// not applied.
+    UpdatePreconditionFailed {
+        key: ContractKey,
+        /// The precondition, of the same kind as the requested one, which the current
+        /// state meets.
+        current: UpdatePrecondition,
+    },
+    /// Answer to a [`ContractRequest::Get`] for [`StateRead::MetadataOnly`].
+    GetMetadataResponse {
+        key: ContractKey,
+        metadata: StateMetadata,
+    },
+    /// Answer to a [`ContractRequest::Get`] for [`StateRead::Range`], holding the
+    /// state bytes starting at `offset` of a state of `total_size` bytes.
+    GetRangeResponse {
+        key: ContractKey,
+        offset: u64,
+        total_size: u64,
+        data: Bytes,
+    },
 }
 
 impl<T> From<ContractResponse<T>> for HostResponse<T> {
@@ -1965,11 +2562,13 @@ mod client_request_test {
                 return_contract_code: fetch_contract,
                 subscribe,
                 blocking_subscribe,
+                read,
             } => {
                 assert_eq!(key.encode(), EXPECTED_ENCODED_CONTRACT_ID);
                 assert!(!fetch_contract);
                 assert!(!subscribe);
                 assert!(!blocking_subscribe);
+                assert_eq!(read, crate::client_api::StateRead::Full);
             }
             _ => panic!("wrong contract request type"),
         }
@@ -2046,6 +2645,7 @@ mod client_request_test {
             &UpdateArgs {
                 key: Some(key_offset),
                 data: Some(update_data_offset),
+                ..Default::default()
             },
         );
         let contract_offset = FbsContractRequest::create(
@@ -2073,7 +2673,7 @@ mod client_request_test {
         };
 
         match request {
-            ContractRequest::Update { key, data } => {
+            ContractRequest::Update { key, data, .. } => {
                 assert_eq!(key.encoded_contract_id(), EXPECTED_ENCODED_CONTRACT_ID);
                 assert_eq!(
                     key.code_hash().as_ref(),
@@ -2215,6 +2815,7 @@ mod client_request_test {
                     fetch_contract: false,
                     subscribe: false,
                     blocking_subscribe: false,
+                    ..Default::default()
                 },
             );
             (ContractRequestType::Get, get.as_union_value())
@@ -2604,7 +3205,7 @@ mod delegate_request_wire_format {
 ///    discriminant — including `NONE` — reaches the decoder's match.
 #[cfg(test)]
 mod fbs_decode_hardening {
-    use super::{ClientRequest, ContractRequest};
+    use super::{ClientRequest, ContractRequest, StateRead, UpdatePrecondition};
     use crate::client_api::TryFromFbs;
     use crate::contract_interface::UpdateData;
     use crate::generated::client_request::{
@@ -2748,6 +3349,7 @@ mod fbs_decode_hardening {
                 fetch_contract: false,
                 subscribe: false,
                 blocking_subscribe: false,
+                ..Default::default()
             },
         );
         let contract = FbsContractRequest::create(
@@ -2779,6 +3381,7 @@ mod fbs_decode_hardening {
                 fetch_contract: false,
                 subscribe: false,
                 blocking_subscribe: false,
+                ..Default::default()
             },
         );
         finish_contract(&mut b, ContractRequestType(d), get.as_union_value())
@@ -2936,6 +3539,7 @@ mod fbs_decode_hardening {
             &UpdateArgs {
                 key: Some(key),
                 data: Some(data),
+                ..Default::default()
             },
         );
         finish_contract(&mut b, ContractRequestType::Update, update.as_union_value())
@@ -3131,6 +3735,7 @@ mod fbs_decode_hardening {
             &UpdateArgs {
                 key: Some(key),
                 data: Some(data),
+                ..Default::default()
             },
         );
         finish_contract(&mut b, ContractRequestType::Update, update.as_union_value())
@@ -3473,6 +4078,294 @@ mod fbs_decode_hardening {
         );
     }
 
+    /// A rejected update reaches clients as an `Error` response carrying the
+    /// reason's code and field next to the message. It used to be tagged as an
+    /// `Ok` response, so the TypeScript SDK never routed client errors to `onErr`.
+    #[test]
+    fn client_error_encodes_rejection_reason() {
+        use crate::client_api::{ClientError, ContractError, ErrorKind};
+        use crate::contract_interface::{ContractError as InterfaceError, ContractKey, ErrorCode};
+        use crate::generated::host_response::{root_as_host_response, HostResponseType};
+
+        let key = ContractKey::from_params_and_code(
+            crate::parameters::Parameters::from(vec![1u8, 2]),
+            crate::contract_interface::ContractCode::from(vec![0u8; 8]),
+        );
+        let reason = InterfaceError::stale_sequence(7, 3).reason().at("sequence");
+        let err = ClientError::from(ErrorKind::RequestError(
+            ContractError::update_rejected(key, reason).into(),
+        ));
+        assert_eq!(
+            err.reason().map(|r| r.code),
+            Some(ErrorCode::STALE_SEQUENCE)
+        );
+
+        let bytes = err.into_fbs_bytes().expect("encoding must succeed");
+        let host = root_as_host_response(&bytes).expect("the encoder must emit a valid buffer");
+        assert_eq!(host.response_type(), HostResponseType::Error);
+        let error = host.response_as_error().expect("an Error");
+        assert_eq!(error.code(), ErrorCode::STALE_SEQUENCE.as_u32());
+        assert_eq!(error.field(), Some("sequence"));
+        assert!(error
+            .msg()
+            .contains("sequence 3 is not newer than current sequence 7"));
+
+        let bytes = ClientError::from(ErrorKind::Disconnect)
+            .into_fbs_bytes()
+            .unwrap();
+        let error = root_as_host_response(&bytes)
+            .unwrap()
+            .response_as_error()
+            .expect("an Error");
+        assert_eq!((error.code(), error.field()), (0, None));
+    }
+
+    /// A query travels as its own request type and its answer as a `QueryResponse`,
+    /// with the payloads passed through untouched in both directions.
+    #[test]
+    fn query_round_trip() {
+        use super::{ContractResponse, HostResponse};
+        use crate::contract_interface::{ContractInstanceId, ContractKey, StateQueryResult};
+        use crate::generated::client_request::{Query as FbsQuery, QueryArgs};
+        use crate::generated::host_response::{root_as_host_response, ContractResponseType};
+
+        let mut b = Builder::new();
+        let key = key_offset(&mut b, &INSTANCE, &CODE_HASH);
+        let query = b.create_vector(&[1u8, 2, 3]);
+        let req = FbsQuery::create(
+            &mut b,
+            &QueryArgs {
+                key: Some(key),
+                query: Some(query),
+            },
+        );
+        let bytes = finish_contract(&mut b, ContractRequestType::Query, req.as_union_value());
+        let request = ClientRequest::try_decode_fbs(&bytes).expect("a valid query must decode");
+        let ClientRequest::ContractOp(ContractRequest::Query { key, query }) = request else {
+            panic!("unexpected request: {request:?}");
+        };
+        assert_eq!(key, ContractInstanceId::new(INSTANCE));
+        assert_eq!(query.as_ref(), [1, 2, 3]);
+
+        let key = ContractKey::from_params_and_code(
+            crate::parameters::Parameters::from(vec![1u8, 2]),
+            crate::contract_interface::ContractCode::from(vec![0u8; 8]),
+        );
+        let response = HostResponse::ContractResponse(ContractResponse::QueryResponse {
+            key,
+            result: StateQueryResult::from(vec![4u8, 5]),
+        });
+        let bytes = response.into_fbs_bytes().expect("encoding must succeed");
+        let contract_response = root_as_host_response(&bytes)
+            .expect("the encoder must emit a valid buffer")
+            .response_as_contract_response()
+            .expect("a ContractResponse");
+        assert_eq!(
+            contract_response.contract_response_type(),
+            ContractResponseType::QueryResponse
+        );
+        let query_response = contract_response
+            .contract_response_as_query_response()
+            .expect("a QueryResponse");
+        assert_eq!(query_response.result().bytes(), [4, 5]);
+        assert_eq!(
+            query_response.key().instance().data().bytes(),
+            key.as_bytes()
+        );
+    }
+
+    /// An UPDATE carrying the given preconditions, to be decoded.
+    fn conditional_update(summary: Option<&[u8]>, state_hash: Option<&[u8]>) -> Vec<u8> {
+        let mut b = Builder::new();
+        let state = b.create_vector(&[5u8; 4]);
+        let state_update = StateUpdate::create(&mut b, &StateUpdateArgs { state: Some(state) });
+        let data = FbsUpdateData::create(
+            &mut b,
+            &UpdateDataArgs {
+                update_data_type: UpdateDataType::StateUpdate,
+                update_data: Some(state_update.as_union_value()),
+            },
+        );
+        let key = key_offset(&mut b, &INSTANCE, &CODE_HASH);
+        let expected_summary = summary.map(|s| b.create_vector(s));
+        let expected_state_hash = state_hash.map(|h| b.create_vector(h));
+        let update = FbsUpdate::create(
+            &mut b,
+            &UpdateArgs {
+                key: Some(key),
+                data: Some(data),
+                expected_summary,
+                expected_state_hash,
+            },
+        );
+        finish_contract(&mut b, ContractRequestType::Update, update.as_union_value())
+    }
+
+    fn decoded_precondition(bytes: &[u8]) -> Result<Option<UpdatePrecondition>, String> {
+        match ClientRequest::try_decode_fbs(bytes).map_err(|e| e.to_string())? {
+            ClientRequest::ContractOp(ContractRequest::Update { precondition, .. }) => {
+                Ok(precondition)
+            }
+            other => panic!("unexpected request: {other:?}"),
+        }
+    }
+
+    /// Both precondition kinds decode, a malformed hash or both kinds at once are
+    /// rejected, and the failure response carries the precondition the state meets.
+    #[test]
+    fn update_precondition_round_trip() {
+        use super::{ContractResponse, HostResponse};
+        use crate::contract_interface::{ContractKey, StateSummary};
+        use crate::generated::host_response::{root_as_host_response, ContractResponseType};
+
+        assert_eq!(
+            decoded_precondition(&conditional_update(None, None)),
+            Ok(None)
+        );
+        assert_eq!(
+            decoded_precondition(&conditional_update(Some(&[]), Some(&[]))),
+            Ok(None)
+        );
+        assert_eq!(
+            decoded_precondition(&conditional_update(Some(&[1, 2]), None)),
+            Ok(Some(UpdatePrecondition::Summary(StateSummary::from(vec![
+                1u8, 2
+            ]))))
+        );
+        assert_eq!(
+            decoded_precondition(&conditional_update(None, Some(&[7; 32]))),
+            Ok(Some(UpdatePrecondition::StateHash([7; 32])))
+        );
+        let short = decoded_precondition(&conditional_update(None, Some(&[7; 8]))).unwrap_err();
+        assert!(short.contains("Update.expected_state_hash"), "got: {short}");
+        let both =
+            decoded_precondition(&conditional_update(Some(&[1]), Some(&[7; 32]))).unwrap_err();
+        assert!(both.contains("mutually exclusive"), "got: {both}");
+
+        let key = ContractKey::from_params_and_code(
+            crate::parameters::Parameters::from(vec![1u8, 2]),
+            crate::contract_interface::ContractCode::from(vec![0u8; 8]),
+        );
+        let hash = *blake3::hash(&[3, 4]).as_bytes();
+        let current = UpdatePrecondition::StateHash(hash);
+        let response = HostResponse::ContractResponse(ContractResponse::UpdatePreconditionFailed {
+            key,
+            current,
+        });
+        let bytes = response.into_fbs_bytes().expect("encoding must succeed");
+        let contract_response = root_as_host_response(&bytes)
+            .expect("the encoder must emit a valid buffer")
+            .response_as_contract_response()
+            .expect("a ContractResponse");
+        assert_eq!(
+            contract_response.contract_response_type(),
+            ContractResponseType::UpdatePreconditionFailed
+        );
+        let failed = contract_response
+            .contract_response_as_update_precondition_failed()
+            .expect("an UpdatePreconditionFailed");
+        assert!(failed.current_summary().is_none());
+        assert_eq!(
+            failed.current_state_hash().map(|h| h.bytes()),
+            Some(&hash[..])
+        );
+    }
+
+    /// A GET asking for the given part of the state, decoded.
+    fn decoded_read(
+        metadata_only: bool,
+        range_offset: u64,
+        range_len: u64,
+    ) -> Result<StateRead, String> {
+        let mut b = Builder::new();
+        let key = key_offset(&mut b, &INSTANCE, &CODE_HASH);
+        let get = FbsGet::create(
+            &mut b,
+            &GetArgs {
+                key: Some(key),
+                metadata_only,
+                range_offset,
+                range_len,
+                ..Default::default()
+            },
+        );
+        let bytes = finish_contract(&mut b, ContractRequestType::Get, get.as_union_value());
+        match ClientRequest::try_decode_fbs(&bytes).map_err(|e| e.to_string())? {
+            ClientRequest::ContractOp(ContractRequest::Get { read, .. }) => Ok(read),
+            other => panic!("unexpected request: {other:?}"),
+        }
+    }
+
+    /// Metadata-only and ranged reads decode, asking for both is rejected, and
+    /// their responses encode.
+    #[test]
+    fn get_read_options_round_trip() {
+        use super::{ContractResponse, HostResponse, StateMetadata};
+        use crate::contract_interface::{ContractKey, StateSummary};
+        use crate::generated::host_response::root_as_host_response;
+
+        assert_eq!(decoded_read(false, 0, 0), Ok(StateRead::Full));
+        assert_eq!(decoded_read(true, 0, 0), Ok(StateRead::MetadataOnly));
+        assert_eq!(
+            decoded_read(false, 10, 20),
+            Ok(StateRead::Range {
+                offset: 10,
+                len: 20
+            })
+        );
+        let both = decoded_read(true, 0, 20).unwrap_err();
+        assert!(both.contains("mutually exclusive"), "got: {both}");
+
+        assert_eq!(StateRead::Full.byte_range(8), 0..8);
+        assert_eq!(StateRead::MetadataOnly.byte_range(8), 0..0);
+        assert_eq!(StateRead::Range { offset: 2, len: 3 }.byte_range(8), 2..5);
+        assert_eq!(
+            StateRead::Range {
+                offset: 6,
+                len: u64::MAX
+            }
+            .byte_range(8),
+            6..8
+        );
+        assert_eq!(StateRead::Range { offset: 9, len: 1 }.byte_range(8), 8..8);
+
+        let key = ContractKey::from_params_and_code(
+            crate::parameters::Parameters::from(vec![1u8, 2]),
+            crate::contract_interface::ContractCode::from(vec![0u8; 8]),
+        );
+        let response = HostResponse::ContractResponse(ContractResponse::GetMetadataResponse {
+            key,
+            metadata: StateMetadata::new(1024, Some(StateSummary::from(vec![9u8]))),
+        });
+        let bytes = response.into_fbs_bytes().expect("encoding must succeed");
+        let metadata = root_as_host_response(&bytes)
+            .expect("the encoder must emit a valid buffer")
+            .response_as_contract_response()
+            .and_then(|r| r.contract_response_as_get_metadata_response())
+            .expect("a GetMetadataResponse");
+        assert_eq!(metadata.state_size(), 1024);
+        assert_eq!(metadata.summary().map(|s| s.bytes()), Some(&[9u8][..]));
+        assert_eq!(
+            metadata.key().code().map(|c| c.bytes()),
+            Some(&key.code_hash().0[..])
+        );
+
+        let response = HostResponse::ContractResponse(ContractResponse::GetRangeResponse {
+            key,
+            offset: 2,
+            total_size: 8,
+            data: vec![3u8, 4, 5].into(),
+        });
+        let bytes = response.into_fbs_bytes().expect("encoding must succeed");
+        let range = root_as_host_response(&bytes)
+            .expect("the encoder must emit a valid buffer")
+            .response_as_contract_response()
+            .and_then(|r| r.contract_response_as_get_range_response())
+            .expect("a GetRangeResponse");
+        assert_eq!((range.offset(), range.total_size()), (2, 8));
+        assert_eq!(range.data().bytes(), [3, 4, 5]);
+    }
+
     /// `SecretsId::try_decode_fbs` has no production caller today, so it is
     /// pinned directly rather than through a request. Fixing it now means the
     /// first client to reach it does not find a panic waiting.
@@ -3512,4 +4405,156 @@ mod fbs_decode_hardening {
             "a 32-byte hash must still decode"
         );
     }
+
+    fn timer_fired<'b>(
+        b: &mut Builder<'b>,
+        fired_at_ms: i64,
+    ) -> flatbuffers::WIPOffset<FbsInboundDelegateMsg<'b>> {
+        use crate::generated::client_request::{TimerFired as FbsTimerFired, TimerFiredArgs};
+        let payload = b.create_vector(&[5u8; 3]);
+        let context = b.create_vector(&[6u8; 2]);
+        let fired = FbsTimerFired::create(
+            b,
+            &TimerFiredArgs {
+                timer_id: 77,
+                fired_at_ms,
+                payload: Some(payload),
+                delegate_context: Some(context),
+            },
+        );
+        FbsInboundDelegateMsg::create(
+            b,
+            &InboundDelegateMsgArgs {
+                inbound_type: InboundDelegateMsgType::TimerFired,
+                inbound: Some(fired.as_union_value()),
+            },
+        )
+    }
+
+    fn decode_timer_fired(
+        fired_at_ms: i64,
+    ) -> Result<crate::delegate_interface::InboundDelegateMsg<'static>, crate::client_api::WsApiError>
+    {
+        use crate::delegate_interface::InboundDelegateMsg;
+
+        let mut b = Builder::new();
+        let msg = timer_fired(&mut b, fired_at_ms);
+        b.finish(msg, None);
+        let msg = flatbuffers::root::<FbsInboundDelegateMsg>(b.finished_data()).unwrap();
+        InboundDelegateMsg::try_decode_fbs(&msg).map(InboundDelegateMsg::into_owned)
+    }
+
+    /// `fired_at_ms` is a plain `int64` on the wire, so an instant chrono cannot
+    /// represent must be rejected, not unwrapped.
+    #[test]
+    fn timer_fired_decodes_and_rejects_out_of_range_instants() {
+        use crate::delegate_interface::InboundDelegateMsg;
+
+        let InboundDelegateMsg::TimerFired(fired) = decode_timer_fired(1_700_000_000_123).unwrap()
+        else {
+            panic!("not a timer firing");
+        };
+        assert_eq!(fired.timer_id.as_u64(), 77);
+        assert_eq!(fired.fired_at.timestamp_millis(), 1_700_000_000_123);
+        assert_eq!(fired.payload, [5u8; 3]);
+        assert_eq!(fired.context.as_ref(), [6u8; 2]);
+
+        let err =
+            decode_timer_fired(i64::MAX).expect_err("an unrepresentable instant must be rejected");
+        assert!(
+            err.to_string().contains("TimerFired.fired_at_ms"),
+            "got: {err}"
+        );
+    }
+
+    /// Timer firings come from the host only. A client must not be able to
+    /// forge one (a spurious wake-up, or an RPC call timing out early), so a
+    /// request carrying one is rejected.
+    #[test]
+    fn client_sent_timer_fired_is_rejected() {
+        let mut b = Builder::new();
+        let inbound_msg = timer_fired(&mut b, 1_000);
+        let inbound = b.create_vector(&[inbound_msg]);
+        let dk = delegate_key_offset(&mut b, &[7u8; 32], &CODE_HASH);
+        let params = b.create_vector(&[1u8, 2, 3]);
+        let msgs = ApplicationMessages::create(
+            &mut b,
+            &ApplicationMessagesArgs {
+                key: Some(dk),
+                params: Some(params),
+                inbound: Some(inbound),
+            },
+        );
+        let bytes = finish_delegate(
+            &mut b,
+            DelegateRequestType::ApplicationMessages,
+            msgs.as_union_value(),
+        );
+        let err = ClientRequest::try_decode_fbs(&bytes)
+            .expect_err("a client-sent timer firing must be rejected");
+        assert!(
+            err.to_string()
+                .contains("TimerFired is delivered by the host"),
+            "got: {err}"
+        );
+    }
+
+    fn user_input_response_with_context(context_len: usize) -> Vec<u8> {
+        use crate::generated::client_request::{
+            ClientResponse as FbsClientResponse, ClientResponseArgs,
+            UserInputResponse as FbsUserInputResponse, UserInputResponseArgs,
+        };
+        let mut b = Builder::new();
+        let data = b.create_vector(&[5u8; 3]);
+        let response = FbsClientResponse::create(&mut b, &ClientResponseArgs { data: Some(data) });
+        let context = b.create_vector(&vec![6u8; context_len]);
+        let user_response = FbsUserInputResponse::create(
+            &mut b,
+            &UserInputResponseArgs {
+                request_id: 1,
+                response: Some(response),
+                delegate_context: Some(context),
+            },
+        );
+        let inbound_msg = FbsInboundDelegateMsg::create(
+            &mut b,
+            &InboundDelegateMsgArgs {
+                inbound_type: InboundDelegateMsgType::UserInputResponse,
+                inbound: Some(user_response.as_union_value()),
+            },
+        );
+        let inbound = b.create_vector(&[inbound_msg]);
+        let dk = delegate_key_offset(&mut b, &[7u8; 32], &CODE_HASH);
+        let params = b.create_vector(&[1u8, 2, 3]);
+        let msgs = ApplicationMessages::create(
+            &mut b,
+            &ApplicationMessagesArgs {
+                key: Some(dk),
+                params: Some(params),
+                inbound: Some(inbound),
+            },
+        );
+        finish_delegate(
+            &mut b,
+            DelegateRequestType::ApplicationMessages,
+            msgs.as_union_value(),
+        )
+    }
+
+    /// `delegate_context` is an unbounded vector on the wire, while
+    /// `DelegateContext::new` asserts it stays under `MAX_SIZE`. An oversized
+    /// context used to panic the decoder.
+    #[test]
+    fn oversized_delegate_context_is_rejected_not_panicking() {
+        use crate::delegate_interface::DelegateContext;
+
+        let bytes = user_input_response_with_context(DelegateContext::MAX_SIZE);
+        let err = ClientRequest::try_decode_fbs(&bytes)
+            .expect_err("an oversized context must be rejected");
+        assert!(
+            err.to_string()
+                .contains("UserInputResponse.delegate_context"),
+            "got: {err}"
+        );
+    }
 }
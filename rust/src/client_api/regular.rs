@@ -3,10 +3,14 @@ use std::{
 };
 
 use super::{
-    client_events::{ClientError, ClientRequest, ErrorKind, HostResponse},
+    client_events::{
+        ClientError, ClientRequest, ContractError, ContractRequest, ContractResponse, ErrorKind,
+        HostResponse, StateRead, StreamContent, UpdatePrecondition,
+    },
     streaming::WsStreamHandle,
     Error, HostResult,
 };
+use crate::prelude::{ContractKey, UpdateData, WrappedState};
 use futures::{stream::FuturesUnordered, Sink, SinkExt, Stream, StreamExt};
 use tokio::{
     net::TcpStream,
@@ -28,6 +32,9 @@ pub struct WebApi {
     stream_rx: Receiver<WsStreamHandle>,
     queue: VecDeque<ClientRequest<'static>>,
     pending_streams: FuturesUnordered<Pin<Box<dyn Future<Output = HostResult> + Send>>>,
+    /// Messages received by [`WebApi::update_if_unchanged`] which were meant for the
+    /// caller, delivered before anything else.
+    deferred: VecDeque<HostResponse>,
 }
 
 impl Drop for WebApi {
@@ -46,6 +53,10 @@ impl Stream for WebApi {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Self::Item>> {
+        if let Some(response) = self.deferred.pop_front() {
+            return Poll::Ready(Some(Ok(response)));
+        }
+
         // Poll all pending stream assemblies concurrently.
         match self.pending_streams.poll_next_unpin(cx) {
             Poll::Ready(Some(result)) => return Poll::Ready(Some(result)),
@@ -173,6 +184,7 @@ impl WebApi {
             stream_rx,
             queue: VecDeque::new(),
             pending_streams: FuturesUnordered::new(),
+            deferred: VecDeque::new(),
         }
     }
 
@@ -191,6 +203,7 @@ impl WebApi {
             stream_rx,
             queue: VecDeque::new(),
             pending_streams: FuturesUnordered::new(),
+            deferred: VecDeque::new(),
         }
     }
 
@@ -232,6 +245,14 @@ impl WebApi {
     /// cancelled; drive it to completion, or use
     /// [`recv_stream()`](Self::recv_stream) and own the handle yourself.
     pub async fn recv(&mut self) -> HostResult {
+        if let Some(response) = self.deferred.pop_front() {
+            return Ok(response);
+        }
+        self.recv_next().await
+    }
+
+    /// Receives the next message from the connection, ignoring the deferred ones.
+    async fn recv_next(&mut self) -> HostResult {
         // Neither channel closing is terminal on its own. `request_handler`
         // delivers its final `HostResult` on `response_tx` and only *then*
         // returns, which drops both senders, so from the caller's side the
@@ -294,10 +315,15 @@ impl WebApi {
     /// Reassemble a streamed response into the complete [`HostResult`] the
     /// server sent.
     async fn assemble_stream(handle: WsStreamHandle) -> HostResult {
+        let content = handle.content().clone();
         let complete = handle
             .assemble()
             .await
             .map_err(|e| ClientError::from(format!("{e}")))?;
+        let complete = match content.raw_response(complete) {
+            Ok(response) => return Ok(response),
+            Err(complete) => complete,
+        };
         let inner: HostResult =
             bincode::deserialize(&complete).map_err(|e| ClientError::from(format!("{e}")))?;
         inner
@@ -320,6 +346,81 @@ impl WebApi {
         self.stream_rx.recv().await.ok_or(Error::ChannelClosed)
     }
 
+    /// Update a contract with compare-and-swap semantics, retrying on conflicts.
+    ///
+    /// Each attempt fetches the current state with a `Get`, builds the update on top of
+    /// it with `make_update` and sends it conditioned on the state being unchanged. If
+    /// another client updated the contract in between, the host answers with
+    /// [`ContractResponse::UpdatePreconditionFailed`] and the next attempt starts over
+    /// from a fresh `Get`, up to `max_attempts` attempts.
+    ///
+    /// Returns the host response to the update that went through. Notifications and
+    /// responses about other contracts received while waiting are kept, and returned by
+    /// the next calls to [`recv()`](Self::recv) in the order they arrived.
+    pub async fn update_if_unchanged<F>(
+        &mut self,
+        key: ContractKey,
+        max_attempts: usize,
+        mut make_update: F,
+    ) -> HostResult
+    where
+        F: FnMut(&WrappedState) -> UpdateData<'static>,
+    {
+        for _ in 0..max_attempts {
+            let get = ContractRequest::Get {
+                key: *key.id(),
+                return_contract_code: false,
+                subscribe: false,
+                blocking_subscribe: false,
+                read: StateRead::Full,
+            };
+            self.send(get.into())
+                .await
+                .map_err(|e| ClientError::from(format!("{e}")))?;
+            let state = loop {
+                match self.recv_next().await? {
+                    HostResponse::ContractResponse(ContractResponse::GetResponse {
+                        key: got,
+                        state,
+                        ..
+                    }) if got.id() == key.id() => break state,
+                    other => self.deferred.push_back(other),
+                }
+            };
+
+            let update = ContractRequest::Update {
+                key,
+                data: make_update(&state),
+                precondition: Some(UpdatePrecondition::state_hash(state.as_ref())),
+            };
+            self.send(update.into())
+                .await
+                .map_err(|e| ClientError::from(format!("{e}")))?;
+            loop {
+                match self.recv_next().await? {
+                    HostResponse::ContractResponse(
+                        ContractResponse::UpdatePreconditionFailed { key: got, .. },
+                    ) if got.id() == key.id() => {
+                        tracing::debug!(%key, "state changed concurrently, retrying update");
+                        break;
+                    }
+                    response @ HostResponse::ContractResponse(
+                        ContractResponse::UpdateResponse { key: got, .. },
+                    ) if got.id() == key.id() => return Ok(response),
+                    other => self.deferred.push_back(other),
+                }
+            }
+        }
+        Err(ErrorKind::RequestError(
+            ContractError::Update {
+                key,
+                cause: format!("state kept changing, gave up after {max_attempts} attempts").into(),
+            }
+            .into(),
+        )
+        .into())
+    }
+
     #[doc(hidden)]
     pub async fn disconnect(self, cause: impl Into<Cow<'static, str>>) {
         let _ = self
@@ -496,6 +597,20 @@ async fn handle_response_payload(
             stream_senders.insert(stream_id, sender);
             match stream_tx.try_send(handle) {
                 Ok(()) => Ok(()),
+                Err(mpsc::error::TrySendError::Full(handle))
+                    if matches!(handle.content(), StreamContent::GetRange { .. }) =>
+                {
+                    // Raw state bytes are no serialized `HostResult`, so they can't go
+                    // through the ReassemblyBuffer; assemble them in the background.
+                    tracing::warn!(stream_id, "stream_tx full, assembling range in background");
+                    let response_tx = response_tx.clone();
+                    tokio::spawn(async move {
+                        let _ = response_tx
+                            .send(WebApi::assemble_stream(handle).await)
+                            .await;
+                    });
+                    Ok(())
+                }
                 Err(mpsc::error::TrySendError::Full(_)) => {
                     tracing::warn!(
                         stream_id,
@@ -893,6 +1008,181 @@ mod test {
         Ok(())
     }
 
+    /// A conflicting update is retried from a fresh `Get`, conditioned on the hash of
+    /// the state that was just fetched.
+    #[tokio::test]
+    async fn update_if_unchanged_retries_after_precondition_failure() {
+        use crate::prelude::{ContractCode, Parameters, StateSummary};
+
+        let key = ContractKey::from_params_and_code(
+            Parameters::from(vec![1u8]),
+            ContractCode::from(vec![0u8; 8]),
+        );
+        let (request_tx, mut request_rx) = mpsc::channel::<ClientRequest<'static>>(1);
+        let (response_tx, response_rx) = mpsc::channel(1);
+        let (_stream_tx, stream_rx) = mpsc::channel(1);
+
+        // the first update loses the race against a concurrent writer
+        let host = tokio::spawn(async move {
+            let mut states = [vec![1u8], vec![2u8]].into_iter();
+            let mut preconditions = vec![];
+            let mut current = vec![];
+            while let Some(request) = request_rx.recv().await {
+                let ClientRequest::ContractOp(request) = request else {
+                    break;
+                };
+                let response = match request {
+                    ContractRequest::Get { .. } => {
+                        current = states.next().unwrap();
+                        ContractResponse::GetResponse {
+                            key,
+                            contract: None,
+                            state: WrappedState::new(current.clone()),
+                        }
+                    }
+                    ContractRequest::Update { precondition, .. } => {
+                        let precondition = precondition.unwrap();
+                        preconditions.push(precondition.clone());
+                        if preconditions.len() == 1 {
+                            ContractResponse::UpdatePreconditionFailed {
+                                key,
+                                current: UpdatePrecondition::state_hash(&[9]),
+                            }
+                        } else {
+                            assert!(precondition
+                                .is_met(&current, || Ok::<_, ()>(StateSummary::from(vec![])))
+                                .unwrap());
+                            ContractResponse::UpdateResponse {
+                                key,
+                                summary: StateSummary::from(vec![]),
+                            }
+                        }
+                    }
+                    other => panic!("unexpected request: {other:?}"),
+                };
+                response_tx.send(Ok(response.into())).await.unwrap();
+            }
+            preconditions
+        });
+
+        let mut client = WebApi::from_parts(request_tx, response_rx, stream_rx);
+        let mut seen = vec![];
+        let response = client
+            .update_if_unchanged(key, 3, |state| {
+                seen.push(state.as_ref().to_vec());
+                UpdateData::Delta(vec![state.as_ref()[0] + 10].into())
+            })
+            .await
+            .expect("the second attempt goes through");
+        assert!(matches!(
+            response,
+            HostResponse::ContractResponse(ContractResponse::UpdateResponse { .. })
+        ));
+        assert_eq!(seen, [vec![1u8], vec![2u8]]);
+
+        drop(client);
+        let preconditions = host.await.unwrap();
+        assert_eq!(
+            preconditions,
+            [
+                UpdatePrecondition::state_hash(&[1]),
+                UpdatePrecondition::state_hash(&[2])
+            ]
+        );
+    }
+
+    /// Notifications and responses about other contracts arriving while the update is
+    /// in flight are not mistaken for the answer, and are still delivered afterwards.
+    #[tokio::test]
+    async fn update_if_unchanged_defers_interleaved_messages() {
+        use crate::prelude::{ContractCode, Parameters, StateDelta, StateSummary};
+
+        let contract = |param: u8| {
+            ContractKey::from_params_and_code(
+                Parameters::from(vec![param]),
+                ContractCode::from(vec![0u8; 8]),
+            )
+        };
+        let (key, other) = (contract(1), contract(2));
+        let (request_tx, mut request_rx) = mpsc::channel::<ClientRequest<'static>>(1);
+        let (response_tx, response_rx) = mpsc::channel(4);
+        let (_stream_tx, stream_rx) = mpsc::channel(1);
+
+        let host = tokio::spawn(async move {
+            let mut updates = 0;
+            while let Some(request) = request_rx.recv().await {
+                let ClientRequest::ContractOp(request) = request else {
+                    break;
+                };
+                let notification = ContractResponse::UpdateNotification {
+                    key,
+                    update: UpdateData::Delta(StateDelta::from(vec![7])),
+                };
+                response_tx.send(Ok(notification.into())).await.unwrap();
+                let response = match request {
+                    ContractRequest::Get { .. } => {
+                        let unrelated = ContractResponse::GetResponse {
+                            key: other,
+                            contract: None,
+                            state: WrappedState::new(vec![9]),
+                        };
+                        response_tx.send(Ok(unrelated.into())).await.unwrap();
+                        ContractResponse::GetResponse {
+                            key,
+                            contract: None,
+                            state: WrappedState::new(vec![1]),
+                        }
+                    }
+                    ContractRequest::Update { .. } => {
+                        updates += 1;
+                        ContractResponse::UpdateResponse {
+                            key,
+                            summary: StateSummary::from(vec![]),
+                        }
+                    }
+                    other => panic!("unexpected request: {other:?}"),
+                };
+                response_tx.send(Ok(response.into())).await.unwrap();
+            }
+            updates
+        });
+
+        let mut client = WebApi::from_parts(request_tx, response_rx, stream_rx);
+        let mut seen = vec![];
+        let response = client
+            .update_if_unchanged(key, 1, |state| {
+                seen.push(state.as_ref().to_vec());
+                UpdateData::Delta(vec![state.as_ref()[0] + 10].into())
+            })
+            .await
+            .expect("the update goes through");
+        assert!(matches!(
+            response,
+            HostResponse::ContractResponse(ContractResponse::UpdateResponse { key: got, .. })
+                if got == key
+        ));
+        assert_eq!(seen, [vec![1u8]]);
+
+        // in the order they arrived, before anything else
+        let is_notification = |response: &HostResponse| {
+            matches!(
+                response,
+                HostResponse::ContractResponse(ContractResponse::UpdateNotification { key: got, .. })
+                    if *got == key
+            )
+        };
+        assert!(is_notification(&client.recv().await.unwrap()));
+        assert!(matches!(
+            client.recv().await.unwrap(),
+            HostResponse::ContractResponse(ContractResponse::GetResponse { key: got, .. })
+                if got == other
+        ));
+        assert!(is_notification(&client.next().await.unwrap().unwrap()));
+
+        drop(client);
+        assert_eq!(host.await.unwrap(), 1);
+    }
+
     /// `recv()` must not throw away a delivered response because the *other*
     /// internal channel closed first.
     ///
@@ -1044,6 +1334,61 @@ mod test {
         );
     }
 
+    /// A range stream carries raw state bytes, not a serialized `HostResult`, and
+    /// `recv()` turns it into a `GetRangeResponse` rather than failing to decode it.
+    #[tokio::test]
+    async fn recv_assembles_state_range_stream() {
+        use crate::client_api::streaming::{chunk_state_range, ws_stream_pair};
+        use crate::contract_interface::{ContractCode, ContractKey};
+        use crate::parameters::Parameters;
+
+        let key = ContractKey::from_params_and_code(
+            Parameters::from(vec![]),
+            ContractCode::from(vec![1, 2, 3]),
+        );
+        let state: Vec<u8> = (0..=255).collect();
+        let mut responses = chunk_state_range(key, state.clone().into(), 16, 32, 0).into_iter();
+        let Some(HostResponse::StreamHeader {
+            total_bytes,
+            content,
+            ..
+        }) = responses.next()
+        else {
+            panic!("expected a StreamHeader");
+        };
+        let (handle, sender) = ws_stream_pair(content, total_bytes);
+        for response in responses {
+            if let HostResponse::StreamChunk { data, .. } = response {
+                sender
+                    .send_chunk(data)
+                    .expect("feeding the stream must succeed");
+            }
+        }
+        drop(sender);
+
+        let (request_tx, _request_rx) = mpsc::channel::<ClientRequest<'static>>(1);
+        let (_response_tx, response_rx) = mpsc::channel::<HostResult>(1);
+        let (stream_tx, stream_rx) = mpsc::channel::<WsStreamHandle>(1);
+        stream_tx
+            .send(handle)
+            .await
+            .expect("queueing the handle must succeed");
+        let mut client = WebApi::from_parts(request_tx, response_rx, stream_rx);
+        match client.recv().await {
+            Ok(HostResponse::ContractResponse(ContractResponse::GetRangeResponse {
+                key: range_key,
+                offset,
+                total_size,
+                data,
+            })) => {
+                assert_eq!(range_key, key);
+                assert_eq!((offset, total_size), (16, 256));
+                assert_eq!(data, &state[16..48]);
+            }
+            other => panic!("expected a GetRangeResponse, got {other:?}"),
+        }
+    }
+
     /// A closed stream channel must not end a still-live connection.
     ///
     /// This pins the stream arm's `None` branch: "the stream side is finished"
@@ -8,7 +8,8 @@ use std::collections::HashMap;
 
 use bytes::Bytes;
 
-use super::{ClientRequest, HostResponse};
+use super::{ClientRequest, HostResponse, StateRead, StreamContent};
+use crate::prelude::ContractKey;
 
 /// Default chunk payload size: 256 KiB.
 pub const CHUNK_SIZE: usize = 256 * 1024;
@@ -97,6 +98,40 @@ pub fn chunk_response(data: Vec<u8>, stream_id: u32) -> Vec<HostResponse> {
         .collect()
 }
 
+/// Stream `len` bytes of `state` starting at `offset`, as requested with
+/// [`StateRead::Range`], as a [`HostResponse::StreamHeader`] followed by
+/// `StreamChunk` variants carrying the state bytes themselves.
+///
+/// The range is clamped to the state. The header announces the clamped offset
+/// and the full state size, so clients can track progress and resume.
+pub fn chunk_state_range(
+    key: ContractKey,
+    state: Bytes,
+    offset: u64,
+    len: u64,
+    stream_id: u32,
+) -> Vec<HostResponse> {
+    let range = StateRead::Range { offset, len }.byte_range(state.len());
+    let header = HostResponse::StreamHeader {
+        stream_id,
+        total_bytes: range.len() as u64,
+        content: StreamContent::GetRange {
+            key,
+            offset: range.start as u64,
+            total_size: state.len() as u64,
+        },
+    };
+    let chunks = chunk_bytes(&state.slice(range))
+        .into_iter()
+        .map(|(index, total, chunk)| HostResponse::StreamChunk {
+            stream_id,
+            index,
+            total,
+            data: chunk,
+        });
+    std::iter::once(header).chain(chunks).collect()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum StreamError {
     #[error("total_chunks is zero")]
@@ -451,6 +486,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn chunk_state_range_streams_raw_state_bytes() {
+        let code = crate::prelude::ContractCode::from(vec![1, 2, 3]);
+        let key =
+            ContractKey::from_params_and_code(crate::prelude::Parameters::from(vec![]), &code);
+        let state: Vec<u8> = (0..CHUNK_SIZE * 3).map(|i| (i % 251) as u8).collect();
+        let offset = CHUNK_SIZE / 2;
+
+        let mut responses =
+            chunk_state_range(key, Bytes::from(state.clone()), offset as u64, u64::MAX, 3)
+                .into_iter();
+        let Some(HostResponse::StreamHeader {
+            stream_id: 3,
+            total_bytes,
+            content:
+                StreamContent::GetRange {
+                    offset: header_offset,
+                    total_size,
+                    ..
+                },
+        }) = responses.next()
+        else {
+            panic!("expected a GetRange StreamHeader first");
+        };
+        assert_eq!(header_offset, offset as u64);
+        assert_eq!(total_size, state.len() as u64);
+        assert_eq!(total_bytes, (state.len() - offset) as u64);
+
+        let mut received = Vec::new();
+        for response in responses {
+            let HostResponse::StreamChunk { data, .. } = response else {
+                panic!("expected only StreamChunks after the header");
+            };
+            received.extend_from_slice(&data);
+        }
+        assert_eq!(received, &state[offset..]);
+
+        // a range past the end of the state streams nothing
+        let responses = chunk_state_range(key, Bytes::from(state.clone()), u64::MAX, 10, 4);
+        assert!(matches!(
+            responses[0],
+            HostResponse::StreamHeader { total_bytes: 0, .. }
+        ));
+        assert_eq!(responses.len(), 2);
+    }
+
     #[test]
     fn chunk_empty() {
         let chunks = chunk_request(Vec::new(), 1);
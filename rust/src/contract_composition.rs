@@ -1,3 +1,11 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashMap},
+    hash::Hash,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
 use crate::{
     contract_interface::{
         ContractError, ContractInstanceId, RelatedContracts, State, UpdateData, ValidateResult,
@@ -19,6 +27,49 @@ pub trait Mergeable<Other> {
     fn merge(&mut self, other: Other);
 }
 
+/// Combines two values by keeping the newer one, for fields marked
+/// `#[component(merge = "replace")]`.
+pub fn replace<T>(current: &mut T, other: T) {
+    *current = other;
+}
+
+/// Merges an optional field of a composite summary or delta, combining both
+/// values with `combine` when present on each side.
+pub fn merge_field<T>(current: &mut Option<T>, other: Option<T>, combine: fn(&mut T, T)) {
+    match (current.as_mut(), other) {
+        (Some(current), Some(other)) => combine(current, other),
+        (None, Some(other)) => *current = Some(other),
+        (_, None) => {}
+    }
+}
+
+/// Collects the summary a child component merges into its parent, see
+/// [`ContractComponent::summarize`].
+pub struct Collected<S> {
+    value: Option<S>,
+    combine: fn(&mut S, S),
+}
+
+impl<S> Collected<S> {
+    pub fn new(combine: fn(&mut S, S)) -> Self {
+        Self {
+            value: None,
+            combine,
+        }
+    }
+
+    pub fn into_inner(self) -> Option<S> {
+        self.value
+    }
+}
+
+impl<S> Mergeable<S> for Collected<S> {
+    fn merge(&mut self, other: S) {
+        let combine = self.combine;
+        merge_field(&mut self.value, Some(other), combine);
+    }
+}
+
 pub trait ContractComponent: std::any::Any + Sized {
     type Context;
     type Parameters: ComponentParameter;
@@ -59,6 +110,14 @@ pub trait ContractComponent: std::any::Any + Sized {
         parameters: &Self::Parameters,
         summary: &Self::Summary,
     ) -> Result<Self::Delta, ContractError>;
+
+    /// Whether applying `delta` would leave the component as it is, so parents can
+    /// leave it out of their own delta.
+    ///
+    /// Defaults to `false`, which is always correct but sends unchanged components along.
+    fn is_unchanged(_delta: &Self::Delta) -> bool {
+        false
+    }
 }
 
 pub enum TypedUpdateData<T: ContractComponent> {
@@ -104,109 +163,448 @@ impl<T: ContractComponent> TryFrom<(Option<T>, Option<T::Delta>)> for TypedUpdat
     }
 }
 
-#[allow(unused)]
-impl<T> ContractComponent for Vec<T>
+/// Summary of a collection of components: the summary of every entry, by key.
+///
+/// Used by the [`ContractComponent`] impls of `Vec<T>` (keyed by index),
+/// `BTreeMap<K, V>`, `HashMap<K, V>` and `Option<T>` (keyed by `()`).
+///
+/// A `Vec<T>` is positional: entries are matched by index, so it only suits lists which
+/// are appended to. Entries reordered or removed on one peer end up merged with whatever
+/// the other peer holds at the same index; key them by a stable id in a map instead.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize, S: Serialize",
+    deserialize = "K: Deserialize<'de> + Ord, S: Deserialize<'de>"
+))]
+pub struct KeyedSummary<K, S> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    entries: BTreeMap<K, S>,
+}
+
+impl<K, S> KeyedSummary<K, S> {
+    pub fn entries(&self) -> &BTreeMap<K, S> {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K, S> Default for KeyedSummary<K, S> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord, S> FromIterator<(K, S)> for KeyedSummary<K, S> {
+    fn from_iter<I: IntoIterator<Item = (K, S)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K: Ord, S: Mergeable<S>> Mergeable<KeyedSummary<K, S>> for KeyedSummary<K, S> {
+    fn merge(&mut self, other: KeyedSummary<K, S>) {
+        merge_entries(&mut self.entries, other.entries, S::merge);
+    }
+}
+
+/// Delta of a collection of components, by key.
+///
+/// Entries the other peer already has are sent as the delta of the entry against its
+/// summary, unless unchanged, entries it is missing are sent whole. Entries are never
+/// removed.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize, T: Serialize, D: Serialize",
+    deserialize = "K: Deserialize<'de> + Ord, T: Deserialize<'de>, D: Deserialize<'de>"
+))]
+pub struct KeyedDelta<K, T, D> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    updated: BTreeMap<K, D>,
+    #[serde_as(as = "Vec<(_, _)>")]
+    added: BTreeMap<K, T>,
+}
+
+impl<K, T, D> KeyedDelta<K, T, D> {
+    /// Deltas for entries present on both sides.
+    pub fn updated(&self) -> &BTreeMap<K, D> {
+        &self.updated
+    }
+
+    /// Entries missing on the other side.
+    pub fn added(&self) -> &BTreeMap<K, T> {
+        &self.added
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.added.is_empty()
+    }
+}
+
+impl<K, T, D> Default for KeyedDelta<K, T, D> {
+    fn default() -> Self {
+        Self {
+            updated: BTreeMap::new(),
+            added: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord, T, D: Mergeable<D>> Mergeable<KeyedDelta<K, T, D>> for KeyedDelta<K, T, D> {
+    fn merge(&mut self, other: KeyedDelta<K, T, D>) {
+        merge_entries(&mut self.updated, other.updated, D::merge);
+        merge_entries(&mut self.added, other.added, replace);
+    }
+}
+
+fn merge_entries<K: Ord, V>(
+    current: &mut BTreeMap<K, V>,
+    other: BTreeMap<K, V>,
+    combine: fn(&mut V, V),
+) {
+    for (key, value) in other {
+        match current.entry(key) {
+            Entry::Occupied(mut entry) => combine(entry.get_mut(), value),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+}
+
+/// A collection of components addressed by key.
+trait Entries {
+    type Key: Ord + Clone;
+    type Value: ContractComponent + Clone;
+
+    fn entries(&self) -> impl Iterator<Item = (Self::Key, &Self::Value)>;
+
+    fn entry_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value>;
+
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> Result<(), ContractError>;
+}
+
+type Params<C> = <<C as Entries>::Value as ContractComponent>::Parameters;
+type EntrySummary<C> =
+    KeyedSummary<<C as Entries>::Key, <<C as Entries>::Value as ContractComponent>::Summary>;
+type EntryDelta<C> = KeyedDelta<
+    <C as Entries>::Key,
+    <C as Entries>::Value,
+    <<C as Entries>::Value as ContractComponent>::Delta,
+>;
+
+impl<T: ContractComponent + Clone> Entries for Vec<T> {
+    type Key = usize;
+    type Value = T;
+
+    fn entries(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
+
+    fn entry_mut(&mut self, key: &usize) -> Option<&mut T> {
+        self.get_mut(*key)
+    }
+
+    fn insert_entry(&mut self, key: usize, value: T) -> Result<(), ContractError> {
+        // entries are positional, a new one can only be appended
+        if key != self.len() {
+            return Err(ContractError::InvalidUpdateWithInfo {
+                reason: format!("entry {key} is past the end of a list of {}", self.len()),
+            });
+        }
+        self.push(value);
+        Ok(())
+    }
+}
+
+impl<K, V> Entries for BTreeMap<K, V>
 where
-    T: ContractComponent + Clone,
-    <T as ContractComponent>::Delta: Clone + Mergeable<<T as ContractComponent>::Delta>,
+    K: Ord + Clone,
+    V: ContractComponent + Clone,
 {
-    type Context = T::Context;
-    type Parameters = T::Parameters;
-    type Delta = T::Delta;
-    type Summary = T::Summary;
+    type Key = K;
+    type Value = V;
 
-    fn verify<Child, Ctx>(
-        &self,
-        parameters: &Self::Parameters,
-        context: &Ctx,
-        related: &RelatedContractsContainer,
-    ) -> Result<ValidateResult, ContractError>
-    where
-        Child: ContractComponent,
-        Self::Context: for<'x> From<&'x Ctx>,
-    {
-        for v in self {
-            match v.verify::<Child, Ctx>(parameters, context, related)? {
-                ValidateResult::Invalid => return Ok(ValidateResult::Invalid),
-                ValidateResult::RequestRelated(related) => {
-                    return Ok(ValidateResult::RequestRelated(related))
-                }
-                ValidateResult::Valid => {}
-            }
+    fn entries(&self) -> impl Iterator<Item = (K, &V)> {
+        self.iter().map(|(k, v)| (k.clone(), v))
+    }
+
+    fn entry_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn insert_entry(&mut self, key: K, value: V) -> Result<(), ContractError> {
+        self.insert(key, value);
+        Ok(())
+    }
+}
+
+impl<K, V> Entries for HashMap<K, V>
+where
+    K: Ord + Hash + Clone,
+    V: ContractComponent + Clone,
+{
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> impl Iterator<Item = (K, &V)> {
+        self.iter().map(|(k, v)| (k.clone(), v))
+    }
+
+    fn entry_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn insert_entry(&mut self, key: K, value: V) -> Result<(), ContractError> {
+        self.insert(key, value);
+        Ok(())
+    }
+}
+
+impl<T: ContractComponent + Clone> Entries for Option<T> {
+    type Key = ();
+    type Value = T;
+
+    fn entries(&self) -> impl Iterator<Item = ((), &T)> {
+        self.iter().map(|v| ((), v))
+    }
+
+    fn entry_mut(&mut self, _: &()) -> Option<&mut T> {
+        self.as_mut()
+    }
+
+    fn insert_entry(&mut self, _: (), value: T) -> Result<(), ContractError> {
+        *self = Some(value);
+        Ok(())
+    }
+}
+
+fn verify_entries<C, Child, Ctx>(
+    collection: &C,
+    parameters: &Params<C>,
+    context: &Ctx,
+    related: &RelatedContractsContainer,
+) -> Result<ValidateResult, ContractError>
+where
+    C: Entries,
+    Child: ContractComponent,
+    <C::Value as ContractComponent>::Context: for<'x> From<&'x Ctx>,
+{
+    for (_, v) in collection.entries() {
+        match v.verify::<Child, Ctx>(parameters, context, related)? {
+            ValidateResult::Valid => {}
+            result => return Ok(result),
         }
-        Ok(ValidateResult::Valid)
     }
+    Ok(ValidateResult::Valid)
+}
 
-    fn merge(
-        &mut self,
-        parameters: &Self::Parameters,
-        update: &TypedUpdateData<Self>,
-        related: &RelatedContractsContainer,
-    ) -> MergeResult {
-        for v in self {
-            let update = match update {
-                TypedUpdateData::RelatedState { state } => TypedUpdateData::RelatedState {
-                    state: state[0].clone(),
-                },
-                TypedUpdateData::RelatedDelta { delta } => TypedUpdateData::RelatedDelta {
-                    delta: delta.clone(),
-                },
-                TypedUpdateData::RelatedStateAndDelta { state, delta } => {
-                    TypedUpdateData::RelatedStateAndDelta {
-                        state: state[0].clone(),
-                        delta: delta.clone(),
+fn merge_keyed<C>(
+    collection: &mut C,
+    parameters: &Params<C>,
+    state: Option<&C>,
+    delta: Option<&EntryDelta<C>>,
+    related: &RelatedContractsContainer,
+) -> MergeResult
+where
+    C: Entries,
+    <C::Value as ContractComponent>::Delta: Clone,
+{
+    type Update<C> = (
+        Option<<C as Entries>::Value>,
+        Option<<<C as Entries>::Value as ContractComponent>::Delta>,
+    );
+    let mut updates: BTreeMap<C::Key, Update<C>> = BTreeMap::new();
+    if let Some(state) = state {
+        for (key, value) in state.entries() {
+            updates.entry(key).or_default().0 = Some(value.clone());
+        }
+    }
+    if let Some(delta) = delta {
+        for (key, value) in &delta.added {
+            updates.entry(key.clone()).or_default().0 = Some(value.clone());
+        }
+        for (key, delta) in &delta.updated {
+            updates.entry(key.clone()).or_default().1 = Some(delta.clone());
+        }
+    }
+    for (key, (state, delta)) in updates {
+        let result = match collection.entry_mut(&key) {
+            Some(current) => match TypedUpdateData::try_from((state, delta)) {
+                Ok(update) => current.merge(parameters, &update, related),
+                Err(err) => return MergeResult::Error(err),
+            },
+            None => {
+                // an entry can only be created from its full state
+                let Some(mut value) = state else {
+                    return MergeResult::Error(ContractError::InvalidDelta);
+                };
+                if let Some(delta) = delta {
+                    let update = TypedUpdateData::RelatedDelta { delta };
+                    match value.merge(parameters, &update, related) {
+                        MergeResult::Success => {}
+                        result => return result,
                     }
                 }
-            };
-            match v.merge(parameters, &update, related) {
-                MergeResult::RequestRelated(req) => return MergeResult::RequestRelated(req),
-                MergeResult::Error(err) => return MergeResult::Error(err),
-                MergeResult::Success => {}
+                match collection.insert_entry(key, value) {
+                    Ok(()) => MergeResult::Success,
+                    Err(err) => MergeResult::Error(err),
+                }
             }
+        };
+        match result {
+            MergeResult::Success => {}
+            result => return result,
         }
-        MergeResult::Success
     }
+    MergeResult::Success
+}
 
-    fn summarize<ParentSummary>(
-        &self,
-        parameters: &Self::Parameters,
-        summary: &mut ParentSummary,
-    ) -> Result<(), ContractError>
-    where
-        ParentSummary: Mergeable<<Self as ContractComponent>::Summary>,
-    {
-        for v in self {
-            v.summarize(parameters, summary);
+fn summarize_entries<C: Entries>(
+    collection: &C,
+    parameters: &Params<C>,
+) -> Result<EntrySummary<C>, ContractError> {
+    let mut entries = BTreeMap::new();
+    for (key, value) in collection.entries() {
+        // each entry merges its own summary once
+        let mut collected = Collected::new(replace);
+        value.summarize(parameters, &mut collected)?;
+        if let Some(summary) = collected.into_inner() {
+            entries.insert(key, summary);
         }
-        Ok(())
     }
+    Ok(KeyedSummary { entries })
+}
 
-    fn delta(
-        &self,
-        parameters: &Self::Parameters,
-        summary: &Self::Summary,
-    ) -> Result<Self::Delta, ContractError> {
-        let mut delta: Option<<T as ContractComponent>::Delta> = None;
-        for v in self {
-            let other_delta = v.delta(parameters, summary)?;
-            if let Some(delta) = &mut delta {
-                delta.merge(other_delta);
-            } else {
-                delta = Some(other_delta)
+fn delta_entries<C: Entries>(
+    collection: &C,
+    parameters: &Params<C>,
+    summary: &EntrySummary<C>,
+) -> Result<EntryDelta<C>, ContractError> {
+    let mut delta = KeyedDelta::default();
+    for (key, value) in collection.entries() {
+        match summary.entries.get(&key) {
+            Some(summary) => {
+                let entry_delta = value.delta(parameters, summary)?;
+                if !C::Value::is_unchanged(&entry_delta) {
+                    delta.updated.insert(key, entry_delta);
+                }
+            }
+            None => {
+                delta.added.insert(key, value.clone());
             }
         }
-        delta.ok_or(ContractError::InvalidDelta)
     }
+    Ok(delta)
 }
 
+/// Implements [`ContractComponent`] for a collection through its [`Entries`] impl.
+macro_rules! keyed_component {
+    ($ty:ty, $key:ty, $value:ident, [$($bounds:tt)*]) => {
+        impl<$($bounds)*> ContractComponent for $ty
+        where
+            $value: ContractComponent + Clone,
+            <$value as ContractComponent>::Delta: Clone,
+        {
+            type Context = $value::Context;
+            type Parameters = $value::Parameters;
+            type Delta = KeyedDelta<$key, $value, $value::Delta>;
+            type Summary = KeyedSummary<$key, $value::Summary>;
+
+            fn verify<Child, Ctx>(
+                &self,
+                parameters: &Self::Parameters,
+                context: &Ctx,
+                related: &RelatedContractsContainer,
+            ) -> Result<ValidateResult, ContractError>
+            where
+                Child: ContractComponent,
+                Self::Context: for<'x> From<&'x Ctx>,
+            {
+                verify_entries::<_, Child, Ctx>(self, parameters, context, related)
+            }
+
+            fn merge(
+                &mut self,
+                parameters: &Self::Parameters,
+                update: &TypedUpdateData<Self>,
+                related: &RelatedContractsContainer,
+            ) -> MergeResult {
+                let (state, delta) = match update {
+                    TypedUpdateData::RelatedState { state } => (Some(state), None),
+                    TypedUpdateData::RelatedDelta { delta } => (None, Some(delta)),
+                    TypedUpdateData::RelatedStateAndDelta { state, delta } => {
+                        (Some(state), Some(delta))
+                    }
+                };
+                merge_keyed(self, parameters, state, delta, related)
+            }
+
+            fn summarize<ParentSummary>(
+                &self,
+                parameters: &Self::Parameters,
+                summary: &mut ParentSummary,
+            ) -> Result<(), ContractError>
+            where
+                ParentSummary: Mergeable<<Self as ContractComponent>::Summary>,
+            {
+                summary.merge(summarize_entries(self, parameters)?);
+                Ok(())
+            }
+
+            fn delta(
+                &self,
+                parameters: &Self::Parameters,
+                summary: &Self::Summary,
+            ) -> Result<Self::Delta, ContractError> {
+                delta_entries(self, parameters, summary)
+            }
+
+            fn is_unchanged(delta: &Self::Delta) -> bool {
+                delta.is_empty()
+            }
+        }
+    };
+}
+
+keyed_component!(Vec<T>, usize, T, [T]);
+keyed_component!(BTreeMap<K, V>, K, V, [K: Ord + Clone + 'static, V]);
+keyed_component!(HashMap<K, V>, K, V, [K: Ord + Hash + Clone + 'static, V]);
+keyed_component!(Option<T>, (), T, [T]);
+
 pub struct NoContext;
 
+pub use freenet_macros::ContractComponent;
+
 impl<'x, T> From<&'x T> for NoContext {
     fn from(_: &'x T) -> Self {
         NoContext
     }
 }
 
+/// Parameters of components which don't need any, like the [`crate::crdt`] types.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoParameters;
+
+impl ComponentParameter for NoParameters {
+    fn contract_id(&self) -> Option<ContractInstanceId> {
+        None
+    }
+}
+
+impl<'x, T> From<&'x T> for NoParameters {
+    fn from(_: &'x T) -> Self {
+        NoParameters
+    }
+}
+
 pub mod from_bytes {
     use serde::de::DeserializeOwned;
 
@@ -248,14 +646,90 @@ pub mod from_bytes {
         let ctx = Ctx::from(&typed_state);
         match typed_state.verify::<Child, Ctx>(&typed_params, &ctx, &related_container)? {
             ValidateResult::Valid => {}
-            ValidateResult::Invalid => return Ok(ValidateResult::Invalid),
-            ValidateResult::RequestRelated(related) => {
-                return Ok(ValidateResult::RequestRelated(related))
-            }
+            result => return Ok(result),
         }
         Ok(ValidateResult::Valid)
     }
 
+    /// Validates the state of a component without listing its children, as done for
+    /// `#[derive(ContractComponent)]` composites.
+    pub fn inner_validate_component<T>(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+        related: RelatedContracts<'static>,
+    ) -> Result<ValidateResult, ContractError>
+    where
+        T: ContractComponent + EncodingAdapter,
+        <T as EncodingAdapter>::Parameters: Into<<T as ContractComponent>::Parameters>,
+        <T as ContractComponent>::Context: for<'x> From<&'x T>,
+        ContractError: From<
+            <<T as EncodingAdapter>::ParametersEncoder as Encoder<
+                <T as EncodingAdapter>::Parameters,
+            >>::Error,
+        >,
+        ContractError: From<<<T as EncodingAdapter>::SelfEncoder as Encoder<T>>::Error>,
+    {
+        let typed_params: <T as ContractComponent>::Parameters =
+            <<T as EncodingAdapter>::ParametersEncoder>::deserialize(parameters.as_ref())?.into();
+        let typed_state: T = <<T as EncodingAdapter>::SelfEncoder>::deserialize(state.as_ref())?;
+        let related_container = RelatedContractsContainer::from(related);
+        typed_state.verify::<T, T>(&typed_params, &typed_state, &related_container)
+    }
+
+    /// Applies updates to the state of a component without listing its children, as
+    /// done for `#[derive(ContractComponent)]` composites.
+    pub fn inner_update_component<T>(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+        data: Vec<UpdateData<'static>>,
+    ) -> Result<UpdateModification<'static>, ContractError>
+    where
+        T: ContractComponent + EncodingAdapter,
+        <T as EncodingAdapter>::Parameters: Into<<T as ContractComponent>::Parameters>,
+        <T as EncodingAdapter>::Delta: Into<<T as ContractComponent>::Delta>,
+        ContractError: From<
+            <<T as EncodingAdapter>::ParametersEncoder as Encoder<
+                <T as EncodingAdapter>::Parameters,
+            >>::Error,
+        >,
+        ContractError: From<
+            <<T as EncodingAdapter>::DeltaEncoder as Encoder<<T as EncodingAdapter>::Delta>>::Error,
+        >,
+        ContractError: From<<<T as EncodingAdapter>::SelfEncoder as Encoder<T>>::Error>,
+    {
+        let typed_params =
+            <<T as EncodingAdapter>::ParametersEncoder>::deserialize(parameters.as_ref())?.into();
+        let mut typed_state: T =
+            <<T as EncodingAdapter>::SelfEncoder>::deserialize(state.as_ref())?;
+        let self_updates = UpdateData::get_self_states(&data);
+        let related_container = RelatedContractsContainer::from(data);
+        for (state, delta) in self_updates {
+            // an empty delta is what `get_state_delta` sends when nothing changed
+            let delta = delta.filter(|d| !d.as_ref().is_empty());
+            if state.is_none() && delta.is_none() {
+                continue;
+            }
+            let state = state
+                .map(|s| <<T as EncodingAdapter>::SelfEncoder>::deserialize(s.as_ref()))
+                .transpose()?;
+            let delta = delta
+                .map(|d| {
+                    <<T as EncodingAdapter>::DeltaEncoder>::deserialize(d.as_ref()).map(Into::into)
+                })
+                .transpose()?;
+            let typed_update = TypedUpdateData::try_from((state, delta))?;
+            match typed_state.merge(&typed_params, &typed_update, &related_container) {
+                MergeResult::Success => {}
+                MergeResult::RequestRelated(req) => {
+                    return UpdateModification::requires(req.into());
+                }
+                MergeResult::Error(err) => return Err(err),
+            }
+        }
+        let encoded = <<T as EncodingAdapter>::SelfEncoder>::serialize(&typed_state)?;
+        Ok(UpdateModification::valid(encoded.into()))
+    }
+
     pub fn inner_update_state<T, Child>(
         parameters: Parameters<'static>,
         state: State<'static>,
@@ -286,6 +760,11 @@ pub mod from_bytes {
         let self_updates = UpdateData::get_self_states(&data);
         let related_container = RelatedContractsContainer::from(data);
         for (state, delta) in self_updates {
+            // an empty delta is what `get_state_delta` sends when nothing changed
+            let delta = delta.filter(|d| !d.as_ref().is_empty());
+            if state.is_none() && delta.is_none() {
+                continue;
+            }
             let state = state
                 .map(|s| <<T as EncodingAdapter>::SelfEncoder>::deserialize(s.as_ref()))
                 .transpose()?;
@@ -361,3 +840,187 @@ pub mod from_bytes {
         typed_state.delta(&typed_params, &typed_summary)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grow-only counter, merged by keeping the highest value.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter(u64);
+
+    struct NoParams;
+
+    impl ComponentParameter for NoParams {
+        fn contract_id(&self) -> Option<ContractInstanceId> {
+            None
+        }
+    }
+
+    impl ContractComponent for Counter {
+        type Context = NoContext;
+        type Parameters = NoParams;
+        type Delta = u64;
+        type Summary = u64;
+
+        fn verify<Child, Ctx>(
+            &self,
+            _: &Self::Parameters,
+            _: &Ctx,
+            _: &RelatedContractsContainer,
+        ) -> Result<ValidateResult, ContractError>
+        where
+            Child: ContractComponent,
+            Self::Context: for<'x> From<&'x Ctx>,
+        {
+            if self.0 == 0 {
+                return Ok(ValidateResult::Invalid);
+            }
+            Ok(ValidateResult::Valid)
+        }
+
+        fn merge(
+            &mut self,
+            _: &Self::Parameters,
+            update: &TypedUpdateData<Self>,
+            _: &RelatedContractsContainer,
+        ) -> MergeResult {
+            let other = match update {
+                TypedUpdateData::RelatedState { state } => state.0,
+                TypedUpdateData::RelatedDelta { delta } => *delta,
+                TypedUpdateData::RelatedStateAndDelta { state, delta } => state.0.max(*delta),
+            };
+            self.0 = self.0.max(other);
+            MergeResult::Success
+        }
+
+        fn summarize<ParentSummary>(
+            &self,
+            _: &Self::Parameters,
+            summary: &mut ParentSummary,
+        ) -> Result<(), ContractError>
+        where
+            ParentSummary: Mergeable<u64>,
+        {
+            summary.merge(self.0);
+            Ok(())
+        }
+
+        fn delta(&self, _: &Self::Parameters, summary: &u64) -> Result<u64, ContractError> {
+            Ok(if self.0 > *summary { self.0 } else { 0 })
+        }
+
+        fn is_unchanged(delta: &u64) -> bool {
+            *delta == 0
+        }
+    }
+
+    fn summary_of<T: ContractComponent<Parameters = NoParams>>(component: &T) -> T::Summary {
+        let mut collected = Collected::new(replace);
+        component.summarize(&NoParams, &mut collected).unwrap();
+        collected.into_inner().unwrap()
+    }
+
+    fn sync<T>(from: &T, to: &mut T)
+    where
+        T: ContractComponent<Parameters = NoParams>,
+    {
+        let delta = from.delta(&NoParams, &summary_of(to)).unwrap();
+        let update = TypedUpdateData::RelatedDelta { delta };
+        let result = to.merge(&NoParams, &update, &RelatedContractsContainer::default());
+        assert!(matches!(result, MergeResult::Success));
+    }
+
+    #[test]
+    fn vec_deltas_address_each_entry() {
+        let local = vec![Counter(4), Counter(5)];
+        let mut remote = vec![Counter(3)];
+
+        let delta = local.delta(&NoParams, &summary_of(&remote)).unwrap();
+        assert_eq!(delta.updated(), &BTreeMap::from([(0, 4)]));
+        assert_eq!(delta.added(), &BTreeMap::from([(1, Counter(5))]));
+
+        sync(&local, &mut remote);
+        assert_eq!(remote, vec![Counter(4), Counter(5)]);
+    }
+
+    #[test]
+    fn unchanged_entries_are_left_out() {
+        let local = BTreeMap::from([("a", Counter(4)), ("b", Counter(1))]);
+        let remote = BTreeMap::from([("a", Counter(4)), ("b", Counter(1))]);
+        let delta = local.delta(&NoParams, &summary_of(&remote)).unwrap();
+        assert!(delta.is_empty());
+        assert!(BTreeMap::<&str, Counter>::is_unchanged(&delta));
+
+        let nested = vec![local.clone()];
+        let delta = nested.delta(&NoParams, &summary_of(&vec![remote])).unwrap();
+        assert!(delta.is_empty(), "nested collections are unchanged too");
+    }
+
+    #[test]
+    fn maps_merge_by_key() {
+        let local = BTreeMap::from([("a", Counter(4)), ("b", Counter(1))]);
+        let mut remote = BTreeMap::from([("b", Counter(2)), ("c", Counter(7))]);
+        sync(&local, &mut remote);
+        assert_eq!(
+            remote,
+            BTreeMap::from([("a", Counter(4)), ("b", Counter(2)), ("c", Counter(7))])
+        );
+
+        let local = HashMap::from([(1u8, Counter(9))]);
+        let mut remote = HashMap::from([(1u8, Counter(3)), (2, Counter(1))]);
+        let state = TypedUpdateData::RelatedState { state: local };
+        let result = remote.merge(&NoParams, &state, &RelatedContractsContainer::default());
+        assert!(matches!(result, MergeResult::Success));
+        assert_eq!(remote, HashMap::from([(1, Counter(9)), (2, Counter(1))]));
+    }
+
+    #[test]
+    fn option_fills_missing_value() {
+        let local = Some(Counter(2));
+        let mut remote: Option<Counter> = None;
+        assert!(summary_of(&remote).is_empty());
+        sync(&local, &mut remote);
+        assert_eq!(remote, Some(Counter(2)));
+    }
+
+    #[test]
+    fn rejects_unaddressable_entries() {
+        let related = RelatedContractsContainer::default();
+        let mut list = vec![Counter(1)];
+
+        let delta = KeyedDelta {
+            updated: BTreeMap::from([(3, 1)]),
+            added: BTreeMap::new(),
+        };
+        let update = TypedUpdateData::RelatedDelta { delta };
+        assert!(matches!(
+            list.merge(&NoParams, &update, &related),
+            MergeResult::Error(ContractError::InvalidDelta)
+        ));
+
+        let delta = KeyedDelta {
+            updated: BTreeMap::new(),
+            added: BTreeMap::from([(2, Counter(1))]),
+        };
+        let update = TypedUpdateData::RelatedDelta { delta };
+        assert!(matches!(
+            list.merge(&NoParams, &update, &related),
+            MergeResult::Error(ContractError::InvalidUpdateWithInfo { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_and_summarize_report_entry_results() {
+        let related = RelatedContractsContainer::default();
+        let list = vec![Counter(1), Counter(0)];
+        let result = list.verify::<Counter, NoContext>(&NoParams, &NoContext, &related);
+        assert!(matches!(result, Ok(ValidateResult::Invalid)));
+
+        let summary = summary_of(&list);
+        let encoded = serde_json::to_string(&summary).unwrap();
+        assert_eq!(encoded, r#"{"entries":[[0,1],[1,0]]}"#);
+        let decoded: KeyedSummary<usize, u64> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, summary);
+    }
+}
@@ -2,6 +2,7 @@
 use std::{
     collections::{HashMap, HashSet},
     marker::PhantomData,
+    time::Duration,
 };
 
 use serde::{de::DeserializeOwned, Serialize};
@@ -18,8 +19,14 @@ pub enum MergeResult {
 #[derive(Default)]
 pub struct RelatedContractsContainer {
     contracts: HashMap<ContractInstanceId, State<'static>>,
-    pending: HashSet<ContractInstanceId>,
+    pending: HashMap<ContractInstanceId, RelatedContract>,
     not_found: HashSet<ContractInstanceId>,
+    /// Updates of related contracts received along the own updates, in arrival order.
+    updates: Vec<(
+        ContractInstanceId,
+        Option<State<'static>>,
+        Option<StateDelta<'static>>,
+    )>,
 }
 
 impl From<RelatedContracts<'static>> for RelatedContractsContainer {
@@ -38,22 +45,15 @@ impl From<RelatedContracts<'static>> for RelatedContractsContainer {
         }
         RelatedContractsContainer {
             contracts,
-            pending: HashSet::new(),
             not_found,
+            ..Default::default()
         }
     }
 }
 
 impl From<RelatedContractsContainer> for Vec<crate::contract_interface::RelatedContract> {
     fn from(related: RelatedContractsContainer) -> Self {
-        related
-            .pending
-            .into_iter()
-            .map(|id| RelatedContract {
-                contract_instance_id: id,
-                mode: RelatedMode::StateOnce,
-            })
-            .collect()
+        related.pending.into_values().collect()
     }
 }
 
@@ -63,12 +63,19 @@ impl From<Vec<UpdateData<'static>>> for RelatedContractsContainer {
         for update in updates {
             match update {
                 UpdateData::RelatedState { related_to, state } => {
-                    this.contracts.insert(related_to, state);
+                    this.contracts.insert(related_to, state.clone());
+                    this.updates.push((related_to, Some(state), None));
+                }
+                UpdateData::RelatedDelta { related_to, delta } => {
+                    this.updates.push((related_to, None, Some(delta)));
                 }
                 UpdateData::RelatedStateAndDelta {
-                    related_to, state, ..
+                    related_to,
+                    state,
+                    delta,
                 } => {
-                    this.contracts.insert(related_to, state);
+                    this.contracts.insert(related_to, state.clone());
+                    this.updates.push((related_to, Some(state), Some(delta)));
                 }
                 _ => {}
             }
@@ -87,7 +94,7 @@ impl RelatedContractsContainer {
             let state = <<C as EncodingAdapter>::SelfEncoder>::deserialize(res.as_ref())?;
             return Ok(Related::Found { state });
         }
-        if self.pending.contains(&id) {
+        if self.pending.contains_key(&id) {
             return Ok(Related::RequestPending);
         }
         if self.not_found.contains(&id) {
@@ -96,8 +103,66 @@ impl RelatedContractsContainer {
         Ok(Related::NotRequested)
     }
 
+    /// The updates of the related contract received along the own updates, in the
+    /// order they arrived.
+    ///
+    /// Deltas only arrive for contracts previously requested with
+    /// [`RelatedMode::StateThenSubscribe`], see [`Self::subscribe`].
+    pub fn updates<C: TypedContract>(
+        &self,
+        params: &C::Parameters,
+    ) -> Result<Vec<TypedUpdateData<C>>, ContractError> {
+        let id = <C as TypedContract>::instance_id(params);
+        self.updates
+            .iter()
+            .filter(|(related_to, _, _)| *related_to == id)
+            .map(|(_, state, delta)| {
+                let state = state
+                    .as_ref()
+                    .map(|s| <<C as EncodingAdapter>::SelfEncoder>::deserialize(s.as_ref()))
+                    .transpose()
+                    .map_err(Into::into)?;
+                let delta = delta
+                    .as_ref()
+                    .map(|d| <<C as EncodingAdapter>::DeltaEncoder>::deserialize(d.as_ref()))
+                    .transpose()
+                    .map_err(Into::into)?;
+                TypedUpdateData::try_from((state, delta))
+            })
+            .collect()
+    }
+
+    /// Requests the state of a related contract once.
     pub fn request<C: TypedContract>(&mut self, id: ContractInstanceId) {
-        self.pending.insert(id);
+        self.request_with::<C>(id, RelatedMode::StateOnce, None);
+    }
+
+    /// Requests the state of a related contract, and keeps receiving its updates as
+    /// [`TypedUpdateData::RelatedDelta`] until `expires_after` elapses.
+    pub fn subscribe<C: TypedContract>(
+        &mut self,
+        id: ContractInstanceId,
+        expires_after: Option<Duration>,
+    ) {
+        self.request_with::<C>(id, RelatedMode::StateThenSubscribe, expires_after);
+    }
+
+    /// Requests a related contract with the given mode, replacing any previous request
+    /// for the same contract.
+    pub fn request_with<C: TypedContract>(
+        &mut self,
+        id: ContractInstanceId,
+        mode: RelatedMode,
+        expires_after: Option<Duration>,
+    ) {
+        self.pending.insert(
+            id,
+            RelatedContract {
+                contract_instance_id: id,
+                mode,
+                expires_after,
+            },
+        );
     }
 
     pub fn merge(&mut self, other: Self) {
@@ -105,10 +170,12 @@ impl RelatedContractsContainer {
             contracts,
             pending,
             not_found,
+            updates,
         } = other;
         self.pending.extend(pending);
         self.not_found.extend(not_found);
         self.contracts.extend(contracts);
+        self.updates.extend(updates);
     }
 }
 
@@ -208,6 +275,24 @@ pub trait TypedContract: EncodingAdapter {
     ) -> Result<Self::Delta, ContractError>;
 }
 
+/// A [`TypedContract`] which answers read-only queries from clients.
+///
+/// Exported as [`ContractInterface::query_state`] when `query` is passed to the
+/// `#[contract]` attribute, e.g. `#[contract(query)]`.
+pub trait QueryableContract: TypedContract {
+    type Query;
+    type QueryResult;
+
+    type QueryEncoder: Encoder<Self::Query>;
+    type QueryResultEncoder: Encoder<Self::QueryResult>;
+
+    fn query(
+        &self,
+        parameters: Self::Parameters,
+        query: Self::Query,
+    ) -> Result<Self::QueryResult, ContractError>;
+}
+
 pub trait Encoder<T> {
     type Error: Into<ContractError>;
     fn deserialize(bytes: &[u8]) -> Result<T, Self::Error>;
@@ -375,3 +460,183 @@ where
     let encoded = <<T as EncodingAdapter>::DeltaEncoder>::serialize(&summary)?;
     Ok(encoded.into())
 }
+
+pub fn inner_query_state<T>(
+    parameters: Parameters<'static>,
+    state: State<'static>,
+    query: StateQuery<'static>,
+) -> Result<StateQueryResult<'static>, ContractError>
+where
+    T: EncodingAdapter + QueryableContract,
+    ContractError: From<<<T as EncodingAdapter>::SelfEncoder as Encoder<T>>::Error>,
+    ContractError: From<
+        <<T as EncodingAdapter>::ParametersEncoder as Encoder<
+            <T as EncodingAdapter>::Parameters,
+        >>::Error,
+    >,
+    ContractError: From<
+        <<T as QueryableContract>::QueryEncoder as Encoder<<T as QueryableContract>::Query>>::Error,
+    >,
+    ContractError: From<
+        <<T as QueryableContract>::QueryResultEncoder as Encoder<
+            <T as QueryableContract>::QueryResult,
+        >>::Error,
+    >,
+{
+    let typed_params =
+        <<T as EncodingAdapter>::ParametersEncoder>::deserialize(parameters.as_ref())?;
+    let typed_state = <<T as EncodingAdapter>::SelfEncoder>::deserialize(state.as_ref())?;
+    let typed_query = <<T as QueryableContract>::QueryEncoder>::deserialize(query.as_ref())?;
+    let result = typed_state.query(typed_params, typed_query)?;
+    let encoded = <<T as QueryableContract>::QueryResultEncoder>::serialize(&result)?;
+    Ok(encoded.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Feed {
+        entries: Vec<u32>,
+        followed: Vec<u32>,
+    }
+
+    impl EncodingAdapter for Feed {
+        type Parameters = u8;
+        type Delta = Vec<u32>;
+        type Summary = ();
+
+        type SelfEncoder = BincodeEncoder<Self>;
+        type ParametersEncoder = BincodeEncoder<Self::Parameters>;
+        type DeltaEncoder = BincodeEncoder<Self::Delta>;
+        type SummaryEncoder = BincodeEncoder<Self::Summary>;
+    }
+
+    impl TypedContract for Feed {
+        fn instance_id(params: &u8) -> ContractInstanceId {
+            ContractInstanceId::new([*params; CONTRACT_KEY_SIZE])
+        }
+
+        fn verify(
+            &self,
+            _: Self::Parameters,
+            _: RelatedContractsContainer,
+        ) -> Result<ValidateResult, ContractError> {
+            Ok(ValidateResult::Valid)
+        }
+
+        fn merge(
+            &mut self,
+            parameters: &u8,
+            update: TypedUpdateData<Self>,
+            related: &RelatedContractsContainer,
+        ) -> MergeResult {
+            if let TypedUpdateData::RelatedDelta { delta } = update {
+                self.entries.extend(delta);
+            }
+            let followed = parameters + 1;
+            let updates = match related.updates::<Feed>(&followed) {
+                Ok(updates) => updates,
+                Err(err) => return MergeResult::Error(err),
+            };
+            if updates.is_empty() {
+                let mut request = RelatedContractsContainer::default();
+                request
+                    .subscribe::<Feed>(Feed::instance_id(&followed), Some(Duration::from_secs(60)));
+                return MergeResult::RequestRelated(request);
+            }
+            for update in updates {
+                if let TypedUpdateData::RelatedDelta { delta } = update {
+                    self.followed.extend(delta);
+                }
+            }
+            MergeResult::Success
+        }
+
+        fn summarize(&self, _: Self::Parameters) -> Result<(), ContractError> {
+            Ok(())
+        }
+
+        fn delta(&self, _: Self::Parameters, _: ()) -> Result<Vec<u32>, ContractError> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    // looks up an entry by position
+    impl QueryableContract for Feed {
+        type Query = usize;
+        type QueryResult = Option<u32>;
+
+        type QueryEncoder = BincodeEncoder<usize>;
+        type QueryResultEncoder = BincodeEncoder<Option<u32>>;
+
+        fn query(&self, _: u8, index: usize) -> Result<Option<u32>, ContractError> {
+            Ok(self.entries.get(index).copied())
+        }
+    }
+
+    fn update(data: Vec<UpdateData<'static>>) -> UpdateModification<'static> {
+        let state = Feed {
+            entries: vec![],
+            followed: vec![],
+        };
+        inner_update_state::<Feed>(
+            bincode::serialize(&1u8).unwrap().into(),
+            bincode::serialize(&state).unwrap().into(),
+            data,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn related_subscription_round_trip() {
+        let own = UpdateData::Delta(bincode::serialize(&vec![1u32]).unwrap().into());
+        let modification = update(vec![own.clone()]);
+        assert_eq!(
+            modification.get_related(),
+            [RelatedContract::subscribe(
+                Feed::instance_id(&2),
+                Some(Duration::from_secs(60))
+            )]
+        );
+
+        let related = UpdateData::RelatedDelta {
+            related_to: Feed::instance_id(&2),
+            delta: bincode::serialize(&vec![7u32, 8]).unwrap().into(),
+        };
+        let modification = update(vec![own, related]);
+        assert!(!modification.requires_dependencies());
+        let state: Feed = bincode::deserialize(modification.unwrap_valid().as_ref()).unwrap();
+        assert_eq!(state.entries, [1]);
+        assert_eq!(state.followed, [7, 8]);
+    }
+
+    #[test]
+    fn typed_query() {
+        let state = Feed {
+            entries: vec![4, 5],
+            followed: vec![],
+        };
+        let query = |index: usize| {
+            let result = inner_query_state::<Feed>(
+                bincode::serialize(&1u8).unwrap().into(),
+                bincode::serialize(&state).unwrap().into(),
+                bincode::serialize(&index).unwrap().into(),
+            )
+            .unwrap();
+            bincode::deserialize::<Option<u32>>(result.as_ref()).unwrap()
+        };
+        assert_eq!(query(1), Some(5));
+        assert_eq!(query(2), None);
+
+        let malformed = inner_query_state::<Feed>(
+            bincode::serialize(&1u8).unwrap().into(),
+            bincode::serialize(&state).unwrap().into(),
+            StateQuery::from(vec![]),
+        );
+        assert!(matches!(malformed, Err(ContractError::Deser(_))));
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for contract interface operations.
 
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 
 /// Type of errors during interaction with a contract.
@@ -21,4 +23,195 @@ pub enum ContractError {
     InvalidDelta,
     #[error("{0}")]
     Other(String),
+    /// The contract rejected the state or update for a structured reason.
+    ///
+    /// Appended last so the bincode tags of the variants above are unchanged.
+    #[error("rejected: {0}")]
+    Rejected(InvalidReason),
+}
+
+impl ContractError {
+    /// The code identifying the kind of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ContractError::Deser(_) => ErrorCode::DESER,
+            ContractError::InvalidUpdate | ContractError::InvalidUpdateWithInfo { .. } => {
+                ErrorCode::INVALID_UPDATE
+            }
+            ContractError::InvalidState => ErrorCode::INVALID_STATE,
+            ContractError::InvalidDelta => ErrorCode::INVALID_DELTA,
+            ContractError::Other(_) => ErrorCode::OTHER,
+            ContractError::Rejected(reason) => reason.code,
+        }
+    }
+
+    /// The reason of this error, to report it to the node and its clients.
+    pub fn reason(&self) -> InvalidReason {
+        match self {
+            ContractError::Rejected(reason) => reason.clone(),
+            other => InvalidReason::new(other.code(), other.to_string()),
+        }
+    }
+
+    /// A signature which doesn't verify, `field` names the signed part.
+    pub fn bad_signature(field: impl Into<String>) -> Self {
+        ContractError::Rejected(InvalidReason::bad_signature(field))
+    }
+
+    /// An update whose sequence number is not newer than the `current` one.
+    pub fn stale_sequence(current: u64, received: u64) -> Self {
+        ContractError::Rejected(InvalidReason::stale_sequence(current, received))
+    }
+}
+
+impl From<InvalidReason> for ContractError {
+    fn from(reason: InvalidReason) -> Self {
+        ContractError::Rejected(reason)
+    }
+}
+
+/// Code identifying why a contract rejected a state or an update.
+///
+/// Codes below [`ErrorCode::CUSTOM`] are reserved for the ones defined here,
+/// contracts can define their own with [`ErrorCode::custom`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ErrorCode(u32);
+
+impl ErrorCode {
+    /// No specific code.
+    pub const UNSPECIFIED: Self = Self(0);
+    /// The state, delta or parameters could not be decoded.
+    pub const DESER: Self = Self(1);
+    pub const INVALID_STATE: Self = Self(2);
+    pub const INVALID_DELTA: Self = Self(3);
+    pub const INVALID_UPDATE: Self = Self(4);
+    /// A signature doesn't verify.
+    pub const BAD_SIGNATURE: Self = Self(5);
+    /// A sequence number is not newer than the current one.
+    pub const STALE_SEQUENCE: Self = Self(6);
+    /// A related contract the state depends on is missing.
+    pub const MISSING_RELATED: Self = Self(7);
+    /// The caller is not allowed to perform the change.
+    pub const UNAUTHORIZED: Self = Self(8);
+    /// A state or update exceeds a size limit.
+    pub const TOO_LARGE: Self = Self(9);
+    pub const OTHER: Self = Self(999);
+    /// First code available to contracts.
+    pub const CUSTOM: Self = Self(1000);
+
+    /// A contract defined code, offset from [`ErrorCode::CUSTOM`].
+    pub const fn custom(code: u32) -> Self {
+        Self(Self::CUSTOM.0.saturating_add(code))
+    }
+
+    pub const fn from_u32(code: u32) -> Self {
+        Self(code)
+    }
+
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Self::UNSPECIFIED => "unspecified",
+            Self::DESER => "deserialization",
+            Self::INVALID_STATE => "invalid state",
+            Self::INVALID_DELTA => "invalid delta",
+            Self::INVALID_UPDATE => "invalid update",
+            Self::BAD_SIGNATURE => "bad signature",
+            Self::STALE_SEQUENCE => "stale sequence",
+            Self::MISSING_RELATED => "missing related contract",
+            Self::UNAUTHORIZED => "unauthorized",
+            Self::TOO_LARGE => "too large",
+            Self::OTHER => "other",
+            _ => return None,
+        })
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "code {}", self.0),
+        }
+    }
+}
+
+/// Why a contract rejected a state or an update: a code, a message for users and,
+/// optionally, the path of the offending field (e.g. `"messages[3].signature"`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidReason {
+    pub code: ErrorCode,
+    pub message: String,
+    pub field: Option<String>,
+}
+
+impl InvalidReason {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            field: None,
+        }
+    }
+
+    /// Sets the path of the offending field.
+    pub fn at(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// A signature which doesn't verify, `field` names the signed part.
+    pub fn bad_signature(field: impl Into<String>) -> Self {
+        Self::new(ErrorCode::BAD_SIGNATURE, "signature verification failed").at(field)
+    }
+
+    /// An update whose sequence number is not newer than the `current` one.
+    pub fn stale_sequence(current: u64, received: u64) -> Self {
+        Self::new(
+            ErrorCode::STALE_SEQUENCE,
+            format!("sequence {received} is not newer than current sequence {current}"),
+        )
+    }
+}
+
+impl Display for InvalidReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)?;
+        if let Some(field) = &self.field {
+            write!(f, " (at `{field}`)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejected_keeps_existing_bincode_tags() {
+        let tag = |err: &ContractError| bincode::serialize(err).unwrap()[..4].to_vec();
+        assert_eq!(tag(&ContractError::InvalidDelta), [4, 0, 0, 0]);
+        assert_eq!(tag(&ContractError::Other(String::new())), [5, 0, 0, 0]);
+
+        let err = ContractError::bad_signature("delta.signature");
+        assert_eq!(tag(&err), [6, 0, 0, 0]);
+        assert_eq!(err.code(), ErrorCode::BAD_SIGNATURE);
+        assert_eq!(
+            err.to_string(),
+            "rejected: bad signature: signature verification failed (at `delta.signature`)"
+        );
+        let decoded: ContractError =
+            bincode::deserialize(&bincode::serialize(&err).unwrap()).unwrap();
+        assert_eq!(decoded.reason(), err.reason());
+
+        let reason = ContractError::InvalidState.reason();
+        assert_eq!(reason.code, ErrorCode::INVALID_STATE);
+        assert_eq!(reason.field, None);
+        assert_eq!(ErrorCode::custom(2).to_string(), "code 1002");
+    }
 }
@@ -108,6 +108,12 @@ impl Deref for ContractInstanceId {
     }
 }
 
+impl AsRef<ContractInstanceId> for ContractInstanceId {
+    fn as_ref(&self) -> &ContractInstanceId {
+        self
+    }
+}
+
 impl FromStr for ContractInstanceId {
     type Err = bs58::decode::Error;
 
@@ -250,6 +256,12 @@ impl From<ContractKey> for ContractInstanceId {
     }
 }
 
+impl AsRef<ContractInstanceId> for ContractKey {
+    fn as_ref(&self) -> &ContractInstanceId {
+        &self.instance
+    }
+}
+
 impl Deref for ContractKey {
     type Target = [u8; CONTRACT_KEY_SIZE];
 
@@ -23,9 +23,9 @@ mod tests;
 // Re-export all public types
 pub use code::ContractCode;
 pub use contract::Contract;
-pub use error::ContractError;
+pub use error::{ContractError, ErrorCode, InvalidReason};
 pub use key::{ContractInstanceId, ContractKey};
-pub use state::{State, StateDelta, StateSummary};
+pub use state::{State, StateDelta, StateQuery, StateQueryResult, StateSummary};
 pub use trait_def::ContractInterface;
 pub use update::{
     RelatedContract, RelatedContracts, RelatedMode, UpdateData, UpdateModification, ValidateResult,
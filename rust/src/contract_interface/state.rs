@@ -1,4 +1,5 @@
-//! Contract state types: State, StateDelta, and StateSummary.
+//! Contract state types: State, StateDelta, StateSummary, and the read-only query
+//! payloads StateQuery and StateQueryResult.
 
 use std::{
     borrow::Cow,
@@ -220,3 +221,126 @@ impl DerefMut for StateSummary<'_> {
         &mut self.0
     }
 }
+
+/// A read-only question about a contract state, answered by
+/// [`ContractInterface::query_state`](super::ContractInterface::query_state).
+///
+/// The format is determined by the contract, like the one of the state itself.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(arbitrary::Arbitrary))]
+pub struct StateQuery<'a>(
+    #[serde_as(as = "serde_with::Bytes")]
+    #[serde(borrow)]
+    Cow<'a, [u8]>,
+);
+
+impl StateQuery<'_> {
+    /// Extracts the owned data as a `Vec<u8>`.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0.into_owned()
+    }
+
+    /// Gets the number of bytes of data stored in the `StateQuery`.
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn into_owned(self) -> StateQuery<'static> {
+        StateQuery(self.0.into_owned().into())
+    }
+
+    pub fn deser_state_query<'de, D>(deser: D) -> Result<StateQuery<'static>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <StateQuery as Deserialize>::deserialize(deser)?;
+        Ok(value.into_owned())
+    }
+}
+
+impl From<Vec<u8>> for StateQuery<'_> {
+    fn from(query: Vec<u8>) -> Self {
+        StateQuery(Cow::from(query))
+    }
+}
+
+impl<'a> From<&'a [u8]> for StateQuery<'a> {
+    fn from(query: &'a [u8]) -> Self {
+        StateQuery(Cow::from(query))
+    }
+}
+
+impl AsRef<[u8]> for StateQuery<'_> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'a> Deref for StateQuery<'a> {
+    type Target = Cow<'a, [u8]>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The answer of a contract to a [`StateQuery`].
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(arbitrary::Arbitrary))]
+pub struct StateQueryResult<'a>(
+    #[serde_as(as = "serde_with::Bytes")]
+    #[serde(borrow)]
+    Cow<'a, [u8]>,
+);
+
+impl StateQueryResult<'_> {
+    /// Extracts the owned data as a `Vec<u8>`.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0.into_owned()
+    }
+
+    /// Gets the number of bytes of data stored in the `StateQueryResult`.
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn into_owned(self) -> StateQueryResult<'static> {
+        StateQueryResult(self.0.into_owned().into())
+    }
+
+    pub fn deser_state_query_result<'de, D>(deser: D) -> Result<StateQueryResult<'static>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <StateQueryResult as Deserialize>::deserialize(deser)?;
+        Ok(value.into_owned())
+    }
+}
+
+impl From<Vec<u8>> for StateQueryResult<'_> {
+    fn from(result: Vec<u8>) -> Self {
+        StateQueryResult(Cow::from(result))
+    }
+}
+
+impl<'a> From<&'a [u8]> for StateQueryResult<'a> {
+    fn from(result: &'a [u8]) -> Self {
+        StateQueryResult(Cow::from(result))
+    }
+}
+
+impl AsRef<[u8]> for StateQueryResult<'_> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'a> Deref for StateQueryResult<'a> {
+    type Target = Cow<'a, [u8]>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -5,8 +5,8 @@
 use crate::parameters::Parameters;
 
 use super::{
-    ContractError, RelatedContracts, State, StateDelta, StateSummary, UpdateData,
-    UpdateModification, ValidateResult,
+    ContractError, RelatedContracts, State, StateDelta, StateQuery, StateQueryResult, StateSummary,
+    UpdateData, UpdateModification, ValidateResult,
 };
 
 /// Trait to implement for the contract building.
@@ -133,5 +133,23 @@ pub trait ContractInterface {
         state: State<'static>,
         summary: StateSummary<'static>,
     ) -> Result<StateDelta<'static>, ContractError>;
+
+    /// Answer a read-only query about the state on behalf of a client.
+    ///
+    /// The query and the result are opaque bytes whose format the contract defines. The
+    /// state is never modified: whatever this returns goes only to the client that asked,
+    /// so a client can read part of a large state without fetching all of it.
+    ///
+    /// Optional. Contracts that don't override it reject every query, and the `#[contract]`
+    /// macro only exports the `query_state` function when the impl defines this method.
+    fn query_state(
+        _parameters: Parameters<'static>,
+        _state: State<'static>,
+        _query: StateQuery<'static>,
+    ) -> Result<StateQueryResult<'static>, ContractError> {
+        Err(ContractError::Other(
+            "contract does not support queries".to_owned(),
+        ))
+    }
 }
 // ANCHOR_END: contractifce
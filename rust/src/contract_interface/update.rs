@@ -3,7 +3,7 @@
 //! This module provides types for updating contract state, managing related contracts,
 //! and validation results.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
@@ -12,7 +12,9 @@ use crate::common_generated::common::{UpdateData as FbsUpdateData, UpdateDataTyp
 use crate::generated::client_request::RelatedContracts as FbsRelatedContracts;
 
 use super::key::instance_id_from_fbs;
-use super::{ContractError, ContractInstanceId, State, StateDelta, CONTRACT_KEY_SIZE};
+use super::{
+    ContractError, ContractInstanceId, InvalidReason, State, StateDelta, CONTRACT_KEY_SIZE,
+};
 
 /// An update to a contract state or any required related contracts to update that state.
 // todo: this should be an enum probably
@@ -162,15 +164,78 @@ impl<'a> From<HashMap<ContractInstanceId, Option<State<'a>>>> for RelatedContrac
 
 /// A contract related to an other contract and the specification
 /// of the kind of update notifications that should be received by this contract.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct RelatedContract {
     pub contract_instance_id: ContractInstanceId,
     pub mode: RelatedMode,
-    // todo: add a timeout so we stop listening/subscribing eventually
+    /// How long after the request the peer should keep sending updates of the
+    /// related contract, `None` to keep them coming for as long as the peer can.
+    ///
+    /// Only meaningful for [`RelatedMode::StateThenSubscribe`].
+    ///
+    /// Added in ABI revision 2; modules built for older revisions encode related contracts
+    /// without it (see [`LegacyUpdateModification`]).
+    pub expires_after: Option<Duration>,
+}
+
+/// [`UpdateModification`] as encoded by contract modules built for ABI revisions before 2,
+/// whose related contracts carry no expiry.
+#[derive(Debug, Deserialize)]
+pub(crate) struct LegacyUpdateModification<'a> {
+    #[serde(borrow)]
+    new_state: Option<State<'a>>,
+    related: Vec<LegacyRelatedContract>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyRelatedContract {
+    contract_instance_id: ContractInstanceId,
+    mode: RelatedMode,
+}
+
+impl<'a> From<LegacyUpdateModification<'a>> for UpdateModification<'a> {
+    fn from(legacy: LegacyUpdateModification<'a>) -> Self {
+        Self {
+            new_state: legacy.new_state,
+            related: legacy
+                .related
+                .into_iter()
+                .map(|related| RelatedContract {
+                    contract_instance_id: related.contract_instance_id,
+                    mode: related.mode,
+                    expires_after: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RelatedContract {
+    /// Requests the state of `contract_instance_id` once.
+    pub fn state_once(contract_instance_id: ContractInstanceId) -> Self {
+        Self {
+            contract_instance_id,
+            mode: RelatedMode::StateOnce,
+            expires_after: None,
+        }
+    }
+
+    /// Requests the state of `contract_instance_id` and its updates until
+    /// `expires_after` elapses.
+    pub fn subscribe(
+        contract_instance_id: ContractInstanceId,
+        expires_after: Option<Duration>,
+    ) -> Self {
+        Self {
+            contract_instance_id,
+            mode: RelatedMode::StateThenSubscribe,
+            expires_after,
+        }
+    }
 }
 
 /// Specification of the notifications of interest from a related contract.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum RelatedMode {
     /// Retrieve the state once, don't be concerned with subsequent changes.
     StateOnce,
@@ -186,6 +251,37 @@ pub enum ValidateResult {
     /// The peer will attempt to retrieve the requested contract states
     /// and will call validate_state() again when it retrieves them.
     RequestRelated(Vec<ContractInstanceId>),
+    /// Same as [`ValidateResult::Invalid`], with the reason the state was rejected.
+    ///
+    /// Appended last so the bincode tags of the variants above are unchanged.
+    InvalidWithReason(InvalidReason),
+}
+
+impl ValidateResult {
+    /// An invalid state, rejected for `reason`.
+    pub fn invalid(reason: InvalidReason) -> Self {
+        ValidateResult::InvalidWithReason(reason)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ValidateResult::Valid)
+    }
+
+    /// Whether the state was rejected, with or without a reason.
+    pub fn is_invalid(&self) -> bool {
+        matches!(
+            self,
+            ValidateResult::Invalid | ValidateResult::InvalidWithReason(_)
+        )
+    }
+
+    /// The reason the state was rejected, if it was and one was given.
+    pub fn reason(&self) -> Option<&InvalidReason> {
+        match self {
+            ValidateResult::InvalidWithReason(reason) => Some(reason),
+            _ => None,
+        }
+    }
 }
 
 /// Update notifications for a contract or a related contract.
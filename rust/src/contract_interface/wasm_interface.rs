@@ -1,8 +1,12 @@
 //! Contains all the types to interface between the host environment and
 //! the wasm module execution.
+use super::update::LegacyUpdateModification;
 use super::*;
 use crate::memory::WasmLinearMem;
 
+/// First ABI revision whose update results encode [`RelatedContract::expires_after`].
+const RELATED_EXPIRY_ABI_VERSION: u32 = 2;
+
 #[repr(i32)]
 enum ResultKind {
     ValidateState = 0,
@@ -10,6 +14,7 @@ enum ResultKind {
     UpdateState = 2,
     SummarizeState = 3,
     StateDelta = 4,
+    QueryState = 5,
 }
 
 impl From<i32> for ResultKind {
@@ -20,6 +25,7 @@ impl From<i32> for ResultKind {
             2 => ResultKind::UpdateState,
             3 => ResultKind::SummarizeState,
             4 => ResultKind::StateDelta,
+            5 => ResultKind::QueryState,
             _ => panic!(),
         }
     }
@@ -91,6 +97,36 @@ impl ContractInterfaceResult {
         }
     }
 
+    /// Deserialize an update state result from WASM memory, in the encoding of a module
+    /// built for `abi_version` (see [`AbiDescriptor::abi_version`](crate::memory::wasm_interface::AbiDescriptor::abi_version)).
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`unwrap_update_state`](Self::unwrap_update_state).
+    pub unsafe fn unwrap_update_state_for(
+        self,
+        mem: WasmLinearMem,
+        abi_version: u32,
+    ) -> Result<UpdateModification<'static>, ContractError> {
+        if abi_version >= RELATED_EXPIRY_ABI_VERSION {
+            return self.unwrap_update_state(mem);
+        }
+        let kind = ResultKind::from(self.kind);
+        match kind {
+            ResultKind::UpdateState => {
+                let ptr = crate::memory::buf::compute_ptr(self.ptr as *mut u8, &mem);
+                let serialized = std::slice::from_raw_parts(ptr as *const u8, self.size as _);
+                let value: Result<LegacyUpdateModification<'_>, ContractError> =
+                    bincode::deserialize(serialized)
+                        .map_err(|e| ContractError::Other(format!("{e}")))?;
+                #[cfg(feature = "trace")]
+                self.log_input(serialized, &value, ptr);
+                value.map(|r| UpdateModification::from(r).into_owned())
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Deserialize a summarize state result from WASM memory.
     ///
     /// # Safety
@@ -149,6 +185,33 @@ impl ContractInterfaceResult {
         }
     }
 
+    /// Deserialize a query state result from WASM memory.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `mem` is a valid WASM linear memory containing
+    /// the serialized result at the offset specified by `self.ptr`, with at least
+    /// `self.size` bytes available.
+    pub unsafe fn unwrap_query_state(
+        self,
+        mem: WasmLinearMem,
+    ) -> Result<StateQueryResult<'static>, ContractError> {
+        let kind = ResultKind::from(self.kind);
+        match kind {
+            ResultKind::QueryState => {
+                let ptr = crate::memory::buf::compute_ptr(self.ptr as *mut u8, &mem);
+                let serialized = std::slice::from_raw_parts(ptr as *const u8, self.size as _);
+                let value: Result<StateQueryResult<'static>, ContractError> =
+                    bincode::deserialize(serialized)
+                        .map_err(|e| ContractError::Other(format!("{e}")))?;
+                #[cfg(feature = "trace")]
+                self.log_input(serialized, &value, ptr);
+                value.map(|r| StateQueryResult::from(r.into_bytes()))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[cfg(feature = "contract")]
     pub fn into_raw(self) -> i64 {
         #[cfg(feature = "trace")]
@@ -233,3 +296,53 @@ conversion!(Result<UpdateModification<'static>, ContractError>: ResultKind::Upda
 conversion!(Result<StateSummary<'static>, ContractError>: ResultKind::SummarizeState);
 #[cfg(feature = "contract")]
 conversion!(Result<StateDelta<'static>, ContractError>: ResultKind::StateDelta);
+#[cfg(feature = "contract")]
+conversion!(Result<StateQueryResult<'static>, ContractError>: ResultKind::QueryState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update_result(serialized: &[u8]) -> ContractInterfaceResult {
+        ContractInterfaceResult {
+            ptr: serialized.as_ptr() as i64,
+            kind: ResultKind::UpdateState as i32,
+            size: serialized.len() as u32,
+        }
+    }
+
+    #[test]
+    fn legacy_update_results_decode_without_expiry() {
+        let id = ContractInstanceId::new([7; CONTRACT_KEY_SIZE]);
+        // the layout of modules built before the expiry, structs encode as tuples
+        let legacy = bincode::serialize(&Ok::<_, ContractError>((
+            Some(State::from(vec![1, 2, 3])),
+            vec![(id, RelatedMode::StateThenSubscribe)],
+        )))
+        .unwrap();
+        // A null-based linear memory maps module pointers onto host addresses unchanged.
+        let mem = || unsafe { WasmLinearMem::new(std::ptr::null(), 0) };
+
+        let update = unsafe { update_result(&legacy).unwrap_update_state_for(mem(), 1) }.unwrap();
+        assert_eq!(update.new_state, Some(State::from(vec![1, 2, 3])));
+        assert_eq!(update.related, vec![RelatedContract::subscribe(id, None)]);
+        assert!(unsafe { update_result(&legacy).unwrap_update_state(mem()) }.is_err());
+
+        let current = bincode::serialize(&Ok::<_, ContractError>(UpdateModification {
+            new_state: None,
+            related: vec![RelatedContract::subscribe(
+                id,
+                Some(std::time::Duration::from_secs(60)),
+            )],
+        }))
+        .unwrap();
+        let update = unsafe {
+            update_result(&current).unwrap_update_state_for(mem(), RELATED_EXPIRY_ABI_VERSION)
+        }
+        .unwrap();
+        assert_eq!(
+            update.related[0].expires_after,
+            Some(std::time::Duration::from_secs(60))
+        );
+    }
+}
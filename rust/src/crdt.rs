@@ -0,0 +1,260 @@
+//! Convergent replicated data types (CRDTs) to build contract states from.
+//!
+//! A contract's `update_state` must reach the same state no matter the order updates
+//! arrive in, or how often the same update is seen. The types in this module are
+//! merged with [`Crdt::join`], which is commutative, associative and idempotent, so
+//! states built from them converge on every peer.
+//!
+//! All of them implement [`ContractComponent`], and can be used as fields of a
+//! `#[derive(ContractComponent)]` composite. Summaries are version vectors (or a single
+//! [`Dot`] for [`LwwRegister`]) and deltas only carry what a summary doesn't cover.
+//!
+//! Writes are tagged with the [`ReplicaId`] of the writer. Every replica id must be used
+//! by a single writer at a time, which must write on top of its latest state.
+//!
+//! ```
+//! use freenet_stdlib::crdt::{Crdt, GCounter, ReplicaId};
+//!
+//! let (alice, bob) = (ReplicaId::new(1), ReplicaId::new(2));
+//! let mut local = GCounter::default();
+//! local.increment(alice, 2);
+//! let mut remote = GCounter::default();
+//! remote.increment(bob, 3);
+//!
+//! let delta = local.delta_since(&remote.summary());
+//! remote.join(delta);
+//! assert_eq!(remote.value(), 5);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    contract_composition::{
+        ContractComponent, Mergeable, NoContext, NoParameters, TypedUpdateData,
+    },
+    contract_interface::{ContractError, ValidateResult},
+    typed_contract::{MergeResult, RelatedContractsContainer},
+};
+
+mod counter;
+mod list;
+mod map;
+mod register;
+mod set;
+
+pub use counter::{GCounter, PnCounter, PnCounterSummary};
+pub use list::Rga;
+pub use map::LwwMap;
+pub use register::{LwwRegister, MvRegister};
+pub use set::OrSet;
+
+/// Identifies a writer of a replicated type, e.g. derived from its public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReplicaId(u64);
+
+impl ReplicaId {
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ReplicaId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+/// A counter tagged with the replica which produced it, identifying a single write.
+///
+/// Dots are ordered by counter first, so a dot with a higher counter wins in last-writer-wins
+/// types regardless of the replica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Dot {
+    counter: u64,
+    replica: ReplicaId,
+}
+
+impl Dot {
+    pub const fn new(replica: ReplicaId, counter: u64) -> Self {
+        Self { counter, replica }
+    }
+
+    pub const fn replica(&self) -> ReplicaId {
+        self.replica
+    }
+
+    pub const fn counter(&self) -> u64 {
+        self.counter
+    }
+}
+
+/// The highest counter seen from every replica.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VersionVector(BTreeMap<ReplicaId, u64>);
+
+impl VersionVector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The highest counter seen from `replica`, 0 if none.
+    pub fn get(&self, replica: ReplicaId) -> u64 {
+        self.0.get(&replica).copied().unwrap_or_default()
+    }
+
+    /// Whether `dot` was already seen.
+    pub fn contains(&self, dot: Dot) -> bool {
+        dot.counter <= self.get(dot.replica)
+    }
+
+    /// Records `dot` as seen.
+    pub fn observe(&mut self, dot: Dot) {
+        let counter = self.0.entry(dot.replica).or_default();
+        *counter = (*counter).max(dot.counter);
+    }
+
+    /// Returns the next dot of `replica` and records it as seen.
+    pub fn increment(&mut self, replica: ReplicaId) -> Dot {
+        let dot = Dot::new(replica, self.get(replica) + 1);
+        self.observe(dot);
+        dot
+    }
+
+    /// The highest counter seen from any replica.
+    pub fn max_counter(&self) -> u64 {
+        self.0.values().copied().max().unwrap_or_default()
+    }
+
+    /// Keeps the highest counter of every replica in both vectors.
+    pub fn join(&mut self, other: &VersionVector) {
+        for (replica, counter) in &other.0 {
+            self.observe(Dot::new(*replica, *counter));
+        }
+    }
+
+    /// The entries of `self` ahead of `other`.
+    pub fn since(&self, other: &VersionVector) -> VersionVector {
+        let entries = self
+            .0
+            .iter()
+            .filter(|(replica, counter)| **counter > other.get(**replica))
+            .map(|(replica, counter)| (*replica, *counter));
+        VersionVector(entries.collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ReplicaId, u64)> + '_ {
+        self.0.iter().map(|(replica, counter)| (*replica, *counter))
+    }
+}
+
+impl Mergeable<VersionVector> for VersionVector {
+    fn merge(&mut self, other: VersionVector) {
+        self.join(&other);
+    }
+}
+
+/// A state-based replicated data type.
+///
+/// Deltas are values of the type itself, holding only the writes the receiving side is
+/// missing, and are applied with [`Crdt::join`] like full states.
+pub trait Crdt: Clone + Default {
+    /// What a replica sends to be told about the writes it is missing.
+    type Summary: Clone + Default;
+
+    /// Merges `other` into `self`.
+    ///
+    /// Must be commutative, associative and idempotent.
+    fn join(&mut self, other: Self);
+
+    fn summary(&self) -> Self::Summary;
+
+    /// The part of `self` a replica with `summary` is missing.
+    fn delta_since(&self, summary: &Self::Summary) -> Self;
+
+    /// Checks the internal consistency of a state received from the network.
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Crdt> Mergeable<T> for T {
+    fn merge(&mut self, other: T) {
+        self.join(other);
+    }
+}
+
+impl<T> ContractComponent for T
+where
+    T: Crdt + 'static,
+{
+    type Context = NoContext;
+    type Parameters = NoParameters;
+    type Delta = T;
+    type Summary = T::Summary;
+
+    fn verify<Child, Ctx>(
+        &self,
+        _: &Self::Parameters,
+        _: &Ctx,
+        _: &RelatedContractsContainer,
+    ) -> Result<ValidateResult, ContractError>
+    where
+        Child: ContractComponent,
+        Self::Context: for<'x> From<&'x Ctx>,
+    {
+        if self.is_valid() {
+            Ok(ValidateResult::Valid)
+        } else {
+            Ok(ValidateResult::Invalid)
+        }
+    }
+
+    fn merge(
+        &mut self,
+        _: &Self::Parameters,
+        update: &TypedUpdateData<Self>,
+        _: &RelatedContractsContainer,
+    ) -> MergeResult {
+        match update {
+            TypedUpdateData::RelatedState { state } => self.join(state.clone()),
+            TypedUpdateData::RelatedDelta { delta } => self.join(delta.clone()),
+            TypedUpdateData::RelatedStateAndDelta { state, delta } => {
+                self.join(state.clone());
+                self.join(delta.clone());
+            }
+        }
+        MergeResult::Success
+    }
+
+    fn summarize<ParentSummary>(
+        &self,
+        _: &Self::Parameters,
+        summary: &mut ParentSummary,
+    ) -> Result<(), ContractError>
+    where
+        ParentSummary: Mergeable<<Self as ContractComponent>::Summary>,
+    {
+        summary.merge(self.summary());
+        Ok(())
+    }
+
+    fn delta(
+        &self,
+        _: &Self::Parameters,
+        summary: &Self::Summary,
+    ) -> Result<Self::Delta, ContractError> {
+        Ok(self.delta_since(summary))
+    }
+}
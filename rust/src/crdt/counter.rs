@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Crdt, Dot, ReplicaId, VersionVector};
+use crate::contract_composition::Mergeable;
+
+/// Grow-only counter: every replica increments its own count, the value is their sum.
+///
+/// The counts double as the summary, and a delta only holds the counts which moved.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GCounter {
+    counts: VersionVector,
+}
+
+impl GCounter {
+    pub fn increment(&mut self, replica: ReplicaId, by: u64) {
+        let count = self.counts.get(replica).saturating_add(by);
+        self.counts.observe(Dot::new(replica, count));
+    }
+
+    pub fn value(&self) -> u64 {
+        self.counts
+            .iter()
+            .fold(0, |total, (_, count)| total.saturating_add(count))
+    }
+}
+
+impl Crdt for GCounter {
+    type Summary = VersionVector;
+
+    fn join(&mut self, other: Self) {
+        self.counts.join(&other.counts);
+    }
+
+    fn summary(&self) -> VersionVector {
+        self.counts.clone()
+    }
+
+    fn delta_since(&self, summary: &VersionVector) -> Self {
+        Self {
+            counts: self.counts.since(summary),
+        }
+    }
+}
+
+/// Counter which can be incremented and decremented, as a pair of [`GCounter`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnCounter {
+    increments: GCounter,
+    decrements: GCounter,
+}
+
+impl PnCounter {
+    pub fn increment(&mut self, replica: ReplicaId, by: u64) {
+        self.increments.increment(replica, by);
+    }
+
+    pub fn decrement(&mut self, replica: ReplicaId, by: u64) {
+        self.decrements.increment(replica, by);
+    }
+
+    pub fn value(&self) -> i128 {
+        i128::from(self.increments.value()) - i128::from(self.decrements.value())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnCounterSummary {
+    pub increments: VersionVector,
+    pub decrements: VersionVector,
+}
+
+impl Mergeable<PnCounterSummary> for PnCounterSummary {
+    fn merge(&mut self, other: PnCounterSummary) {
+        self.increments.join(&other.increments);
+        self.decrements.join(&other.decrements);
+    }
+}
+
+impl Crdt for PnCounter {
+    type Summary = PnCounterSummary;
+
+    fn join(&mut self, other: Self) {
+        self.increments.join(other.increments);
+        self.decrements.join(other.decrements);
+    }
+
+    fn summary(&self) -> PnCounterSummary {
+        PnCounterSummary {
+            increments: self.increments.summary(),
+            decrements: self.decrements.summary(),
+        }
+    }
+
+    fn delta_since(&self, summary: &PnCounterSummary) -> Self {
+        Self {
+            increments: self.increments.delta_since(&summary.increments),
+            decrements: self.decrements.delta_since(&summary.decrements),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_converge() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = PnCounter::default();
+        left.increment(a, 5);
+        let mut right = left.clone();
+        left.decrement(a, 2);
+        right.increment(b, 1);
+        right.decrement(b, 7);
+
+        let to_right = left.delta_since(&right.summary());
+        assert_eq!(to_right.increments, GCounter::default());
+        let to_left = right.delta_since(&left.summary());
+        left.join(to_left);
+        right.join(to_right);
+        assert_eq!(left, right);
+        assert_eq!(left.value(), -3);
+
+        // joining again changes nothing
+        right.join(left.clone());
+        assert_eq!(left, right);
+    }
+}
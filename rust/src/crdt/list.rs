@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Crdt, Dot, ReplicaId, VersionVector};
+
+/// Replicated growable array: a list where every element is inserted after another one,
+/// its origin, and concurrent insertions after the same origin are ordered by their dots.
+///
+/// Dots are Lamport timestamps, so an element always has a higher dot than its origin.
+/// Removed elements are kept as tombstones to keep ordering the ones inserted after them.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct Rga<T> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    nodes: BTreeMap<Dot, Node<T>>,
+    /// Removed element of every removal.
+    #[serde_as(as = "Vec<(_, _)>")]
+    removes: BTreeMap<Dot, Dot>,
+    clock: VersionVector,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Node<T> {
+    origin: Option<Dot>,
+    value: Option<T>,
+}
+
+impl<T> Default for Rga<T> {
+    fn default() -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            removes: BTreeMap::new(),
+            clock: VersionVector::new(),
+        }
+    }
+}
+
+impl<T> Rga<T> {
+    /// Inserts `value` at `index`, shifting the elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T, replica: ReplicaId) -> Dot {
+        let visible = self.visible();
+        assert!(
+            index <= visible.len(),
+            "insertion index (is {index}) should be <= len (is {})",
+            visible.len()
+        );
+        let origin = index.checked_sub(1).map(|prev| visible[prev]);
+        let dot = self.next_dot(replica);
+        let value = Some(value);
+        self.nodes.insert(dot, Node { origin, value });
+        dot
+    }
+
+    /// Removes and returns the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize, replica: ReplicaId) -> T {
+        let visible = self.visible();
+        assert!(
+            index < visible.len(),
+            "removal index (is {index}) should be < len (is {})",
+            visible.len()
+        );
+        let target = visible[index];
+        let dot = self.next_dot(replica);
+        self.removes.insert(dot, target);
+        self.nodes
+            .get_mut(&target)
+            .and_then(|node| node.value.take())
+            .expect("visible elements have a value")
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// The elements of the list, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.order()
+            .into_iter()
+            .filter_map(|dot| self.nodes[&dot].value.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes
+            .values()
+            .filter(|node| node.value.is_some())
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_dot(&mut self, replica: ReplicaId) -> Dot {
+        let dot = Dot::new(replica, self.clock.max_counter() + 1);
+        self.clock.observe(dot);
+        dot
+    }
+
+    fn visible(&self) -> Vec<Dot> {
+        self.order()
+            .into_iter()
+            .filter(|dot| self.nodes[dot].value.is_some())
+            .collect()
+    }
+
+    /// Every element, including tombstones, in list order: each element is followed by
+    /// the ones inserted after it, latest first.
+    fn order(&self) -> Vec<Dot> {
+        let mut children: BTreeMap<Option<Dot>, Vec<Dot>> = BTreeMap::new();
+        for (dot, node) in &self.nodes {
+            children.entry(node.origin).or_default().push(*dot);
+        }
+        let mut order = Vec::with_capacity(self.nodes.len());
+        // children are pushed in ascending order so the latest is popped first
+        let mut stack = children.remove(&None).unwrap_or_default();
+        while let Some(dot) = stack.pop() {
+            order.push(dot);
+            if let Some(inserted_after) = children.remove(&Some(dot)) {
+                stack.extend(inserted_after);
+            }
+        }
+        order
+    }
+}
+
+impl<T: Clone> Crdt for Rga<T> {
+    type Summary = VersionVector;
+
+    fn join(&mut self, other: Self) {
+        for (dot, node) in other.nodes {
+            self.nodes.entry(dot).or_insert(node);
+        }
+        self.removes.extend(other.removes);
+        for target in self.removes.values() {
+            if let Some(node) = self.nodes.get_mut(target) {
+                node.value = None;
+            }
+        }
+        self.clock.join(&other.clock);
+    }
+
+    fn summary(&self) -> VersionVector {
+        self.clock.clone()
+    }
+
+    fn delta_since(&self, summary: &VersionVector) -> Self {
+        Self {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|(dot, _)| !summary.contains(**dot))
+                .map(|(dot, node)| (*dot, node.clone()))
+                .collect(),
+            removes: self
+                .removes
+                .iter()
+                .filter(|(dot, _)| !summary.contains(**dot))
+                .map(|(dot, target)| (*dot, *target))
+                .collect(),
+            clock: self.clock.clone(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let nodes_valid = self.nodes.iter().all(|(dot, node)| {
+            self.clock.contains(*dot)
+                && node.origin.map_or(true, |origin| {
+                    origin < *dot && self.nodes.contains_key(&origin)
+                })
+        });
+        let removes_valid = self.removes.iter().all(|(dot, target)| {
+            self.clock.contains(*dot)
+                && self
+                    .nodes
+                    .get(target)
+                    .is_some_and(|node| node.value.is_none())
+        });
+        nodes_valid && removes_valid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_edits_converge() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = Rga::default();
+        left.insert(0, 'a', a);
+        left.insert(1, 'c', a);
+        let mut right = left.clone();
+        left.insert(1, 'b', a);
+        right.insert(1, 'x', b);
+        right.insert(2, 'y', b);
+        assert_eq!(right.remove(0, b), 'a');
+
+        let to_right = left.delta_since(&right.summary());
+        assert_eq!(to_right.nodes.len(), 1);
+        left.join(right.delta_since(&left.summary()));
+        right.join(to_right);
+        assert_eq!(left, right);
+        assert_eq!(left.iter().collect::<String>(), "xybc");
+        assert_eq!(left.get(1), Some(&'y'));
+        assert!(left.is_valid());
+    }
+}
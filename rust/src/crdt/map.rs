@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Crdt, Dot, ReplicaId, VersionVector};
+
+/// Map whose entries are last-writer-wins registers, removals leave a tombstone behind
+/// so they win over older insertions.
+///
+/// Dots count timestamps supplied by the writers, bumped past every timestamp seen from
+/// the same replica and past the entry they overwrite.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize, V: Serialize",
+    deserialize = "K: Deserialize<'de> + Ord, V: Deserialize<'de>"
+))]
+pub struct LwwMap<K, V> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    entries: BTreeMap<K, (Dot, Option<V>)>,
+    clock: VersionVector,
+}
+
+impl<K, V> Default for LwwMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            clock: VersionVector::new(),
+        }
+    }
+}
+
+impl<K: Ord, V> LwwMap<K, V> {
+    pub fn insert(&mut self, key: K, value: V, time: u64, replica: ReplicaId) -> Dot {
+        let dot = self.stamp(&key, time, replica);
+        self.entries.insert(key, (dot, Some(value)));
+        dot
+    }
+
+    /// Removes the entry for `key`, returns whether it was present.
+    pub fn remove(&mut self, key: K, time: u64, replica: ReplicaId) -> bool {
+        if !self.contains_key(&key) {
+            return false;
+        }
+        let dot = self.stamp(&key, time, replica);
+        self.entries.insert(key, (dot, None));
+        true
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).and_then(|(_, value)| value.as_ref())
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// The live entries of the map, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries
+            .iter()
+            .filter_map(|(key, (_, value))| Some((key, value.as_ref()?)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    fn stamp(&mut self, key: &K, time: u64, replica: ReplicaId) -> Dot {
+        let mut time = time.max(self.clock.get(replica) + 1);
+        if let Some((current, _)) = self.entries.get(key) {
+            time = time.max(current.counter() + 1);
+        }
+        let dot = Dot::new(replica, time);
+        self.clock.observe(dot);
+        dot
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Crdt for LwwMap<K, V> {
+    type Summary = VersionVector;
+
+    fn join(&mut self, other: Self) {
+        for (key, (dot, value)) in other.entries {
+            match self.entries.get(&key) {
+                Some((current, _)) if *current >= dot => {}
+                _ => {
+                    self.entries.insert(key, (dot, value));
+                }
+            }
+        }
+        self.clock.join(&other.clock);
+    }
+
+    fn summary(&self) -> VersionVector {
+        self.clock.clone()
+    }
+
+    fn delta_since(&self, summary: &VersionVector) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .filter(|(_, (dot, _))| !summary.contains(*dot))
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect(),
+            clock: self.clock.clone(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.entries
+            .values()
+            .all(|(dot, _)| self.clock.contains(*dot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_write_per_key_wins() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = LwwMap::default();
+        left.insert("k1", 1, 100, a);
+        left.insert("k2", 2, 100, a);
+        let mut right = left.clone();
+        left.insert("k1", 10, 105, a);
+        assert!(right.remove("k2", 101, b));
+        right.insert("k3", 3, 90, b);
+
+        let to_right = left.delta_since(&right.summary());
+        assert_eq!(to_right.entries.len(), 1);
+        left.join(right.delta_since(&left.summary()));
+        right.join(to_right);
+        assert_eq!(left, right);
+        assert_eq!(
+            left.iter().collect::<Vec<_>>(),
+            vec![(&"k1", &10), (&"k3", &3)]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Crdt, Dot, ReplicaId, VersionVector};
+use crate::contract_composition::Mergeable;
+
+/// Last-writer-wins register: holds the value with the highest [`Dot`].
+///
+/// The counter of the dot is a timestamp supplied by the writer, bumped past the current
+/// one so a write always supersedes the value it overwrites.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LwwRegister<T> {
+    entry: Option<(Dot, T)>,
+}
+
+impl<T> Default for LwwRegister<T> {
+    fn default() -> Self {
+        Self { entry: None }
+    }
+}
+
+impl<T> LwwRegister<T> {
+    pub fn set(&mut self, value: T, time: u64, replica: ReplicaId) -> Dot {
+        let time = match self.stamp() {
+            Some(current) => time.max(current.counter() + 1),
+            None => time,
+        };
+        let dot = Dot::new(replica, time);
+        self.entry = Some((dot, value));
+        dot
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.entry.as_ref().map(|(_, value)| value)
+    }
+
+    /// The dot of the current value.
+    pub fn stamp(&self) -> Option<Dot> {
+        self.entry.as_ref().map(|(dot, _)| *dot)
+    }
+}
+
+impl Mergeable<Option<Dot>> for Option<Dot> {
+    fn merge(&mut self, other: Option<Dot>) {
+        *self = (*self).max(other);
+    }
+}
+
+impl<T: Clone> Crdt for LwwRegister<T> {
+    type Summary = Option<Dot>;
+
+    fn join(&mut self, other: Self) {
+        if other.stamp() > self.stamp() {
+            *self = other;
+        }
+    }
+
+    fn summary(&self) -> Option<Dot> {
+        self.stamp()
+    }
+
+    fn delta_since(&self, summary: &Option<Dot>) -> Self {
+        if self.stamp() > *summary {
+            self.clone()
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Multi-value register: a write replaces every value it has seen, concurrent writes
+/// are all kept until overwritten.
+///
+/// Deltas hold the whole register whenever it has writes the summary doesn't cover, as
+/// it only grows with the number of concurrent writers.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct MvRegister<T> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    values: BTreeMap<Dot, T>,
+    clock: VersionVector,
+}
+
+impl<T> Default for MvRegister<T> {
+    fn default() -> Self {
+        Self {
+            values: BTreeMap::new(),
+            clock: VersionVector::new(),
+        }
+    }
+}
+
+impl<T> MvRegister<T> {
+    pub fn set(&mut self, value: T, replica: ReplicaId) -> Dot {
+        let dot = self.clock.increment(replica);
+        self.values.clear();
+        self.values.insert(dot, value);
+        dot
+    }
+
+    /// The concurrently written values, ordered by their dots.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.values.values()
+    }
+}
+
+impl<T: Clone> Crdt for MvRegister<T> {
+    type Summary = VersionVector;
+
+    fn join(&mut self, other: Self) {
+        let Self { values, clock } = other;
+        // values the other side has seen and dropped were overwritten
+        self.values
+            .retain(|dot, _| values.contains_key(dot) || !clock.contains(*dot));
+        for (dot, value) in values {
+            if !self.clock.contains(dot) || self.values.contains_key(&dot) {
+                self.values.insert(dot, value);
+            }
+        }
+        self.clock.join(&clock);
+    }
+
+    fn summary(&self) -> VersionVector {
+        self.clock.clone()
+    }
+
+    fn delta_since(&self, summary: &VersionVector) -> Self {
+        if self.clock.since(summary).is_empty() {
+            Self::default()
+        } else {
+            self.clone()
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.values.keys().all(|dot| self.clock.contains(*dot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lww_register_keeps_latest_write() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = LwwRegister::default();
+        left.set("first", 10, a);
+        let mut right = left.clone();
+        // the clock of b is behind, its write still overwrites the value it saw
+        right.set("second", 3, b);
+        left.set("concurrent", 11, a);
+
+        let delta = right.delta_since(&left.summary());
+        left.join(delta);
+        right.join(left.delta_since(&right.summary()));
+        assert_eq!(left, right);
+        // both writes got the same timestamp, the replica id breaks the tie
+        assert_eq!(left.get(), Some(&"second"));
+        assert_eq!(left.stamp(), Some(Dot::new(b, 11)));
+    }
+
+    #[test]
+    fn mv_register_keeps_concurrent_writes() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = MvRegister::default();
+        left.set(1, a);
+        let mut right = left.clone();
+        left.set(2, a);
+        right.set(3, b);
+
+        let to_right = left.delta_since(&right.summary());
+        left.join(right.delta_since(&left.summary()));
+        right.join(to_right);
+        assert_eq!(left, right);
+        assert_eq!(left.values().copied().collect::<Vec<_>>(), vec![3, 2]);
+
+        right.set(4, b);
+        left.join(right.delta_since(&left.summary()));
+        assert_eq!(left.values().copied().collect::<Vec<_>>(), vec![4]);
+        assert!(left.is_valid());
+    }
+}
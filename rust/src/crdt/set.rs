@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Crdt, Dot, ReplicaId, VersionVector};
+
+/// Observed-remove set: a removal only removes the additions it has seen, so an element
+/// added concurrently with its removal stays in the set.
+///
+/// Every addition is tagged with a dot, removals keep the dots they removed so they can
+/// be sent in deltas.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct OrSet<T> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    adds: BTreeMap<Dot, T>,
+    #[serde_as(as = "Vec<(_, _)>")]
+    removes: BTreeMap<Dot, BTreeSet<Dot>>,
+    clock: VersionVector,
+}
+
+impl<T> Default for OrSet<T> {
+    fn default() -> Self {
+        Self {
+            adds: BTreeMap::new(),
+            removes: BTreeMap::new(),
+            clock: VersionVector::new(),
+        }
+    }
+}
+
+impl<T: Ord> OrSet<T> {
+    pub fn insert(&mut self, value: T, replica: ReplicaId) -> Dot {
+        let dot = self.clock.increment(replica);
+        self.adds.insert(dot, value);
+        dot
+    }
+
+    /// Removes every observed addition of `value`, returns whether it was present.
+    pub fn remove(&mut self, value: &T, replica: ReplicaId) -> bool {
+        let removed: BTreeSet<Dot> = self
+            .adds
+            .iter()
+            .filter(|(_, added)| *added == value)
+            .map(|(dot, _)| *dot)
+            .collect();
+        if removed.is_empty() {
+            return false;
+        }
+        self.adds.retain(|dot, _| !removed.contains(dot));
+        let dot = self.clock.increment(replica);
+        self.removes.insert(dot, removed);
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.adds.values().any(|added| added == value)
+    }
+
+    /// The elements of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.adds.values().collect::<BTreeSet<_>>().into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adds.is_empty()
+    }
+}
+
+impl<T: Ord + Clone> Crdt for OrSet<T> {
+    type Summary = VersionVector;
+
+    fn join(&mut self, other: Self) {
+        let Self {
+            adds,
+            removes,
+            clock,
+        } = other;
+        self.removes.extend(removes);
+        let removed: BTreeSet<Dot> = self.removes.values().flatten().copied().collect();
+        self.adds.extend(adds);
+        self.adds.retain(|dot, _| !removed.contains(dot));
+        self.clock.join(&clock);
+    }
+
+    fn summary(&self) -> VersionVector {
+        self.clock.clone()
+    }
+
+    fn delta_since(&self, summary: &VersionVector) -> Self {
+        Self {
+            adds: self
+                .adds
+                .iter()
+                .filter(|(dot, _)| !summary.contains(**dot))
+                .map(|(dot, value)| (*dot, value.clone()))
+                .collect(),
+            removes: self
+                .removes
+                .iter()
+                .filter(|(dot, _)| !summary.contains(**dot))
+                .map(|(dot, removed)| (*dot, removed.clone()))
+                .collect(),
+            clock: self.clock.clone(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.adds
+            .keys()
+            .chain(self.removes.keys())
+            .all(|dot| self.clock.contains(*dot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_add_wins_over_remove() {
+        let (a, b) = (ReplicaId::new(1), ReplicaId::new(2));
+        let mut left = OrSet::default();
+        left.insert("x", a);
+        left.insert("y", a);
+        let mut right = left.clone();
+        assert!(left.remove(&"x", a));
+        right.insert("x", b);
+        assert!(right.remove(&"y", b));
+
+        let to_right = left.delta_since(&right.summary());
+        assert_eq!(to_right.adds.len(), 0);
+        left.join(right.delta_since(&left.summary()));
+        right.join(to_right);
+        assert_eq!(left, right);
+        assert_eq!(left.iter().collect::<Vec<_>>(), vec![&"x"]);
+        assert!(!left.remove(&"y", a));
+        assert!(left.is_valid());
+    }
+}
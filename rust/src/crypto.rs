@@ -0,0 +1,561 @@
+//! Cryptographic primitives for contracts and delegates.
+//!
+//! Available with the `crypto` feature. Everything in here is pure Rust and compiles to
+//! `wasm32-unknown-unknown` without pulling an OS entropy source; inside contracts the
+//! `generate` constructors and `SymmetricKey::seal` draw randomness from
+//! [`rand::rand_bytes`](crate::rand::rand_bytes). They are only available with the
+//! `contract` feature on wasm targets, since native builds get no randomness from the
+//! host; elsewhere keys are built from caller supplied bytes.
+//!
+//! - **Signatures**: ed25519 through [`SigningKey`], [`VerifyingKey`] and [`Signature`].
+//! - **Key agreement**: x25519 through [`X25519SecretKey`], [`X25519PublicKey`] and
+//!   [`SharedSecret`].
+//! - **Authenticated encryption**: XChaCha20-Poly1305 through [`SymmetricKey`].
+//! - **Keyed hashing**: blake3 through [`keyed_hash`], [`verify_keyed_hash`] and [`derive_key`].
+//!
+//! Public key and signature types serialize as fixed size byte arrays so they can be
+//! embedded in typed contract states and [`Parameters`](crate::prelude::Parameters)
+//! with any of the stock encoders.
+
+use std::fmt::Display;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroize;
+
+use crate::prelude::{ContractError, DelegateError};
+
+/// Length in bytes of ed25519 and x25519 public keys.
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+/// Length in bytes of ed25519 and x25519 secret keys, and of symmetric keys.
+pub const SECRET_KEY_LENGTH: usize = 32;
+/// Length in bytes of an ed25519 signature.
+pub const SIGNATURE_LENGTH: usize = 64;
+/// Length in bytes of an XChaCha20-Poly1305 nonce.
+pub const NONCE_LENGTH: usize = 24;
+/// Length in bytes of a blake3 keyed hash.
+pub const HASH_LENGTH: usize = 32;
+
+/// Type of errors produced by the cryptographic primitives.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CryptoError {
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("sealed message too short: {0} bytes")]
+    Truncated(usize),
+    #[error("failed to seal message")]
+    Seal,
+    #[error("failed to open sealed message")]
+    Open,
+}
+
+impl From<CryptoError> for ContractError {
+    fn from(value: CryptoError) -> Self {
+        ContractError::Other(format!("{value}"))
+    }
+}
+
+impl From<CryptoError> for DelegateError {
+    fn from(value: CryptoError) -> Self {
+        DelegateError::Other(format!("{value}"))
+    }
+}
+
+#[cfg(all(feature = "contract", target_family = "wasm"))]
+fn random_array<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&crate::rand::rand_bytes(N as u32));
+    bytes
+}
+
+fn encode_bs58(bytes: &[u8]) -> String {
+    bs58::encode(bytes)
+        .with_alphabet(bs58::Alphabet::BITCOIN)
+        .into_string()
+}
+
+// ============================================================================
+// ed25519 signatures
+// ============================================================================
+
+/// Secret half of an ed25519 key pair.
+///
+/// Serializes as its 32 byte seed so it can be kept in delegate secrets; never embed it
+/// in contract states or parameters, which are public.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "SecretKeyBytes", into = "SecretKeyBytes")]
+pub struct SigningKey(ed25519_dalek::SigningKey);
+
+impl SigningKey {
+    pub fn from_bytes(bytes: &[u8; SECRET_KEY_LENGTH]) -> Self {
+        Self(ed25519_dalek::SigningKey::from_bytes(bytes))
+    }
+
+    /// Generates a new key from the host provided randomness.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn generate() -> Self {
+        let mut seed = random_array::<SECRET_KEY_LENGTH>();
+        let key = Self::from_bytes(&seed);
+        seed.zeroize();
+        key
+    }
+
+    pub fn to_bytes(&self) -> [u8; SECRET_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(self.0.verifying_key().to_bytes())
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        use ed25519_dalek::Signer;
+        Signature(self.0.sign(message).to_bytes())
+    }
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SigningKey")
+            .field(&self.verifying_key())
+            .finish()
+    }
+}
+
+/// Public half of an ed25519 key pair.
+///
+/// The bytes are only checked to be a valid curve point when verifying, so a key
+/// deserialized from untrusted input is rejected by [`Self::verify`] rather than on decode.
+#[serde_as]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[repr(transparent)]
+pub struct VerifyingKey(#[serde_as(as = "[_; PUBLIC_KEY_LENGTH]")] [u8; PUBLIC_KEY_LENGTH]);
+
+impl VerifyingKey {
+    /// Builds a key from its raw bytes, checking that they encode a valid curve point.
+    pub fn from_bytes(bytes: &[u8; PUBLIC_KEY_LENGTH]) -> Result<Self, CryptoError> {
+        ed25519_dalek::VerifyingKey::from_bytes(bytes)
+            .map_err(|_| CryptoError::InvalidPublicKey)?;
+        Ok(Self(*bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PUBLIC_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// `Base58` string representation of the key.
+    pub fn encode(&self) -> String {
+        encode_bs58(&self.0)
+    }
+
+    /// Verifies `signature` over `message`.
+    ///
+    /// Uses strict verification, which rejects weak keys and malleable signatures so a
+    /// valid signature is unique for a given key and message.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<(), CryptoError> {
+        let key = ed25519_dalek::VerifyingKey::from_bytes(&self.0)
+            .map_err(|_| CryptoError::InvalidPublicKey)?;
+        let signature = ed25519_dalek::Signature::from_bytes(&signature.0);
+        key.verify_strict(message, &signature)
+            .map_err(|_| CryptoError::InvalidSignature)
+    }
+}
+
+impl Display for VerifyingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl std::fmt::Debug for VerifyingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("VerifyingKey").field(&self.encode()).finish()
+    }
+}
+
+/// An ed25519 signature.
+#[serde_as]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(transparent)]
+pub struct Signature(#[serde_as(as = "[_; SIGNATURE_LENGTH]")] [u8; SIGNATURE_LENGTH]);
+
+impl Signature {
+    pub const fn from_bytes(bytes: [u8; SIGNATURE_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; SIGNATURE_LENGTH] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = CryptoError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        value
+            .try_into()
+            .map(Self)
+            .map_err(|_| CryptoError::InvalidSignature)
+    }
+}
+
+impl std::fmt::Debug for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signature")
+            .field(&encode_bs58(&self.0))
+            .finish()
+    }
+}
+
+// ============================================================================
+// x25519 key agreement
+// ============================================================================
+
+/// Secret half of an x25519 key pair.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "SecretKeyBytes", into = "SecretKeyBytes")]
+pub struct X25519SecretKey(x25519_dalek::StaticSecret);
+
+impl X25519SecretKey {
+    pub fn from_bytes(bytes: &[u8; SECRET_KEY_LENGTH]) -> Self {
+        Self(x25519_dalek::StaticSecret::from(*bytes))
+    }
+
+    /// Generates a new key from the host provided randomness.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn generate() -> Self {
+        let mut bytes = random_array::<SECRET_KEY_LENGTH>();
+        let key = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        key
+    }
+
+    pub fn to_bytes(&self) -> [u8; SECRET_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    pub fn public_key(&self) -> X25519PublicKey {
+        X25519PublicKey(x25519_dalek::PublicKey::from(&self.0).to_bytes())
+    }
+
+    /// Computes the secret shared with the owner of `their_public`.
+    pub fn diffie_hellman(&self, their_public: &X25519PublicKey) -> SharedSecret {
+        let shared = self
+            .0
+            .diffie_hellman(&x25519_dalek::PublicKey::from(their_public.0));
+        SharedSecret(shared.to_bytes())
+    }
+}
+
+impl std::fmt::Debug for X25519SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("X25519SecretKey")
+            .field(&self.public_key())
+            .finish()
+    }
+}
+
+/// Public half of an x25519 key pair.
+#[serde_as]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[repr(transparent)]
+pub struct X25519PublicKey(#[serde_as(as = "[_; PUBLIC_KEY_LENGTH]")] [u8; PUBLIC_KEY_LENGTH]);
+
+impl X25519PublicKey {
+    pub const fn from_bytes(bytes: [u8; PUBLIC_KEY_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PUBLIC_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// `Base58` string representation of the key.
+    pub fn encode(&self) -> String {
+        encode_bs58(&self.0)
+    }
+}
+
+impl Display for X25519PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl std::fmt::Debug for X25519PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("X25519PublicKey")
+            .field(&self.encode())
+            .finish()
+    }
+}
+
+/// Raw output of an x25519 key agreement.
+///
+/// Not uniformly random, so it must go through [`Self::derive_key`] before being used
+/// as a symmetric key.
+pub struct SharedSecret([u8; SECRET_KEY_LENGTH]);
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; SECRET_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Derives a symmetric key bound to `context`, see [`derive_key`].
+    pub fn derive_key(&self, context: &str) -> SymmetricKey {
+        SymmetricKey(derive_key(context, &self.0))
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedSecret").finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// XChaCha20-Poly1305 authenticated encryption
+// ============================================================================
+
+/// A 256 bit key for XChaCha20-Poly1305 authenticated encryption.
+///
+/// Sealed messages are laid out as `[nonce: 24 bytes][ciphertext + 16 byte tag]`, so
+/// [`Self::open`] needs nothing but the key and the associated data.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SymmetricKey(#[serde_as(as = "[_; SECRET_KEY_LENGTH]")] [u8; SECRET_KEY_LENGTH]);
+
+impl SymmetricKey {
+    pub const fn from_bytes(bytes: [u8; SECRET_KEY_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a new key from the host provided randomness.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn generate() -> Self {
+        Self(random_array())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; SECRET_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Encrypts `plaintext` under a fresh random nonce, authenticating `aad` along with it.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.seal_with_nonce(&random_array(), plaintext, aad)
+    }
+
+    /// Encrypts `plaintext` under the given nonce, authenticating `aad` along with it.
+    ///
+    /// A nonce must never be reused with the same key. 24 random bytes are safe to use,
+    /// prefer [`Self::seal`] inside contracts.
+    pub fn seal_with_nonce(
+        &self,
+        nonce: &[u8; NONCE_LENGTH],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.0));
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| CryptoError::Seal)?;
+        let mut sealed = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+        sealed.extend_from_slice(nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypts a message produced by [`Self::seal`] or [`Self::seal_with_nonce`].
+    ///
+    /// Fails if the message was tampered with or `aad` differs from the one it was sealed with.
+    pub fn open(&self, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if sealed.len() < NONCE_LENGTH {
+            return Err(CryptoError::Truncated(sealed.len()));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.0));
+        cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| CryptoError::Open)
+    }
+}
+
+impl Drop for SymmetricKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SymmetricKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymmetricKey").finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// blake3 keyed hashing
+// ============================================================================
+
+/// Computes a blake3 keyed hash (a MAC) of `data` under `key`.
+pub fn keyed_hash(key: &[u8; SECRET_KEY_LENGTH], data: &[u8]) -> [u8; HASH_LENGTH] {
+    *blake3::keyed_hash(key, data).as_bytes()
+}
+
+/// Checks in constant time that `expected` is the keyed hash of `data` under `key`.
+pub fn verify_keyed_hash(
+    key: &[u8; SECRET_KEY_LENGTH],
+    data: &[u8],
+    expected: &[u8; HASH_LENGTH],
+) -> bool {
+    // `blake3::Hash` equality is constant time.
+    blake3::keyed_hash(key, data) == blake3::Hash::from(*expected)
+}
+
+/// Derives a 256 bit key from `key_material` using blake3 in key derivation mode.
+///
+/// `context` should be a hardcoded, globally unique string describing the purpose of the
+/// key (e.g. `"my-app 2024-01-01 session key"`), so keys derived for different purposes
+/// from the same material are independent.
+pub fn derive_key(context: &str, key_material: &[u8]) -> [u8; SECRET_KEY_LENGTH] {
+    blake3::derive_key(context, key_material)
+}
+
+/// Serde representation shared by the secret key types.
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+struct SecretKeyBytes(#[serde_as(as = "[_; SECRET_KEY_LENGTH]")] [u8; SECRET_KEY_LENGTH]);
+
+impl Drop for SecretKeyBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<SecretKeyBytes> for SigningKey {
+    fn from(value: SecretKeyBytes) -> Self {
+        Self::from_bytes(&value.0)
+    }
+}
+
+impl From<SigningKey> for SecretKeyBytes {
+    fn from(value: SigningKey) -> Self {
+        Self(value.to_bytes())
+    }
+}
+
+impl From<SecretKeyBytes> for X25519SecretKey {
+    fn from(value: SecretKeyBytes) -> Self {
+        Self::from_bytes(&value.0)
+    }
+}
+
+impl From<X25519SecretKey> for SecretKeyBytes {
+    fn from(value: X25519SecretKey) -> Self {
+        Self(value.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7; SECRET_KEY_LENGTH]);
+        let public = key.verifying_key();
+        let signature = key.sign(b"state");
+        assert!(public.verify(b"state", &signature).is_ok());
+        assert_eq!(
+            public.verify(b"other state", &signature),
+            Err(CryptoError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn keys_roundtrip_through_encoders() {
+        let key = SigningKey::from_bytes(&[7; SECRET_KEY_LENGTH]);
+        let public = key.verifying_key();
+        let signature = key.sign(b"state");
+
+        let encoded = bincode::serialize(&(public, signature)).unwrap();
+        assert_eq!(encoded.len(), PUBLIC_KEY_LENGTH + SIGNATURE_LENGTH);
+        let decoded: (VerifyingKey, Signature) = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, (public, signature));
+
+        let json = serde_json::to_vec(&public).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<VerifyingKey>(&json).unwrap(),
+            public
+        );
+
+        let secret = bincode::serialize(&key).unwrap();
+        let key: SigningKey = bincode::deserialize(&secret).unwrap();
+        assert_eq!(key.verifying_key(), public);
+    }
+
+    #[test]
+    fn invalid_public_key_is_rejected() {
+        // y = 2 is not the encoding of a point on the curve
+        let mut bytes = [0; PUBLIC_KEY_LENGTH];
+        bytes[0] = 2;
+        assert_eq!(
+            VerifyingKey::from_bytes(&bytes),
+            Err(CryptoError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn key_agreement_and_sealing() {
+        let alice = X25519SecretKey::from_bytes(&[1; SECRET_KEY_LENGTH]);
+        let bob = X25519SecretKey::from_bytes(&[2; SECRET_KEY_LENGTH]);
+        let alice_key = alice.diffie_hellman(&bob.public_key()).derive_key("test");
+        let bob_key = bob.diffie_hellman(&alice.public_key()).derive_key("test");
+        assert_eq!(alice_key.as_bytes(), bob_key.as_bytes());
+
+        let sealed = alice_key
+            .seal_with_nonce(&[3; NONCE_LENGTH], b"hello", b"aad")
+            .unwrap();
+        assert_eq!(bob_key.open(&sealed, b"aad").unwrap(), b"hello");
+        assert_eq!(bob_key.open(&sealed, b"other"), Err(CryptoError::Open));
+        assert_eq!(
+            bob_key.open(&sealed[..10], b"aad"),
+            Err(CryptoError::Truncated(10))
+        );
+    }
+
+    #[test]
+    fn keyed_hashes() {
+        let key = [9; SECRET_KEY_LENGTH];
+        let mac = keyed_hash(&key, b"data");
+        assert!(verify_keyed_hash(&key, b"data", &mac));
+        assert!(!verify_keyed_hash(&key, b"tampered", &mac));
+        assert_ne!(derive_key("a", b"material"), derive_key("b", b"material"));
+    }
+}
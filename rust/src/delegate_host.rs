@@ -27,11 +27,13 @@
 //!         ctx.set_secret(b"new_secret", b"value");
 //!
 //!         // V2: Direct contract access (no round-trips!)
-//!         let contract_id = [0u8; 32]; // your contract instance ID
-//!         if let Some(state) = ctx.get_contract_state(&contract_id) {
-//!             // process state...
+//!         let contract_id = ContractInstanceId::new([0u8; 32]); // your contract instance ID
+//!         match ctx.try_get_contract_state(contract_id) {
+//!             Ok(state) => { /* process state... */ }
+//!             Err(HostError::ContractNotFound) => { /* not cached locally */ }
+//!             Err(err) => return Err(err.into()),
 //!         }
-//!         ctx.put_contract_state(&contract_id, b"new state");
+//!         ctx.try_put_contract_state(contract_id, b"new state")?;
 //!
 //!         Ok(vec![])
 //!     }
@@ -67,9 +69,46 @@
 //! | -8   | Internal state store error |
 //! | -9   | WASM memory bounds violation |
 //! | -10  | Contract code not registered |
+//! | -11  | Atomic secret transactions not supported by the host |
+//! | -12  | Update rejected by the contract |
+//! | -20  | Delegate creation depth exceeded |
+//! | -21  | Per-call delegate creation limit exceeded |
+//! | -23  | Invalid WASM module |
+//! | -24  | Delegate store registration failed |
 //!
 //! The wrapper methods in [`DelegateCtx`] handle these error codes and present
-//! a more ergonomic API.
+//! a more ergonomic API. The `try_*` contract methods surface them as a
+//! [`HostError`].
+//!
+//! # Tracing
+//!
+//! With the `trace` feature every [`DelegateCtx`] host call is recorded, with
+//! its result, into the trace of the running `process()` call, see the
+//! `delegate_trace` module.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::contract_interface::{ContractError, ContractInstanceId, StateDelta, UpdateData};
+#[cfg(feature = "trace")]
+use crate::delegate_trace::HostCall;
+use crate::prelude::DelegateError;
+use crate::typed_contract::{Encoder, EncodingAdapter};
+
+/// Runs the body of a [`DelegateCtx`] host call, recording or replaying it when a trace
+/// session is active (see [`delegate_trace`](crate::delegate_trace)).
+macro_rules! host_call {
+    ($call:expr, $body:block) => {{
+        #[cfg(feature = "trace")]
+        {
+            crate::delegate_trace::host_call(|| $call, || $body)
+        }
+        #[cfg(not(feature = "trace"))]
+        $body
+    }};
+}
 
 /// Error codes returned by host functions.
 ///
@@ -98,6 +137,10 @@ pub mod error_codes {
     pub const ERR_MEMORY_BOUNDS: i32 = -9;
     /// Contract code not registered in the index.
     pub const ERR_CONTRACT_CODE_NOT_REGISTERED: i32 = -10;
+    /// The host cannot apply a secret transaction atomically.
+    pub const ERR_TRANSACTION_UNSUPPORTED: i32 = -11;
+    /// The contract's update logic rejected the update.
+    pub const ERR_INVALID_UPDATE: i32 = -12;
     /// Delegate creation depth limit exceeded.
     pub const ERR_DEPTH_EXCEEDED: i32 = -20;
     /// Per-call delegate creation limit exceeded.
@@ -108,6 +151,132 @@ pub mod error_codes {
     pub const ERR_STORE_FAILED: i32 = -24;
 }
 
+/// A host function error, one variant per [`error_codes`] value.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub enum HostError {
+    #[error("called outside of a process() context")]
+    NotInProcess,
+    #[error("secret not found")]
+    SecretNotFound,
+    #[error("storage operation failed")]
+    StorageFailed,
+    #[error("invalid parameter")]
+    InvalidParam,
+    #[error("context too large")]
+    ContextTooLarge,
+    #[error("buffer too small")]
+    BufferTooSmall,
+    #[error("contract not found in the local store")]
+    ContractNotFound,
+    #[error("internal state store error")]
+    StoreError,
+    #[error("WASM memory bounds violation")]
+    MemoryBounds,
+    #[error("contract code not registered")]
+    ContractCodeNotRegistered,
+    #[error("the host cannot apply the transaction atomically")]
+    TransactionUnsupported,
+    #[error("the contract rejected the update")]
+    InvalidUpdate,
+    #[error("delegate creation depth limit exceeded")]
+    DepthExceeded,
+    #[error("per-call delegate creation limit exceeded")]
+    CreationsExceeded,
+    #[error("invalid WASM module")]
+    InvalidWasm,
+    #[error("failed to register the delegate")]
+    StoreFailed,
+    #[error("unknown host error code {0}")]
+    Unknown(i32),
+}
+
+impl HostError {
+    /// Maps a host function return value to an error, `None` for non-negative
+    /// (successful) values.
+    pub fn from_code(code: i32) -> Option<Self> {
+        use error_codes::*;
+        let err = match code {
+            c if c >= SUCCESS => return None,
+            ERR_NOT_IN_PROCESS => Self::NotInProcess,
+            ERR_SECRET_NOT_FOUND => Self::SecretNotFound,
+            ERR_STORAGE_FAILED => Self::StorageFailed,
+            ERR_INVALID_PARAM => Self::InvalidParam,
+            ERR_CONTEXT_TOO_LARGE => Self::ContextTooLarge,
+            ERR_BUFFER_TOO_SMALL => Self::BufferTooSmall,
+            ERR_CONTRACT_NOT_FOUND => Self::ContractNotFound,
+            ERR_STORE_ERROR => Self::StoreError,
+            ERR_MEMORY_BOUNDS => Self::MemoryBounds,
+            ERR_CONTRACT_CODE_NOT_REGISTERED => Self::ContractCodeNotRegistered,
+            ERR_TRANSACTION_UNSUPPORTED => Self::TransactionUnsupported,
+            ERR_INVALID_UPDATE => Self::InvalidUpdate,
+            ERR_DEPTH_EXCEEDED => Self::DepthExceeded,
+            ERR_CREATIONS_EXCEEDED => Self::CreationsExceeded,
+            ERR_INVALID_WASM => Self::InvalidWasm,
+            ERR_STORE_FAILED => Self::StoreFailed,
+            other => Self::Unknown(other),
+        };
+        Some(err)
+    }
+
+    /// The [`error_codes`] value of this error.
+    pub fn code(&self) -> i32 {
+        use error_codes::*;
+        match self {
+            Self::NotInProcess => ERR_NOT_IN_PROCESS,
+            Self::SecretNotFound => ERR_SECRET_NOT_FOUND,
+            Self::StorageFailed => ERR_STORAGE_FAILED,
+            Self::InvalidParam => ERR_INVALID_PARAM,
+            Self::ContextTooLarge => ERR_CONTEXT_TOO_LARGE,
+            Self::BufferTooSmall => ERR_BUFFER_TOO_SMALL,
+            Self::ContractNotFound => ERR_CONTRACT_NOT_FOUND,
+            Self::StoreError => ERR_STORE_ERROR,
+            Self::MemoryBounds => ERR_MEMORY_BOUNDS,
+            Self::ContractCodeNotRegistered => ERR_CONTRACT_CODE_NOT_REGISTERED,
+            Self::TransactionUnsupported => ERR_TRANSACTION_UNSUPPORTED,
+            Self::InvalidUpdate => ERR_INVALID_UPDATE,
+            Self::DepthExceeded => ERR_DEPTH_EXCEEDED,
+            Self::CreationsExceeded => ERR_CREATIONS_EXCEEDED,
+            Self::InvalidWasm => ERR_INVALID_WASM,
+            Self::StoreFailed => ERR_STORE_FAILED,
+            Self::Unknown(code) => *code,
+        }
+    }
+
+    /// Checks an `i64` host function return value, passing through
+    /// non-negative results.
+    #[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
+    fn check(result: i64) -> Result<u64, Self> {
+        if result >= 0 {
+            return Ok(result as u64);
+        }
+        let code = i32::try_from(result).unwrap_or(i32::MIN);
+        Err(Self::from_code(code).unwrap_or(Self::Unknown(code)))
+    }
+}
+
+impl From<HostError> for DelegateError {
+    fn from(err: HostError) -> Self {
+        DelegateError::Other(format!("{err}"))
+    }
+}
+
+/// Error of the typed contract state methods of [`DelegateCtx`].
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum ContractAccessError {
+    #[error(transparent)]
+    Host(#[from] HostError),
+    #[error("contract state encoding failed: {0}")]
+    Encoding(ContractError),
+}
+
+impl From<ContractAccessError> for DelegateError {
+    fn from(err: ContractAccessError) -> Self {
+        DelegateError::Other(format!("{err}"))
+    }
+}
+
 // ============================================================================
 // Host function declarations (WASM only)
 // ============================================================================
@@ -151,6 +320,23 @@ extern "C" {
         out_ptr: i64,
         out_len: i32,
     ) -> i32;
+    /// Atomically apply the secret operations encoded (see `encode_secret_ops`) in the
+    /// `ops_len` bytes at `ops_ptr`: either all of them are applied or none. Returns 0 on
+    /// success, or negative error code.
+    fn __frnt__delegate__commit_secrets(ops_ptr: i64, ops_len: i32) -> i32;
+    /// Store a secret which expires `ttl_ms` milliseconds from now. Returns 0 on success,
+    /// or negative error code.
+    fn __frnt__delegate__set_secret_with_ttl(
+        key_ptr: i64,
+        key_len: i32,
+        val_ptr: i64,
+        val_len: i32,
+        ttl_ms: i64,
+    ) -> i32;
+    /// Write the metadata of a secret, encoded as described in `SecretMetadata::to_bytes`,
+    /// to the `SecretMetadata::ENCODED_LEN` bytes at `out_ptr`. Returns 0 on success, or
+    /// negative error code.
+    fn __frnt__delegate__secret_metadata(key_ptr: i64, key_len: i32, out_ptr: i64) -> i32;
 }
 
 #[cfg(target_family = "wasm")]
@@ -181,6 +367,15 @@ extern "C" {
     ) -> i64;
     /// Subscribe to contract updates. Returns 0 on success, or negative error code (i64).
     fn __frnt__delegate__subscribe_contract(id_ptr: i64, id_len: i32) -> i64;
+    /// Apply the bincode-encoded `UpdateData` at `update_ptr` through the contract's
+    /// `update_state` logic and store the result. Returns 0 on success, or negative
+    /// error code (i64).
+    fn __frnt__delegate__apply_contract_update(
+        id_ptr: i64,
+        id_len: i32,
+        update_ptr: i64,
+        update_len: i64,
+    ) -> i64;
 }
 
 #[cfg(target_family = "wasm")]
@@ -218,12 +413,21 @@ extern "C" {
 /// # Secret Methods
 /// - [`get_secret`](Self::get_secret), [`set_secret`](Self::set_secret),
 ///   [`has_secret`](Self::has_secret), [`remove_secret`](Self::remove_secret)
+/// - [`commit_secrets`](Self::commit_secrets) to apply several changes atomically
+/// - [`set_secret_with_ttl`](Self::set_secret_with_ttl),
+///   [`secret_metadata`](Self::secret_metadata)
 ///
 /// # Contract Methods (V2)
 /// - [`get_contract_state`](Self::get_contract_state),
 ///   [`put_contract_state`](Self::put_contract_state),
 ///   [`update_contract_state`](Self::update_contract_state),
 ///   [`subscribe_contract`](Self::subscribe_contract)
+/// - `try_*` variants taking a [`ContractInstanceId`] or
+///   [`ContractKey`](crate::prelude::ContractKey) and returning a [`HostError`]
+/// - [`update_contract`](Self::update_contract) to apply deltas
+/// - [`get_typed_contract_state`](Self::get_typed_contract_state),
+///   [`put_typed_contract_state`](Self::put_typed_contract_state),
+///   [`apply_typed_contract_delta`](Self::apply_typed_contract_delta)
 ///
 /// # Delegate Management Methods (V2)
 /// - [`create_delegate`](Self::create_delegate)
@@ -252,19 +456,21 @@ impl DelegateCtx {
     /// Returns the current context length in bytes.
     #[inline]
     pub fn len(&self) -> usize {
-        #[cfg(target_family = "wasm")]
-        {
-            let len = unsafe { __frnt__delegate__ctx_len() };
-            if len < 0 {
+        host_call!(HostCall::ContextLen, {
+            #[cfg(target_family = "wasm")]
+            {
+                let len = unsafe { __frnt__delegate__ctx_len() };
+                if len < 0 {
+                    0
+                } else {
+                    len as usize
+                }
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
                 0
-            } else {
-                len as usize
             }
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            0
-        }
+        })
     }
 
     /// Returns `true` if the context is empty.
@@ -277,27 +483,49 @@ impl DelegateCtx {
     ///
     /// Returns an empty `Vec` if no context has been written.
     pub fn read(&self) -> Vec<u8> {
-        #[cfg(target_family = "wasm")]
-        {
-            let len = unsafe { __frnt__delegate__ctx_len() };
-            if len <= 0 {
-                return Vec::new();
+        host_call!(HostCall::ContextRead, {
+            #[cfg(target_family = "wasm")]
+            {
+                let len = unsafe { __frnt__delegate__ctx_len() };
+                if len <= 0 {
+                    return Vec::new();
+                }
+                let mut buf = vec![0u8; len as usize];
+                let read = unsafe { __frnt__delegate__ctx_read(buf.as_mut_ptr() as i64, len) };
+                buf.truncate(read.max(0) as usize);
+                buf
             }
-            let mut buf = vec![0u8; len as usize];
-            let read = unsafe { __frnt__delegate__ctx_read(buf.as_mut_ptr() as i64, len) };
-            buf.truncate(read.max(0) as usize);
-            buf
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            Vec::new()
-        }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                Vec::new()
+            }
+        })
     }
 
     /// Read context into a provided buffer.
     ///
     /// Returns the number of bytes actually read.
     pub fn read_into(&self, buf: &mut [u8]) -> usize {
+        #[cfg(feature = "trace")]
+        {
+            // Record the bytes read, the count alone can't be replayed.
+            let max_len = buf.len();
+            let data = crate::delegate_trace::host_call(
+                || HostCall::ContextReadInto { max_len },
+                || {
+                    let read = self.read_into_untraced(buf);
+                    buf[..read].to_vec()
+                },
+            );
+            let read = data.len().min(max_len);
+            buf[..read].copy_from_slice(&data[..read]);
+            read
+        }
+        #[cfg(not(feature = "trace"))]
+        self.read_into_untraced(buf)
+    }
+
+    fn read_into_untraced(&self, buf: &mut [u8]) -> usize {
         #[cfg(target_family = "wasm")]
         {
             let read =
@@ -315,17 +543,25 @@ impl DelegateCtx {
     ///
     /// Returns `true` on success, `false` on error.
     pub fn write(&mut self, data: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result =
-                unsafe { __frnt__delegate__ctx_write(data.as_ptr() as i64, data.len() as i32) };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = data;
-            false
-        }
+        host_call!(
+            HostCall::ContextWrite {
+                data: data.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    let result = unsafe {
+                        __frnt__delegate__ctx_write(data.as_ptr() as i64, data.len() as i32)
+                    };
+                    result == 0
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = data;
+                    false
+                }
+            }
+        )
     }
 
     /// Clear the context.
@@ -342,116 +578,205 @@ impl DelegateCtx {
     ///
     /// Returns `None` if the secret does not exist.
     pub fn get_secret_len(&self, key: &[u8]) -> Option<usize> {
-        #[cfg(target_family = "wasm")]
-        {
-            let result =
-                unsafe { __frnt__delegate__get_secret_len(key.as_ptr() as i64, key.len() as i32) };
-            if result < 0 {
+        host_call!(HostCall::GetSecretLen { key: key.to_vec() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                let result = unsafe {
+                    __frnt__delegate__get_secret_len(key.as_ptr() as i64, key.len() as i32)
+                };
+                if result < 0 {
+                    None
+                } else {
+                    Some(result as usize)
+                }
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = key;
                 None
-            } else {
-                Some(result as usize)
             }
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = key;
-            None
-        }
+        })
     }
 
     /// Get a secret by key.
     ///
-    /// Returns `None` if the secret does not exist.
+    /// Returns `None` if the secret does not exist or expired.
     pub fn get_secret(&self, key: &[u8]) -> Option<Vec<u8>> {
-        #[cfg(target_family = "wasm")]
-        {
-            // First get the length to allocate the right buffer size
-            let len = self.get_secret_len(key)?;
+        host_call!(HostCall::GetSecret { key: key.to_vec() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                // First get the length to allocate the right buffer size
+                let len = self.get_secret_len(key)?;
 
-            if len == 0 {
-                return Some(Vec::new());
-            }
+                if len == 0 {
+                    return Some(Vec::new());
+                }
 
-            let mut out = vec![0u8; len];
-            let result = unsafe {
-                __frnt__delegate__get_secret(
-                    key.as_ptr() as i64,
-                    key.len() as i32,
-                    out.as_mut_ptr() as i64,
-                    out.len() as i32,
-                )
-            };
-            if result < 0 {
+                let mut out = vec![0u8; len];
+                let result = unsafe {
+                    __frnt__delegate__get_secret(
+                        key.as_ptr() as i64,
+                        key.len() as i32,
+                        out.as_mut_ptr() as i64,
+                        out.len() as i32,
+                    )
+                };
+                if result < 0 {
+                    None
+                } else {
+                    out.truncate(result as usize);
+                    Some(out)
+                }
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = key;
                 None
-            } else {
-                out.truncate(result as usize);
-                Some(out)
             }
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = key;
-            None
-        }
+        })
     }
 
     /// Store a secret.
     ///
     /// Returns `true` on success, `false` on error.
     pub fn set_secret(&mut self, key: &[u8], value: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result = unsafe {
-                __frnt__delegate__set_secret(
-                    key.as_ptr() as i64,
-                    key.len() as i32,
-                    value.as_ptr() as i64,
-                    value.len() as i32,
-                )
-            };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = (key, value);
-            false
-        }
+        host_call!(
+            HostCall::SetSecret {
+                key: key.to_vec(),
+                value: value.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    let result = unsafe {
+                        __frnt__delegate__set_secret(
+                            key.as_ptr() as i64,
+                            key.len() as i32,
+                            value.as_ptr() as i64,
+                            value.len() as i32,
+                        )
+                    };
+                    result == 0
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (key, value);
+                    false
+                }
+            }
+        )
     }
 
-    /// Check if a secret exists.
+    /// Check if a secret exists. Expired secrets don't.
     pub fn has_secret(&self, key: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result =
-                unsafe { __frnt__delegate__has_secret(key.as_ptr() as i64, key.len() as i32) };
-            result == 1
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = key;
-            false
-        }
+        host_call!(HostCall::HasSecret { key: key.to_vec() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                let result =
+                    unsafe { __frnt__delegate__has_secret(key.as_ptr() as i64, key.len() as i32) };
+                result == 1
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = key;
+                false
+            }
+        })
     }
 
     /// Remove a secret.
     ///
     /// Returns `true` if the secret was removed, `false` if it didn't exist.
     pub fn remove_secret(&mut self, key: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result =
-                unsafe { __frnt__delegate__remove_secret(key.as_ptr() as i64, key.len() as i32) };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = key;
-            false
-        }
+        host_call!(HostCall::RemoveSecret { key: key.to_vec() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                let result = unsafe {
+                    __frnt__delegate__remove_secret(key.as_ptr() as i64, key.len() as i32)
+                };
+                result == 0
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = key;
+                false
+            }
+        })
+    }
+
+    /// Store a secret which expires after `ttl`.
+    ///
+    /// Once expired the secret is hidden from [`get_secret`](Self::get_secret),
+    /// [`has_secret`](Self::has_secret) and [`list_secrets`](Self::list_secrets), and the
+    /// host reclaims its storage. Setting the secret again, with or without a TTL,
+    /// replaces the expiry. Returns `true` on success, `false` on error, including a zero
+    /// `ttl`.
+    pub fn set_secret_with_ttl(&mut self, key: &[u8], value: &[u8], ttl: Duration) -> bool {
+        host_call!(
+            HostCall::SetSecretWithTtl {
+                key: key.to_vec(),
+                value: value.to_vec(),
+                ttl
+            },
+            {
+                let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+                if ttl_ms == 0 {
+                    return false;
+                }
+                #[cfg(target_family = "wasm")]
+                {
+                    let result = unsafe {
+                        __frnt__delegate__set_secret_with_ttl(
+                            key.as_ptr() as i64,
+                            key.len() as i32,
+                            value.as_ptr() as i64,
+                            value.len() as i32,
+                            ttl_ms,
+                        )
+                    };
+                    result == 0
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (key, value);
+                    false
+                }
+            }
+        )
+    }
+
+    /// Get the metadata of a secret.
+    ///
+    /// Returns `None` if the secret does not exist or expired.
+    pub fn secret_metadata(&self, key: &[u8]) -> Option<SecretMetadata> {
+        host_call!(HostCall::SecretMetadata { key: key.to_vec() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                let mut out = [0u8; SecretMetadata::ENCODED_LEN];
+                let result = unsafe {
+                    __frnt__delegate__secret_metadata(
+                        key.as_ptr() as i64,
+                        key.len() as i32,
+                        out.as_mut_ptr() as i64,
+                    )
+                };
+                if result < 0 {
+                    None
+                } else {
+                    SecretMetadata::from_bytes(&out)
+                }
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = key;
+                None
+            }
+        })
     }
 
     /// Enumerate the keys of every secret this delegate has stored whose raw
     /// key begins with `prefix` (pass an empty slice to list all keys).
+    /// Expired secrets are not listed.
     ///
     /// Returns the matching raw keys (the same byte strings originally passed
     /// to [`set_secret`](Self::set_secret)). Order is unspecified. The host
@@ -464,37 +789,83 @@ impl DelegateCtx {
     /// after a delegate-WASM rebuild the delegate can now rediscover what it
     /// has stored instead of probing a hardcoded key set.
     pub fn list_secrets(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
-        #[cfg(target_family = "wasm")]
-        {
-            let len = unsafe {
-                __frnt__delegate__list_secrets_len(prefix.as_ptr() as i64, prefix.len() as i32)
-            };
-            if len <= 0 {
-                // Negative => error; zero => no matching keys. Either way the
-                // caller gets an empty list (errors are non-fatal: enumeration
-                // is advisory).
-                return Vec::new();
+        host_call!(
+            HostCall::ListSecrets {
+                prefix: prefix.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    let len = unsafe {
+                        __frnt__delegate__list_secrets_len(
+                            prefix.as_ptr() as i64,
+                            prefix.len() as i32,
+                        )
+                    };
+                    if len <= 0 {
+                        // Negative => error; zero => no matching keys. Either way the
+                        // caller gets an empty list (errors are non-fatal: enumeration
+                        // is advisory).
+                        return Vec::new();
+                    }
+                    let mut out = vec![0u8; len as usize];
+                    let written = unsafe {
+                        __frnt__delegate__list_secrets(
+                            prefix.as_ptr() as i64,
+                            prefix.len() as i32,
+                            out.as_mut_ptr() as i64,
+                            out.len() as i32,
+                        )
+                    };
+                    if written < 0 {
+                        return Vec::new();
+                    }
+                    out.truncate(written as usize);
+                    decode_secret_key_list(&out)
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = prefix;
+                    Vec::new()
+                }
             }
-            let mut out = vec![0u8; len as usize];
-            let written = unsafe {
-                __frnt__delegate__list_secrets(
-                    prefix.as_ptr() as i64,
-                    prefix.len() as i32,
-                    out.as_mut_ptr() as i64,
-                    out.len() as i32,
-                )
-            };
-            if written < 0 {
-                return Vec::new();
-            }
-            out.truncate(written as usize);
-            decode_secret_key_list(&out)
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = prefix;
-            Vec::new()
+        )
+    }
+
+    /// Atomically apply every operation staged in `transaction`.
+    ///
+    /// Either all the operations are applied or, on error, none of them. Removing a
+    /// secret that does not exist is not an error.
+    ///
+    /// # Errors
+    /// - [`HostError::NotInProcess`]: called outside process() context
+    /// - [`HostError::StorageFailed`]: storage operation failed, nothing was applied
+    /// - [`HostError::InvalidParam`]: malformed transaction
+    /// - [`HostError::MemoryBounds`]: WASM memory bounds violation
+    /// - [`HostError::TransactionUnsupported`]: the host cannot apply the operations
+    ///   atomically, nothing was applied
+    pub fn commit_secrets(&mut self, transaction: &SecretTransaction) -> Result<(), HostError> {
+        if transaction.is_empty() {
+            return Ok(());
         }
+        let ops = encode_secret_ops(&transaction.ops);
+        let Ok(ops_len) = i32::try_from(ops.len()) else {
+            return Err(HostError::InvalidParam);
+        };
+        host_call!(HostCall::CommitSecrets { ops: ops.clone() }, {
+            #[cfg(target_family = "wasm")]
+            {
+                HostError::check(i64::from(unsafe {
+                    __frnt__delegate__commit_secrets(ops.as_ptr() as i64, ops_len)
+                }))?;
+                Ok(())
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = (&ops, ops_len);
+                Err(HostError::NotInProcess)
+            }
+        })
     }
 
     // ========================================================================
@@ -504,47 +875,67 @@ impl DelegateCtx {
     /// Get contract state by instance ID.
     ///
     /// Returns `Some(state_bytes)` if the contract exists locally,
-    /// `None` if not found or on error.
+    /// `None` if not found or on error. See
+    /// [`try_get_contract_state`](Self::try_get_contract_state) to tell these apart.
+    pub fn get_contract_state(&self, instance_id: &[u8; 32]) -> Option<Vec<u8>> {
+        self.try_get_contract_state(ContractInstanceId::new(*instance_id))
+            .ok()
+    }
+
+    /// Get contract state by [`ContractInstanceId`] or [`ContractKey`](crate::prelude::ContractKey).
     ///
     /// Uses a two-step protocol: first queries the state length, then reads
     /// the state bytes into an allocated buffer.
-    pub fn get_contract_state(&self, instance_id: &[u8; 32]) -> Option<Vec<u8>> {
-        #[cfg(target_family = "wasm")]
-        {
-            // Step 1: Get the state length
-            let len = unsafe {
-                __frnt__delegate__get_contract_state_len(instance_id.as_ptr() as i64, 32)
-            };
-            if len < 0 {
-                return None;
-            }
-            let len = len as usize;
-            if len == 0 {
-                return Some(Vec::new());
-            }
+    ///
+    /// # Errors
+    /// - [`HostError::ContractNotFound`]: no state for the contract in the local store
+    /// - [`HostError::NotInProcess`]: called outside process() context
+    pub fn try_get_contract_state(
+        &self,
+        id: impl AsRef<ContractInstanceId>,
+    ) -> Result<Vec<u8>, HostError> {
+        let id: &[u8; 32] = id.as_ref();
+        host_call!(HostCall::GetContractState { id: *id }, {
+            #[cfg(target_family = "wasm")]
+            {
+                // Step 1: Get the state length
+                let len = HostError::check(unsafe {
+                    __frnt__delegate__get_contract_state_len(id.as_ptr() as i64, 32)
+                })? as usize;
+                if len == 0 {
+                    return Ok(Vec::new());
+                }
 
-            // Step 2: Read the state bytes
-            let mut buf = vec![0u8; len];
-            let read = unsafe {
-                __frnt__delegate__get_contract_state(
-                    instance_id.as_ptr() as i64,
-                    32,
-                    buf.as_mut_ptr() as i64,
-                    buf.len() as i64,
-                )
-            };
-            if read < 0 {
-                None
-            } else {
+                // Step 2: Read the state bytes
+                let mut buf = vec![0u8; len];
+                let read = HostError::check(unsafe {
+                    __frnt__delegate__get_contract_state(
+                        id.as_ptr() as i64,
+                        32,
+                        buf.as_mut_ptr() as i64,
+                        buf.len() as i64,
+                    )
+                })?;
                 buf.truncate(read as usize);
-                Some(buf)
+                Ok(buf)
             }
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = instance_id;
-            None
-        }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = id;
+                Err(HostError::NotInProcess)
+            }
+        })
+    }
+
+    /// Get contract state and decode it with the contract's
+    /// [`SelfEncoder`](EncodingAdapter::SelfEncoder), as declared by its
+    /// [`TypedContract`](crate::typed_contract::TypedContract) implementation.
+    pub fn get_typed_contract_state<T: EncodingAdapter>(
+        &self,
+        id: impl AsRef<ContractInstanceId>,
+    ) -> Result<T, ContractAccessError> {
+        let bytes = self.try_get_contract_state(id)?;
+        T::SelfEncoder::deserialize(&bytes).map_err(|err| ContractAccessError::Encoding(err.into()))
     }
 
     /// Store (PUT) contract state by instance ID.
@@ -552,49 +943,163 @@ impl DelegateCtx {
     /// The contract's code must already be registered in the runtime's contract
     /// store. Returns `true` on success, `false` on error.
     pub fn put_contract_state(&mut self, instance_id: &[u8; 32], state: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result = unsafe {
-                __frnt__delegate__put_contract_state(
-                    instance_id.as_ptr() as i64,
-                    32,
-                    state.as_ptr() as i64,
-                    state.len() as i64,
-                )
-            };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = (instance_id, state);
-            false
-        }
+        self.try_put_contract_state(ContractInstanceId::new(*instance_id), state)
+            .is_ok()
+    }
+
+    /// Store (PUT) contract state by [`ContractInstanceId`] or
+    /// [`ContractKey`](crate::prelude::ContractKey).
+    ///
+    /// # Errors
+    /// - [`HostError::ContractCodeNotRegistered`]: the contract's code is not in the contract store
+    /// - [`HostError::StoreError`]: the state store failed
+    pub fn try_put_contract_state(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+        state: &[u8],
+    ) -> Result<(), HostError> {
+        let id: &[u8; 32] = id.as_ref();
+        host_call!(
+            HostCall::PutContractState {
+                id: *id,
+                state: state.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    HostError::check(unsafe {
+                        __frnt__delegate__put_contract_state(
+                            id.as_ptr() as i64,
+                            32,
+                            state.as_ptr() as i64,
+                            state.len() as i64,
+                        )
+                    })?;
+                    Ok(())
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (id, state);
+                    Err(HostError::NotInProcess)
+                }
+            }
+        )
+    }
+
+    /// Encode a state with the contract's [`SelfEncoder`](EncodingAdapter::SelfEncoder)
+    /// and store it, see [`try_put_contract_state`](Self::try_put_contract_state).
+    pub fn put_typed_contract_state<T: EncodingAdapter>(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+        state: &T,
+    ) -> Result<(), ContractAccessError> {
+        let bytes = T::SelfEncoder::serialize(state)
+            .map_err(|err| ContractAccessError::Encoding(err.into()))?;
+        Ok(self.try_put_contract_state(id, &bytes)?)
     }
 
     /// Update contract state by instance ID.
     ///
     /// Like `put_contract_state`, but only succeeds if the contract already has
     /// stored state. This performs a full state replacement (not a delta-based
-    /// update through the contract's `update_state` logic). Returns `true` on
+    /// update through the contract's `update_state` logic, see
+    /// [`update_contract`](Self::update_contract) for that). Returns `true` on
     /// success, `false` if no prior state exists or on other errors.
     pub fn update_contract_state(&mut self, instance_id: &[u8; 32], state: &[u8]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result = unsafe {
-                __frnt__delegate__update_contract_state(
-                    instance_id.as_ptr() as i64,
-                    32,
-                    state.as_ptr() as i64,
-                    state.len() as i64,
-                )
-            };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = (instance_id, state);
-            false
-        }
+        self.try_update_contract_state(ContractInstanceId::new(*instance_id), state)
+            .is_ok()
+    }
+
+    /// Replace existing contract state by [`ContractInstanceId`] or
+    /// [`ContractKey`](crate::prelude::ContractKey).
+    ///
+    /// # Errors
+    /// - [`HostError::ContractNotFound`]: the contract has no stored state yet
+    /// - [`HostError::StoreError`]: the state store failed
+    pub fn try_update_contract_state(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+        state: &[u8],
+    ) -> Result<(), HostError> {
+        let id: &[u8; 32] = id.as_ref();
+        host_call!(
+            HostCall::UpdateContractState {
+                id: *id,
+                state: state.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    HostError::check(unsafe {
+                        __frnt__delegate__update_contract_state(
+                            id.as_ptr() as i64,
+                            32,
+                            state.as_ptr() as i64,
+                            state.len() as i64,
+                        )
+                    })?;
+                    Ok(())
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (id, state);
+                    Err(HostError::NotInProcess)
+                }
+            }
+        )
+    }
+
+    /// Apply an update to the contract's stored state through the contract's
+    /// own `update_state` logic, so deltas can be sent instead of full states.
+    ///
+    /// # Errors
+    /// - [`HostError::ContractNotFound`]: the contract has no stored state yet
+    /// - [`HostError::InvalidUpdate`]: the contract rejected the update
+    /// - [`HostError::InvalidParam`]: the update could not be encoded
+    pub fn update_contract(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+        update: &UpdateData<'_>,
+    ) -> Result<(), HostError> {
+        let id: &[u8; 32] = id.as_ref();
+        let update = bincode::serialize(update).map_err(|_| HostError::InvalidParam)?;
+        host_call!(
+            HostCall::UpdateContract {
+                id: *id,
+                update: update.clone()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    HostError::check(unsafe {
+                        __frnt__delegate__apply_contract_update(
+                            id.as_ptr() as i64,
+                            32,
+                            update.as_ptr() as i64,
+                            update.len() as i64,
+                        )
+                    })?;
+                    Ok(())
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (id, &update);
+                    Err(HostError::NotInProcess)
+                }
+            }
+        )
+    }
+
+    /// Encode a delta with the contract's [`DeltaEncoder`](EncodingAdapter::DeltaEncoder)
+    /// and apply it, see [`update_contract`](Self::update_contract).
+    pub fn apply_typed_contract_delta<T: EncodingAdapter>(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+        delta: &T::Delta,
+    ) -> Result<(), ContractAccessError> {
+        let bytes = T::DeltaEncoder::serialize(delta)
+            .map_err(|err| ContractAccessError::Encoding(err.into()))?;
+        Ok(self.update_contract(id, &UpdateData::Delta(StateDelta::from(bytes)))?)
     }
 
     /// Subscribe to contract updates by instance ID.
@@ -605,17 +1110,34 @@ impl DelegateCtx {
     ///
     /// Returns `true` on success, `false` if the contract is unknown or on error.
     pub fn subscribe_contract(&mut self, instance_id: &[u8; 32]) -> bool {
-        #[cfg(target_family = "wasm")]
-        {
-            let result =
-                unsafe { __frnt__delegate__subscribe_contract(instance_id.as_ptr() as i64, 32) };
-            result == 0
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = instance_id;
-            false
-        }
+        self.try_subscribe_contract(ContractInstanceId::new(*instance_id))
+            .is_ok()
+    }
+
+    /// Subscribe to contract updates by [`ContractInstanceId`] or
+    /// [`ContractKey`](crate::prelude::ContractKey).
+    ///
+    /// # Errors
+    /// - [`HostError::ContractNotFound`]: the contract is unknown
+    pub fn try_subscribe_contract(
+        &mut self,
+        id: impl AsRef<ContractInstanceId>,
+    ) -> Result<(), HostError> {
+        let id: &[u8; 32] = id.as_ref();
+        host_call!(HostCall::SubscribeContract { id: *id }, {
+            #[cfg(target_family = "wasm")]
+            {
+                HostError::check(unsafe {
+                    __frnt__delegate__subscribe_contract(id.as_ptr() as i64, 32)
+                })?;
+                Ok(())
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let _ = id;
+                Err(HostError::NotInProcess)
+            }
+        })
     }
 
     /// Create a new child delegate from WASM bytecode and parameters.
@@ -646,33 +1168,41 @@ impl DelegateCtx {
         cipher: &[u8; 32],
         nonce: &[u8; 24],
     ) -> Result<([u8; 32], [u8; 32]), i32> {
-        #[cfg(target_family = "wasm")]
-        {
-            let mut key_buf = [0u8; 32];
-            let mut hash_buf = [0u8; 32];
-            let result = unsafe {
-                __frnt__delegate__create_delegate(
-                    wasm_code.as_ptr() as i64,
-                    wasm_code.len() as i64,
-                    params.as_ptr() as i64,
-                    params.len() as i64,
-                    cipher.as_ptr() as i64,
-                    nonce.as_ptr() as i64,
-                    key_buf.as_mut_ptr() as i64,
-                    hash_buf.as_mut_ptr() as i64,
-                )
-            };
-            if result == 0 {
-                Ok((key_buf, hash_buf))
-            } else {
-                Err(result)
+        host_call!(
+            HostCall::CreateDelegate {
+                code_hash: *blake3::hash(wasm_code).as_bytes(),
+                params: params.to_vec()
+            },
+            {
+                #[cfg(target_family = "wasm")]
+                {
+                    let mut key_buf = [0u8; 32];
+                    let mut hash_buf = [0u8; 32];
+                    let result = unsafe {
+                        __frnt__delegate__create_delegate(
+                            wasm_code.as_ptr() as i64,
+                            wasm_code.len() as i64,
+                            params.as_ptr() as i64,
+                            params.len() as i64,
+                            cipher.as_ptr() as i64,
+                            nonce.as_ptr() as i64,
+                            key_buf.as_mut_ptr() as i64,
+                            hash_buf.as_mut_ptr() as i64,
+                        )
+                    };
+                    if result == 0 {
+                        Ok((key_buf, hash_buf))
+                    } else {
+                        Err(result)
+                    }
+                }
+                #[cfg(not(target_family = "wasm"))]
+                {
+                    let _ = (wasm_code, params, cipher, nonce);
+                    Err(error_codes::ERR_NOT_IN_PROCESS)
+                }
             }
-        }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            let _ = (wasm_code, params, cipher, nonce);
-            Err(error_codes::ERR_NOT_IN_PROCESS)
-        }
+        )
     }
 }
 
@@ -726,6 +1256,185 @@ pub fn decode_secret_key_list(buf: &[u8]) -> Vec<Vec<u8>> {
     keys
 }
 
+// ============================================================================
+// Secret metadata
+// ============================================================================
+
+/// Information about a stored secret, see [`DelegateCtx::secret_metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretMetadata {
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// When the secret expires, if it was set with a TTL.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Size of the value in bytes.
+    pub size: u64,
+}
+
+impl SecretMetadata {
+    /// Length of the encoding written by the host.
+    pub const ENCODED_LEN: usize = 32;
+
+    /// Encodes the metadata as written by the host into the delegate memory: creation,
+    /// last update and expiry as milliseconds since the Unix epoch (`i64`, with `0` for no
+    /// expiry) followed by the size (`u64`), all little-endian.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut buf = [0u8; Self::ENCODED_LEN];
+        buf[0..8].copy_from_slice(&self.created_at.timestamp_millis().to_le_bytes());
+        buf[8..16].copy_from_slice(&self.updated_at.timestamp_millis().to_le_bytes());
+        let expires_at = self.expires_at.map_or(0, |t| t.timestamp_millis());
+        buf[16..24].copy_from_slice(&expires_at.to_le_bytes());
+        buf[24..32].copy_from_slice(&self.size.to_le_bytes());
+        buf
+    }
+
+    /// Inverse of [`Self::to_bytes`], `None` if a timestamp is out of range.
+    pub fn from_bytes(buf: &[u8; Self::ENCODED_LEN]) -> Option<Self> {
+        let field = |i: usize| <[u8; 8]>::try_from(&buf[i * 8..(i + 1) * 8]).unwrap();
+        let timestamp = |i: usize| DateTime::from_timestamp_millis(i64::from_le_bytes(field(i)));
+        let expires_at = match i64::from_le_bytes(field(2)) {
+            0 => None,
+            _ => Some(timestamp(2)?),
+        };
+        Some(Self {
+            created_at: timestamp(0)?,
+            updated_at: timestamp(1)?,
+            expires_at,
+            size: u64::from_le_bytes(field(3)),
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expiry| now >= expiry)
+    }
+}
+
+// ============================================================================
+// Secret transactions
+// ============================================================================
+
+/// A change to a secret, staged in a [`SecretTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretOp {
+    Set { key: Vec<u8>, value: Vec<u8> },
+    Remove { key: Vec<u8> },
+}
+
+impl SecretOp {
+    pub fn key(&self) -> &[u8] {
+        match self {
+            SecretOp::Set { key, .. } | SecretOp::Remove { key } => key,
+        }
+    }
+}
+
+/// Secret changes to be applied all at once by [`DelegateCtx::commit_secrets`].
+///
+/// Operations are applied in the order they were staged, so a later operation on the
+/// same key wins. Nothing is written until the transaction is committed.
+///
+/// ```ignore
+/// let mut tx = SecretTransaction::new();
+/// tx.set(b"signing_key", &new_key).remove(b"old_signing_key");
+/// ctx.commit_secrets(&tx)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretTransaction {
+    ops: Vec<SecretOp>,
+}
+
+impl SecretTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> &mut Self {
+        self.ops.push(SecretOp::Set {
+            key: key.to_vec(),
+            value: value.to_vec(),
+        });
+        self
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> &mut Self {
+        self.ops.push(SecretOp::Remove { key: key.to_vec() });
+        self
+    }
+
+    pub fn ops(&self) -> &[SecretOp] {
+        &self.ops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+}
+
+const SECRET_OP_SET: u8 = 0;
+const SECRET_OP_REMOVE: u8 = 1;
+
+/// Serialize secret operations into the wire format read by the host implementation of
+/// `__frnt__delegate__commit_secrets`: for each operation a tag byte (`0` set, `1`
+/// remove), then the key as a 4-byte little-endian length followed by the key bytes and,
+/// for sets, the value encoded the same way.
+pub fn encode_secret_ops(ops: &[SecretOp]) -> Vec<u8> {
+    fn put(buf: &mut Vec<u8>, bytes: &[u8]) {
+        buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buf.extend_from_slice(bytes);
+    }
+    let mut buf = Vec::new();
+    for op in ops {
+        match op {
+            SecretOp::Set { key, value } => {
+                buf.push(SECRET_OP_SET);
+                put(&mut buf, key);
+                put(&mut buf, value);
+            }
+            SecretOp::Remove { key } => {
+                buf.push(SECRET_OP_REMOVE);
+                put(&mut buf, key);
+            }
+        }
+    }
+    buf
+}
+
+/// Inverse of [`encode_secret_ops`].
+///
+/// Unlike the key list, a transaction must be applied whole, so any malformed or
+/// truncated record rejects the entire buffer (`None`); hosts should answer with
+/// [`error_codes::ERR_INVALID_PARAM`].
+pub fn decode_secret_ops(buf: &[u8]) -> Option<Vec<SecretOp>> {
+    fn take<'a>(buf: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+        let len = u32::from_le_bytes(buf.get(*pos..*pos + 4)?.try_into().ok()?) as usize;
+        *pos += 4;
+        let bytes = buf.get(*pos..pos.checked_add(len)?)?;
+        *pos += len;
+        Some(bytes)
+    }
+    let mut ops = Vec::new();
+    let mut pos = 0usize;
+    while pos < buf.len() {
+        let tag = buf[pos];
+        pos += 1;
+        let key = take(buf, &mut pos)?.to_vec();
+        let op = match tag {
+            SECRET_OP_SET => SecretOp::Set {
+                key,
+                value: take(buf, &mut pos)?.to_vec(),
+            },
+            SECRET_OP_REMOVE => SecretOp::Remove { key },
+            _ => return None,
+        };
+        ops.push(op);
+    }
+    Some(ops)
+}
+
 #[cfg(test)]
 mod secret_key_list_codec_tests {
     use super::{decode_secret_key_list, encode_secret_key_list};
@@ -768,3 +1477,148 @@ mod secret_key_list_codec_tests {
         assert_eq!(decode_secret_key_list(&encoded), vec![b"abc".to_vec()]);
     }
 }
+
+#[cfg(test)]
+mod secret_ops_codec_tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_transaction() {
+        let mut tx = SecretTransaction::new();
+        tx.set(b"new_key", b"secret")
+            .remove(b"old_key")
+            .set(b"empty", b"");
+        let encoded = encode_secret_ops(tx.ops());
+        assert_eq!(decode_secret_ops(&encoded).as_deref(), Some(tx.ops()));
+    }
+
+    #[test]
+    fn set_wire_format_is_stable() {
+        let encoded = encode_secret_ops(&[SecretOp::Set {
+            key: b"k".to_vec(),
+            value: b"vv".to_vec(),
+        }]);
+        assert_eq!(encoded, vec![0, 1, 0, 0, 0, b'k', 2, 0, 0, 0, b'v', b'v']);
+    }
+
+    #[test]
+    fn malformed_transaction_is_rejected_whole() {
+        let mut tx = SecretTransaction::new();
+        tx.remove(b"a").set(b"b", b"value");
+        let encoded = encode_secret_ops(tx.ops());
+        assert!(decode_secret_ops(&encoded[..encoded.len() - 1]).is_none());
+
+        let mut bad_tag = encoded.clone();
+        bad_tag[0] = 7;
+        assert!(decode_secret_ops(&bad_tag).is_none());
+        assert_eq!(decode_secret_ops(&[]), Some(vec![]));
+    }
+
+    #[test]
+    fn native_commit_is_unavailable() {
+        let mut ctx = DelegateCtx::default();
+        let mut tx = SecretTransaction::new();
+        assert_eq!(ctx.commit_secrets(&tx), Ok(()));
+        tx.set(b"k", b"v");
+        assert_eq!(ctx.commit_secrets(&tx), Err(HostError::NotInProcess));
+    }
+}
+
+#[cfg(test)]
+mod secret_metadata_tests {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trip() {
+        let created_at = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        let updated_at = DateTime::from_timestamp_millis(1_700_000_500_000).unwrap();
+        let mut metadata = SecretMetadata {
+            created_at,
+            updated_at,
+            expires_at: Some(updated_at + chrono::Duration::minutes(5)),
+            size: 42,
+        };
+        assert_eq!(
+            SecretMetadata::from_bytes(&metadata.to_bytes()),
+            Some(metadata)
+        );
+        assert!(!metadata.is_expired(updated_at));
+        assert!(metadata.is_expired(updated_at + chrono::Duration::minutes(5)));
+
+        metadata.expires_at = None;
+        let encoded = metadata.to_bytes();
+        assert_eq!(&encoded[16..24], &[0; 8]);
+        assert_eq!(SecretMetadata::from_bytes(&encoded), Some(metadata));
+        assert!(!metadata.is_expired(DateTime::<Utc>::MAX_UTC));
+    }
+
+    #[test]
+    fn zero_ttl_is_rejected() {
+        let mut ctx = DelegateCtx::default();
+        assert!(!ctx.set_secret_with_ttl(b"k", b"v", Duration::ZERO));
+        assert_eq!(ctx.secret_metadata(b"k"), None);
+    }
+}
+
+#[cfg(test)]
+mod host_error_tests {
+    use super::*;
+    use crate::prelude::{ContractCode, ContractKey, Parameters};
+
+    #[test]
+    fn every_error_code_round_trips() {
+        use error_codes::*;
+        for code in [
+            ERR_NOT_IN_PROCESS,
+            ERR_SECRET_NOT_FOUND,
+            ERR_STORAGE_FAILED,
+            ERR_INVALID_PARAM,
+            ERR_CONTEXT_TOO_LARGE,
+            ERR_BUFFER_TOO_SMALL,
+            ERR_CONTRACT_NOT_FOUND,
+            ERR_STORE_ERROR,
+            ERR_MEMORY_BOUNDS,
+            ERR_CONTRACT_CODE_NOT_REGISTERED,
+            ERR_TRANSACTION_UNSUPPORTED,
+            ERR_INVALID_UPDATE,
+            ERR_DEPTH_EXCEEDED,
+            ERR_CREATIONS_EXCEEDED,
+            ERR_INVALID_WASM,
+            ERR_STORE_FAILED,
+        ] {
+            let err = HostError::from_code(code).unwrap();
+            assert!(!matches!(err, HostError::Unknown(_)), "{code} is unmapped");
+            assert_eq!(err.code(), code);
+        }
+        assert_eq!(HostError::from_code(SUCCESS), None);
+        assert_eq!(HostError::from_code(12), None);
+        assert_eq!(HostError::from_code(-99), Some(HostError::Unknown(-99)));
+        assert_eq!(HostError::check(7), Ok(7));
+        assert_eq!(HostError::check(-7), Err(HostError::ContractNotFound));
+        assert_eq!(
+            HostError::check(i64::MIN),
+            Err(HostError::Unknown(i32::MIN))
+        );
+    }
+
+    #[test]
+    fn native_contract_access_is_unavailable() {
+        let mut ctx = DelegateCtx::default();
+        let code = ContractCode::from(vec![1, 2, 3]);
+        let key = ContractKey::from_params_and_code(Parameters::from(vec![]), &code);
+        assert_eq!(
+            ctx.try_get_contract_state(key),
+            Err(HostError::NotInProcess)
+        );
+        assert_eq!(
+            ctx.try_subscribe_contract(*key.id()),
+            Err(HostError::NotInProcess)
+        );
+        let update = UpdateData::Delta(StateDelta::from(vec![1]));
+        assert_eq!(
+            ctx.update_contract(key, &update),
+            Err(HostError::NotInProcess)
+        );
+        assert!(!ctx.put_contract_state(&[0; 32], b"state"));
+    }
+}
@@ -5,9 +5,11 @@ use std::{
     io::Read,
     ops::Deref,
     path::Path,
+    time::Duration,
 };
 
 use blake3::{traits::digest::Digest, Hasher as Blake3};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::serde_as;
 
@@ -21,6 +23,7 @@ use crate::common_generated::common::SecretsId as FbsSecretsId;
 use crate::client_api::{fixed_size_field, unknown_union_discriminant, TryFromFbs, WsApiError};
 use crate::contract_interface::{RelatedContracts, UpdateData, CONTRACT_KEY_SIZE};
 use crate::prelude::{ContractInstanceId, WrappedState};
+use crate::secret_store::SecretStore;
 use crate::versioning::ContractContainer;
 use crate::{code_hash::CodeHash, prelude::Parameters};
 
@@ -488,19 +491,168 @@ pub struct DelegateContext(#[serde_as(as = "serde_with::Bytes")] Vec<u8>);
 impl DelegateContext {
     pub const MAX_SIZE: usize = 4096 * 10 * 10;
 
+    /// Prefix of the secrets holding contexts spilled by [`Self::spill`].
+    pub const SPILL_SECRET_PREFIX: &'static [u8] = b"__freenet_context_spill/";
+    /// How long spilled contexts are kept if they are never released.
+    pub const SPILL_TTL: Duration = Duration::from_secs(60 * 60);
+
+    const INLINE_TAG: u8 = 0;
+    const SPILLED_TAG: u8 = 1;
+    /// Content hash followed by the little endian spill sequence number.
+    const SPILL_REFERENCE_LEN: usize = 32 + 8;
+    /// Secret holding the sequence number of the next spill.
+    const SPILL_COUNTER_KEY: &'static [u8] = b"__freenet_context_spill_counter";
+
+    /// # Panics
+    ///
+    /// If `bytes` is not smaller than [`Self::MAX_SIZE`], see [`Self::try_new`].
     pub fn new(bytes: Vec<u8>) -> Self {
-        assert!(bytes.len() < Self::MAX_SIZE);
-        Self(bytes)
+        Self::try_new(bytes).unwrap()
+    }
+
+    pub fn try_new(bytes: Vec<u8>) -> Result<Self, ContextError> {
+        Self::check_size(bytes.len())?;
+        Ok(Self(bytes))
     }
 
+    /// # Panics
+    ///
+    /// If the context would not be smaller than [`Self::MAX_SIZE`], see [`Self::try_append`].
     pub fn append(&mut self, bytes: &mut Vec<u8>) {
-        assert!(self.0.len() + bytes.len() < Self::MAX_SIZE);
-        self.0.append(bytes)
+        self.try_append(bytes).unwrap()
+    }
+
+    /// Appends `bytes`, leaving both untouched if the context would grow too large.
+    pub fn try_append(&mut self, bytes: &mut Vec<u8>) -> Result<(), ContextError> {
+        Self::check_size(self.0.len() + bytes.len())?;
+        self.0.append(bytes);
+        Ok(())
     }
 
+    /// # Panics
+    ///
+    /// If `bytes` is not smaller than [`Self::MAX_SIZE`], see [`Self::try_replace`].
     pub fn replace(&mut self, bytes: Vec<u8>) {
-        assert!(bytes.len() < Self::MAX_SIZE);
-        let _ = std::mem::replace(&mut self.0, bytes);
+        self.try_replace(bytes).unwrap()
+    }
+
+    pub fn try_replace(&mut self, bytes: Vec<u8>) -> Result<(), ContextError> {
+        Self::check_size(bytes.len())?;
+        self.0 = bytes;
+        Ok(())
+    }
+
+    fn check_size(size: usize) -> Result<(), ContextError> {
+        if size < Self::MAX_SIZE {
+            Ok(())
+        } else {
+            Err(ContextError::TooLarge {
+                size,
+                max: Self::MAX_SIZE,
+            })
+        }
+    }
+
+    /// Wraps `bytes` into a context of any size, to be read back with [`Self::restore`].
+    ///
+    /// Contexts which fit are kept inline. Larger ones are written to a secret under
+    /// [`Self::SPILL_SECRET_PREFIX`], expiring after [`Self::SPILL_TTL`], and the context
+    /// only carries a reference to it: the hash of the content and a sequence number
+    /// drawn for this spill, so spilling the same bytes twice, for instance from two
+    /// concurrent conversations, gives two independent secrets.
+    ///
+    /// Typed delegates get this for free: `typed_delegate::inner_process` spills, restores
+    /// and releases their context.
+    pub fn spill(store: &mut impl SecretStore, bytes: Vec<u8>) -> Result<Self, ContextError> {
+        if bytes.len() < Self::MAX_SIZE - 1 {
+            let mut inline = Vec::with_capacity(bytes.len() + 1);
+            inline.push(Self::INLINE_TAG);
+            inline.extend_from_slice(&bytes);
+            return Ok(Self(inline));
+        }
+        let nonce = Self::next_spill_nonce(store)?;
+        let mut reference = vec![Self::SPILLED_TAG];
+        reference.extend_from_slice(blake3::hash(&bytes).as_bytes());
+        reference.extend_from_slice(&nonce.to_le_bytes());
+        if !store.set_secret_with_ttl(&Self::spill_key(&reference[1..]), &bytes, Self::SPILL_TTL) {
+            return Err(ContextError::SpillFailed);
+        }
+        Ok(Self(reference))
+    }
+
+    /// Reads back the bytes wrapped by [`Self::spill`], an empty context gives no bytes.
+    ///
+    /// A spilled context stays in the secrets, so it can be restored again if processing
+    /// the message fails. Remove it with [`Self::release`] once processed, otherwise it
+    /// expires after [`Self::SPILL_TTL`].
+    pub fn restore(&self, store: &impl SecretStore) -> Result<Vec<u8>, ContextError> {
+        match self.0.split_first() {
+            None => Ok(vec![]),
+            Some((&Self::INLINE_TAG, bytes)) => Ok(bytes.to_vec()),
+            Some((&Self::SPILLED_TAG, reference))
+                if reference.len() == Self::SPILL_REFERENCE_LEN =>
+            {
+                let bytes = store
+                    .get_secret(&Self::spill_key(reference))
+                    .ok_or(ContextError::SpillLost)?;
+                if blake3::hash(&bytes).as_bytes() != &reference[..32] {
+                    return Err(ContextError::SpillLost);
+                }
+                Ok(bytes)
+            }
+            Some(_) => Err(ContextError::Malformed),
+        }
+    }
+
+    /// Removes the secret holding a spilled context, returning whether it existed.
+    ///
+    /// Inline contexts have nothing to remove.
+    pub fn release(&self, store: &mut impl SecretStore) -> bool {
+        match self.0.split_first() {
+            Some((&Self::SPILLED_TAG, reference))
+                if reference.len() == Self::SPILL_REFERENCE_LEN =>
+            {
+                store.remove_secret(&Self::spill_key(reference))
+            }
+            _ => false,
+        }
+    }
+
+    fn spill_key(reference: &[u8]) -> Vec<u8> {
+        [Self::SPILL_SECRET_PREFIX, reference].concat()
+    }
+
+    fn next_spill_nonce(store: &mut impl SecretStore) -> Result<u64, ContextError> {
+        let nonce = store
+            .get_secret(Self::SPILL_COUNTER_KEY)
+            .and_then(|bytes| <[u8; 8]>::try_from(bytes.as_slice()).ok())
+            .map_or(0, u64::from_le_bytes);
+        if !store.set_secret(
+            Self::SPILL_COUNTER_KEY,
+            &nonce.wrapping_add(1).to_le_bytes(),
+        ) {
+            return Err(ContextError::SpillFailed);
+        }
+        Ok(nonce)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContextError {
+    #[error("delegate context of {size} bytes exceeds the maximum of {max} bytes")]
+    TooLarge { size: usize, max: usize },
+    #[error("failed to spill the delegate context to the secret storage")]
+    SpillFailed,
+    #[error("the spilled delegate context is missing or corrupted")]
+    SpillLost,
+    #[error("the delegate context was not created by `DelegateContext::spill`")]
+    Malformed,
+}
+
+impl From<ContextError> for DelegateError {
+    fn from(err: ContextError) -> Self {
+        DelegateError::Other(format!("{err}"))
     }
 }
 
@@ -532,6 +684,7 @@ pub enum InboundDelegateMsg<'a> {
     SubscribeContractResponse(SubscribeContractResponse),
     ContractNotification(ContractNotification),
     DelegateMessage(DelegateMessage),
+    TimerFired(TimerFired),
 }
 
 impl InboundDelegateMsg<'_> {
@@ -555,6 +708,7 @@ impl InboundDelegateMsg<'_> {
                 InboundDelegateMsg::ContractNotification(r)
             }
             InboundDelegateMsg::DelegateMessage(r) => InboundDelegateMsg::DelegateMessage(r),
+            InboundDelegateMsg::TimerFired(r) => InboundDelegateMsg::TimerFired(r),
         }
     }
 
@@ -580,6 +734,7 @@ impl InboundDelegateMsg<'_> {
                 Some(context)
             }
             InboundDelegateMsg::DelegateMessage(DelegateMessage { context, .. }) => Some(context),
+            InboundDelegateMsg::TimerFired(TimerFired { context, .. }) => Some(context),
             _ => None,
         }
     }
@@ -606,6 +761,7 @@ impl InboundDelegateMsg<'_> {
                 Some(context)
             }
             InboundDelegateMsg::DelegateMessage(DelegateMessage { context, .. }) => Some(context),
+            InboundDelegateMsg::TimerFired(TimerFired { context, .. }) => Some(context),
             _ => None,
         }
     }
@@ -617,6 +773,18 @@ impl From<ApplicationMessage> for InboundDelegateMsg<'_> {
     }
 }
 
+impl From<TimerFired> for InboundDelegateMsg<'_> {
+    fn from(value: TimerFired) -> Self {
+        Self::TimerFired(value)
+    }
+}
+
+/// Oversized contexts are a client error, not a reason to panic the decoder.
+fn wire_context(field: &str, bytes: &[u8]) -> Result<DelegateContext, WsApiError> {
+    DelegateContext::try_new(bytes.to_vec())
+        .map_err(|err| WsApiError::deserialization(format!("{field}: {err}")))
+}
+
 impl<'a> TryFromFbs<&FbsInboundDelegateMsg<'a>> for InboundDelegateMsg<'a> {
     fn try_decode_fbs(msg: &FbsInboundDelegateMsg<'a>) -> Result<Self, WsApiError> {
         match msg.inbound_type() {
@@ -624,7 +792,7 @@ impl<'a> TryFromFbs<&FbsInboundDelegateMsg<'a>> for InboundDelegateMsg<'a> {
                 let app_msg = msg.inbound_as_common_application_message().unwrap();
                 let app_msg = ApplicationMessage {
                     payload: app_msg.payload().bytes().to_vec(),
-                    context: DelegateContext::new(app_msg.context().bytes().to_vec()),
+                    context: wire_context("ApplicationMessage.context", app_msg.context().bytes())?,
                     processed: app_msg.processed(),
                 };
                 Ok(InboundDelegateMsg::ApplicationMessage(app_msg))
@@ -634,12 +802,32 @@ impl<'a> TryFromFbs<&FbsInboundDelegateMsg<'a>> for InboundDelegateMsg<'a> {
                 let user_response = UserInputResponse {
                     request_id: user_response.request_id(),
                     response: ClientResponse::new(user_response.response().data().bytes().to_vec()),
-                    context: DelegateContext::new(
-                        user_response.delegate_context().bytes().to_vec(),
-                    ),
+                    context: wire_context(
+                        "UserInputResponse.delegate_context",
+                        user_response.delegate_context().bytes(),
+                    )?,
                 };
                 Ok(InboundDelegateMsg::UserResponse(user_response))
             }
+            InboundDelegateMsgType::TimerFired => {
+                let fired = msg.inbound_as_timer_fired().unwrap();
+                let fired_at =
+                    DateTime::from_timestamp_millis(fired.fired_at_ms()).ok_or_else(|| {
+                        WsApiError::deserialization(format!(
+                            "TimerFired.fired_at_ms out of range: {}",
+                            fired.fired_at_ms()
+                        ))
+                    })?;
+                Ok(InboundDelegateMsg::TimerFired(TimerFired {
+                    timer_id: TimerId::new(fired.timer_id()),
+                    fired_at,
+                    payload: fired.payload().bytes().to_vec(),
+                    context: wire_context(
+                        "TimerFired.delegate_context",
+                        fired.delegate_context().bytes(),
+                    )?,
+                }))
+            }
             // Reachable, not `unreachable!()`: the generated verifier for this
             // union ends in `_ => Ok(())`, so any discriminant a client sets —
             // including `NONE` — arrives here. See `unknown_union_discriminant`.
@@ -712,6 +900,10 @@ pub enum OutboundDelegateMsg {
     UpdateContractRequest(UpdateContractRequest),
     SubscribeContractRequest(SubscribeContractRequest),
     SendDelegateMessage(DelegateMessage),
+    /// Wake up the delegate later with an [`InboundDelegateMsg::TimerFired`].
+    ScheduleTimer(ScheduleTimer),
+    /// Cancel a pending timer; unknown or already fired timers are ignored.
+    CancelTimer(TimerId),
 }
 
 impl From<ApplicationMessage> for OutboundDelegateMsg {
@@ -720,6 +912,12 @@ impl From<ApplicationMessage> for OutboundDelegateMsg {
     }
 }
 
+impl From<UserInputRequest<'static>> for OutboundDelegateMsg {
+    fn from(request: UserInputRequest<'static>) -> Self {
+        OutboundDelegateMsg::RequestUserInput(request)
+    }
+}
+
 impl From<GetContractRequest> for OutboundDelegateMsg {
     fn from(req: GetContractRequest) -> Self {
         Self::GetContractRequest(req)
@@ -750,6 +948,12 @@ impl From<DelegateMessage> for OutboundDelegateMsg {
     }
 }
 
+impl From<ScheduleTimer> for OutboundDelegateMsg {
+    fn from(timer: ScheduleTimer) -> Self {
+        Self::ScheduleTimer(timer)
+    }
+}
+
 impl OutboundDelegateMsg {
     fn deser_user_input_req<'de, D>(deser: D) -> Result<UserInputRequest<'static>, D::Error>
     where
@@ -769,6 +973,8 @@ impl OutboundDelegateMsg {
             OutboundDelegateMsg::SendDelegateMessage(msg) => msg.processed,
             OutboundDelegateMsg::RequestUserInput(_) => true,
             OutboundDelegateMsg::ContextUpdated(_) => true,
+            OutboundDelegateMsg::ScheduleTimer(_) => true,
+            OutboundDelegateMsg::CancelTimer(_) => true,
         }
     }
 
@@ -793,6 +999,7 @@ impl OutboundDelegateMsg {
             OutboundDelegateMsg::SendDelegateMessage(DelegateMessage { context, .. }) => {
                 Some(context)
             }
+            OutboundDelegateMsg::ScheduleTimer(ScheduleTimer { context, .. }) => Some(context),
             _ => None,
         }
     }
@@ -818,6 +1025,7 @@ impl OutboundDelegateMsg {
             OutboundDelegateMsg::SendDelegateMessage(DelegateMessage { context, .. }) => {
                 Some(context)
             }
+            OutboundDelegateMsg::ScheduleTimer(ScheduleTimer { context, .. }) => Some(context),
             _ => None,
         }
     }
@@ -1015,6 +1223,89 @@ pub struct ContractNotification {
     pub context: DelegateContext,
 }
 
+/// Identifies a timer scheduled by a delegate.
+///
+/// Ids are chosen by the delegate and only need to be unique among its own timers.
+/// Scheduling a timer with the id of a pending one replaces it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerId(u64);
+
+impl TimerId {
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for TimerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// When a scheduled timer fires. Hosts work with millisecond precision.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerDeadline {
+    /// After the given delay, counted from when the host receives the request.
+    After(Duration),
+    /// At the given instant, or right away if it already passed.
+    At(#[serde(with = "chrono::serde::ts_milliseconds")] DateTime<Utc>),
+}
+
+/// Request to wake up the delegate later.
+///
+/// When the deadline is reached the host delivers an [`InboundDelegateMsg::TimerFired`]
+/// carrying the same `payload` and `context`. Pending timers can be cancelled with
+/// `OutboundDelegateMsg::CancelTimer`. Hosts may cap how many timers a delegate keeps
+/// pending and may fire them late, e.g. if the node was offline at the deadline.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScheduleTimer {
+    pub timer_id: TimerId,
+    pub deadline: TimerDeadline,
+    /// Arbitrary data handed back when the timer fires.
+    pub payload: Vec<u8>,
+    pub context: DelegateContext,
+}
+
+impl ScheduleTimer {
+    /// A timer firing after `delay`.
+    pub fn after(timer_id: TimerId, delay: Duration, payload: Vec<u8>) -> Self {
+        Self {
+            timer_id,
+            deadline: TimerDeadline::After(delay),
+            payload,
+            context: DelegateContext::default(),
+        }
+    }
+
+    /// A timer firing at `instant`.
+    pub fn at(timer_id: TimerId, instant: DateTime<Utc>, payload: Vec<u8>) -> Self {
+        Self {
+            timer_id,
+            deadline: TimerDeadline::At(instant),
+            payload,
+            context: DelegateContext::default(),
+        }
+    }
+}
+
+/// Delivered to a delegate when a timer it scheduled fires.
+///
+/// Only the host delivers it; client requests carrying a timer firing are rejected.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimerFired {
+    pub timer_id: TimerId,
+    /// When the host fired the timer, which may be later than the deadline.
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub fired_at: DateTime<Utc>,
+    /// The payload given when scheduling the timer.
+    pub payload: Vec<u8>,
+    pub context: DelegateContext,
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NotificationMessage<'a>(
@@ -1249,3 +1540,130 @@ mod message_origin_tests {
         assert!(matches!(decoded, InboundDelegateMsg::ApplicationMessage(_)));
     }
 }
+
+#[cfg(test)]
+mod timer_tests {
+    use super::*;
+
+    /// Timer variants are appended after every pre-existing variant, so
+    /// deployed delegates keep decoding the messages they already know.
+    #[test]
+    fn timer_variants_are_appended() {
+        let fired = InboundDelegateMsg::TimerFired(TimerFired {
+            timer_id: TimerId::new(3),
+            fired_at: DateTime::from_timestamp_millis(1_500).unwrap(),
+            payload: vec![1, 2],
+            context: DelegateContext::new(vec![9]),
+        });
+        let encoded = bincode::serialize(&fired).unwrap();
+        assert_eq!(encoded[..4], [8, 0, 0, 0]);
+        let InboundDelegateMsg::TimerFired(decoded) = bincode::deserialize(&encoded).unwrap()
+        else {
+            panic!("timer fired must round-trip");
+        };
+        assert_eq!(decoded.timer_id, TimerId::new(3));
+        assert_eq!(decoded.fired_at.timestamp_millis(), 1_500);
+        assert_eq!(decoded.context.as_ref(), [9]);
+
+        let schedule: OutboundDelegateMsg =
+            ScheduleTimer::after(TimerId::new(3), Duration::from_secs(30), vec![]).into();
+        assert_eq!(bincode::serialize(&schedule).unwrap()[..4], [8, 0, 0, 0]);
+        let cancel = OutboundDelegateMsg::CancelTimer(TimerId::new(3));
+        assert_eq!(bincode::serialize(&cancel).unwrap()[..4], [9, 0, 0, 0]);
+        assert!(schedule.processed() && cancel.processed());
+    }
+
+    #[test]
+    fn absolute_deadline_roundtrips_with_millisecond_precision() {
+        let instant = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        let timer = ScheduleTimer::at(TimerId::new(1), instant, vec![7]);
+        let decoded: ScheduleTimer =
+            bincode::deserialize(&bincode::serialize(&timer).unwrap()).unwrap();
+        assert_eq!(decoded.deadline, TimerDeadline::At(instant));
+        assert_eq!(decoded.payload, [7]);
+    }
+}
+
+#[cfg(test)]
+mod context_tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn oversized_context_is_an_error() {
+        let max = DelegateContext::MAX_SIZE;
+        assert_eq!(
+            DelegateContext::try_new(vec![0; max]),
+            Err(ContextError::TooLarge { size: max, max })
+        );
+        let mut context = DelegateContext::try_new(vec![0; max - 10]).unwrap();
+        let mut extra = vec![1; 10];
+        assert!(context.try_append(&mut extra).is_err());
+        assert_eq!((context.as_ref().len(), extra.len()), (max - 10, 10));
+        assert!(context.try_replace(vec![0; max + 1]).is_err());
+        assert!(context.try_replace(vec![2; 3]).is_ok());
+        assert_eq!(context.as_ref(), [2; 3]);
+    }
+
+    #[test]
+    fn spill_roundtrips_small_and_large_contexts() {
+        let mut store = BTreeMap::new();
+        assert!(DelegateContext::default()
+            .restore(&store)
+            .unwrap()
+            .is_empty());
+
+        let small = DelegateContext::spill(&mut store, vec![1, 2, 3]).unwrap();
+        assert!(store.is_empty());
+        assert_eq!(small.restore(&store).unwrap(), [1, 2, 3]);
+        assert!(!small.release(&mut store));
+
+        let large_bytes = vec![7; DelegateContext::MAX_SIZE * 2];
+        let large = DelegateContext::spill(&mut store, large_bytes.clone()).unwrap();
+        assert_eq!(large.as_ref().len(), 41);
+        assert_eq!(large.restore(&store).unwrap(), large_bytes);
+        assert_eq!(
+            large.restore(&store).unwrap(),
+            large_bytes,
+            "restoring leaves the spilled context in place"
+        );
+        assert!(large.release(&mut store));
+        assert_eq!(large.restore(&store), Err(ContextError::SpillLost));
+        assert_eq!(
+            store
+                .keys()
+                .filter(|k| k.starts_with(DelegateContext::SPILL_SECRET_PREFIX))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn identical_spills_are_independent() {
+        let mut store = BTreeMap::new();
+        let bytes = vec![7; DelegateContext::MAX_SIZE];
+        let first = DelegateContext::spill(&mut store, bytes.clone()).unwrap();
+        let second = DelegateContext::spill(&mut store, bytes.clone()).unwrap();
+        assert_ne!(first, second);
+
+        assert!(first.release(&mut store));
+        assert_eq!(first.restore(&store), Err(ContextError::SpillLost));
+        assert_eq!(second.restore(&store).unwrap(), bytes);
+    }
+
+    #[test]
+    fn tampered_spill_is_rejected() {
+        let mut store = BTreeMap::new();
+        let large = DelegateContext::spill(&mut store, vec![7; DelegateContext::MAX_SIZE]).unwrap();
+        store
+            .iter_mut()
+            .filter(|(key, _)| key.starts_with(DelegateContext::SPILL_SECRET_PREFIX))
+            .for_each(|(_, value)| value[0] = 0);
+        assert_eq!(large.restore(&store), Err(ContextError::SpillLost));
+        assert_eq!(
+            DelegateContext::new(vec![9]).restore(&store),
+            Err(ContextError::Malformed)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Declarative authorization of delegate operations based on the [`MessageOrigin`].
+//!
+//! An [`OriginPolicy`] lists which web applications and delegates may invoke each of the
+//! operations a delegate exposes. Operations are identified by name, chosen by the
+//! delegate. The policy can be built in code or kept in the delegate secrets, so it can
+//! be changed without rebuilding the delegate (see [`OriginPolicy::load`] and
+//! [`OriginPolicy::store`]).
+//!
+//! A denied call produces an [`AccessDenied`], which converts into the standard error
+//! reply for the caller: a processed [`ApplicationMessage`] whose payload is
+//! [`ACCESS_DENIED_MAGIC`] followed by the bincode encoded [`AccessDenied`].
+//!
+//! ```ignore
+//! let policy = OriginPolicy::new()
+//!     .allow("sign", AllowedOrigin::WebApp(my_app))
+//!     .allow(ANY_OPERATION, AllowedOrigin::Delegate(backup_delegate));
+//!
+//! if let Err(denied) = policy.check("sign", origin.as_ref()) {
+//!     return Ok(vec![denied.into()]);
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    delegate_host::DelegateCtx,
+    delegate_interface::{
+        ApplicationMessage, DelegateError, DelegateKey, MessageOrigin, OutboundDelegateMsg,
+    },
+    prelude::ContractInstanceId,
+    typed_delegate::TypedOutbound,
+};
+
+/// Operation name matching every operation.
+pub const ANY_OPERATION: &str = "*";
+
+/// Secret key under which [`OriginPolicy::load_default`] and
+/// [`OriginPolicy::store_default`] keep the policy.
+pub const POLICY_SECRET_KEY: &[u8] = b"__freenet_origin_policy";
+
+/// Prefix of the payload of every [`AccessDenied`] reply.
+pub const ACCESS_DENIED_MAGIC: [u8; 4] = *b"FDNY";
+
+/// An origin, or group of origins, allowed to invoke an operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllowedOrigin {
+    /// Any caller, including messages without an attested origin.
+    Any,
+    /// Any web application.
+    AnyWebApp,
+    /// The web application backed by this contract.
+    WebApp(ContractInstanceId),
+    /// Any delegate.
+    AnyDelegate,
+    /// The delegate with this key.
+    Delegate(DelegateKey),
+}
+
+impl AllowedOrigin {
+    pub fn matches(&self, origin: Option<&MessageOrigin>) -> bool {
+        match (self, origin) {
+            (AllowedOrigin::Any, _) => true,
+            (AllowedOrigin::AnyWebApp, Some(MessageOrigin::WebApp(_))) => true,
+            (AllowedOrigin::WebApp(allowed), Some(MessageOrigin::WebApp(id))) => allowed == id,
+            (AllowedOrigin::AnyDelegate, Some(MessageOrigin::Delegate(_))) => true,
+            (AllowedOrigin::Delegate(allowed), Some(MessageOrigin::Delegate(key))) => {
+                allowed == key
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PolicyRule {
+    operation: String,
+    origin: AllowedOrigin,
+}
+
+/// Set of origins allowed to invoke each operation of a delegate.
+///
+/// Everything not explicitly allowed is denied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OriginPolicy {
+    rules: Vec<PolicyRule>,
+}
+
+impl OriginPolicy {
+    /// An empty policy, which denies everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows `origin` to invoke `operation`, use [`ANY_OPERATION`] to allow every operation.
+    pub fn allow(mut self, operation: impl Into<String>, origin: AllowedOrigin) -> Self {
+        self.insert(operation, origin);
+        self
+    }
+
+    /// Same as [`Self::allow`] but in place.
+    pub fn insert(&mut self, operation: impl Into<String>, origin: AllowedOrigin) {
+        let rule = PolicyRule {
+            operation: operation.into(),
+            origin,
+        };
+        if !self.rules.contains(&rule) {
+            self.rules.push(rule);
+        }
+    }
+
+    /// Removes a previously allowed origin, returns whether it was present.
+    pub fn revoke(&mut self, operation: &str, origin: &AllowedOrigin) -> bool {
+        let len = self.rules.len();
+        self.rules
+            .retain(|rule| !(rule.operation == operation && &rule.origin == origin));
+        len != self.rules.len()
+    }
+
+    pub fn is_allowed(&self, operation: &str, origin: Option<&MessageOrigin>) -> bool {
+        self.rules.iter().any(|rule| {
+            (rule.operation == operation || rule.operation == ANY_OPERATION)
+                && rule.origin.matches(origin)
+        })
+    }
+
+    /// Checks whether `origin` may invoke `operation`.
+    pub fn check(
+        &self,
+        operation: &str,
+        origin: Option<&MessageOrigin>,
+    ) -> Result<(), AccessDenied> {
+        if self.is_allowed(operation, origin) {
+            Ok(())
+        } else {
+            Err(AccessDenied {
+                operation: operation.to_owned(),
+                origin: origin.cloned(),
+            })
+        }
+    }
+
+    /// Loads the policy stored in the delegate secrets under `key`, if any.
+    pub fn load(ctx: &DelegateCtx, key: &[u8]) -> Result<Option<Self>, DelegateError> {
+        ctx.get_secret(key)
+            .map(|bytes| {
+                bincode::deserialize(&bytes).map_err(|e| DelegateError::Deser(format!("{e}")))
+            })
+            .transpose()
+    }
+
+    /// Stores the policy in the delegate secrets under `key`.
+    pub fn store(&self, ctx: &mut DelegateCtx, key: &[u8]) -> Result<(), DelegateError> {
+        let bytes = bincode::serialize(self).map_err(|e| DelegateError::Deser(format!("{e}")))?;
+        if ctx.set_secret(key, &bytes) {
+            Ok(())
+        } else {
+            Err(DelegateError::Other(
+                "failed to store the origin policy".into(),
+            ))
+        }
+    }
+
+    /// [`Self::load`] from [`POLICY_SECRET_KEY`].
+    pub fn load_default(ctx: &DelegateCtx) -> Result<Option<Self>, DelegateError> {
+        Self::load(ctx, POLICY_SECRET_KEY)
+    }
+
+    /// [`Self::store`] under [`POLICY_SECRET_KEY`].
+    pub fn store_default(&self, ctx: &mut DelegateCtx) -> Result<(), DelegateError> {
+        self.store(ctx, POLICY_SECRET_KEY)
+    }
+}
+
+/// A call denied by an [`OriginPolicy`].
+///
+/// Converts into the standard reply sent back to the caller, an [`ApplicationMessage`]
+/// carrying this value bincode encoded after [`ACCESS_DENIED_MAGIC`], or into a [`DelegateError`] to abort processing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("operation `{operation}` not allowed for origin {origin:?}")]
+pub struct AccessDenied {
+    pub operation: String,
+    pub origin: Option<MessageOrigin>,
+}
+
+impl AccessDenied {
+    /// Decodes the payload of an application message produced from an [`AccessDenied`].
+    ///
+    /// Returns `None` for any other payload, including ordinary replies that happen to
+    /// decode as an [`AccessDenied`].
+    pub fn from_payload(payload: &[u8]) -> Option<Self> {
+        let denied = payload.strip_prefix(&ACCESS_DENIED_MAGIC)?;
+        bincode::deserialize(denied).ok()
+    }
+}
+
+impl From<AccessDenied> for ApplicationMessage {
+    fn from(denied: AccessDenied) -> Self {
+        let mut payload = ACCESS_DENIED_MAGIC.to_vec();
+        bincode::serialize_into(&mut payload, &denied).expect("serialization never fails");
+        ApplicationMessage::new(payload).processed(true)
+    }
+}
+
+impl From<AccessDenied> for OutboundDelegateMsg {
+    fn from(denied: AccessDenied) -> Self {
+        OutboundDelegateMsg::ApplicationMessage(denied.into())
+    }
+}
+
+impl<R> From<AccessDenied> for TypedOutbound<R> {
+    fn from(denied: AccessDenied) -> Self {
+        TypedOutbound::Raw(denied.into())
+    }
+}
+
+impl From<AccessDenied> for DelegateError {
+    fn from(denied: AccessDenied) -> Self {
+        DelegateError::Other(denied.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_hash::CodeHash;
+
+    const APP: ContractInstanceId = ContractInstanceId::new([1; 32]);
+    const OTHER_APP: ContractInstanceId = ContractInstanceId::new([2; 32]);
+
+    fn delegate(byte: u8) -> DelegateKey {
+        DelegateKey::new([byte; 32], CodeHash::new([0; 32]))
+    }
+
+    #[test]
+    fn only_listed_origins_are_allowed() {
+        let policy = OriginPolicy::new()
+            .allow("sign", AllowedOrigin::WebApp(APP))
+            .allow(ANY_OPERATION, AllowedOrigin::Delegate(delegate(1)));
+
+        let app = MessageOrigin::WebApp(APP);
+        let other_app = MessageOrigin::WebApp(OTHER_APP);
+        assert!(policy.check("sign", Some(&app)).is_ok());
+        assert!(policy.check("export", Some(&app)).is_err());
+        assert!(policy.check("sign", Some(&other_app)).is_err());
+        assert!(policy.check("sign", None).is_err());
+
+        let trusted = MessageOrigin::Delegate(delegate(1));
+        let untrusted = MessageOrigin::Delegate(delegate(2));
+        assert!(policy.check("export", Some(&trusted)).is_ok());
+        assert!(policy.check("export", Some(&untrusted)).is_err());
+    }
+
+    #[test]
+    fn revoke_removes_rule() {
+        let mut policy = OriginPolicy::new().allow("sign", AllowedOrigin::AnyWebApp);
+        let app = MessageOrigin::WebApp(APP);
+        assert!(policy.is_allowed("sign", Some(&app)));
+        assert!(policy.revoke("sign", &AllowedOrigin::AnyWebApp));
+        assert!(!policy.is_allowed("sign", Some(&app)));
+        assert!(!policy.revoke("sign", &AllowedOrigin::AnyWebApp));
+    }
+
+    #[test]
+    fn denied_call_reply_roundtrips() {
+        let origin = MessageOrigin::WebApp(OTHER_APP);
+        let denied = OriginPolicy::new()
+            .check("sign", Some(&origin))
+            .unwrap_err();
+        let OutboundDelegateMsg::ApplicationMessage(msg) =
+            OutboundDelegateMsg::from(denied.clone())
+        else {
+            unreachable!()
+        };
+        assert!(msg.processed);
+        assert_eq!(AccessDenied::from_payload(&msg.payload), Some(denied));
+    }
+
+    #[test]
+    fn ordinary_payload_is_not_a_denial() {
+        // Without the tag this would decode: an empty operation and no origin.
+        let payload = bincode::serialize(&(String::new(), None::<MessageOrigin>)).unwrap();
+        assert!(bincode::deserialize::<AccessDenied>(&payload).is_ok());
+        assert_eq!(AccessDenied::from_payload(&payload), None);
+        assert_eq!(AccessDenied::from_payload(&ACCESS_DENIED_MAGIC[..2]), None);
+    }
+}
@@ -0,0 +1,465 @@
+//! Request/response calls between delegates.
+//!
+//! [`DelegateMessage`]s are fire-and-forget. This module layers calls on top of them: the
+//! caller sends a typed request tagged with a call id, the callee answers with a typed
+//! reply for the same id, and the reply is routed back to the pending call together with
+//! the state the caller needs to continue.
+//!
+//! The pending calls live in a [`PendingCalls`], which is serializable so it can be kept
+//! in the caller's [`DelegateContext`] between messages, either on its own
+//! ([`PendingCalls::from_context`] and [`PendingCalls::to_context`]) or as part of a larger
+//! context type. Calls with a timeout schedule a timer
+//! ([`OutboundDelegateMsg::ScheduleTimer`]), which completes the call as timed out if no
+//! reply came first.
+//!
+//! On the caller:
+//!
+//! ```ignore
+//! let mut calls = PendingCalls::<Step>::from_context(&context)?;
+//! let mut outbound = calls.call(&my_key, signer, &SignRequest { .. }, Step::Signing, Some(timeout))?;
+//!
+//! // later, on InboundDelegateMsg::DelegateMessage(msg)
+//! if let Some(done) = calls.handle_reply::<Signature>(&msg)? {
+//!     outbound.extend(done.cancel_timer());
+//!     match done.outcome { /* ... */ }
+//! }
+//! // or on InboundDelegateMsg::TimerFired(fired)
+//! if let Some(done) = calls.handle_timer::<Signature>(&fired) { /* RpcOutcome::TimedOut */ }
+//! outbound.push(OutboundDelegateMsg::ContextUpdated(calls.to_context()?));
+//! ```
+//!
+//! On the callee:
+//!
+//! ```ignore
+//! if let Some(request) = RpcRequest::<SignRequest>::decode(&msg)? {
+//!     return Ok(vec![request.reply(&my_key, &sign(request.body))?]);
+//! }
+//! ```
+//!
+//! # Wire format
+//!
+//! The payload of the [`DelegateMessage`] is [`RPC_MAGIC`] followed by the bincode encoded
+//! frame, which is either a request carrying the call id and the bincode encoded request,
+//! or a response carrying the call id and either the bincode encoded reply or an error
+//! message. Payloads without the magic prefix are plain delegate messages and are left to
+//! the delegate.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::delegate_interface::{
+    ContextError, DelegateContext, DelegateError, DelegateKey, DelegateMessage,
+    OutboundDelegateMsg, ScheduleTimer, TimerFired, TimerId,
+};
+
+/// Prefix of the payload of every RPC message.
+pub const RPC_MAGIC: [u8; 4] = *b"FRPC";
+
+/// Timer ids with this bit set are reserved for call timeouts, delegates using
+/// [`PendingCalls`] must not schedule timers with it.
+pub const RPC_TIMER_FLAG: u64 = 1 << 63;
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("failed to encode or decode rpc message: {0}")]
+    Encoding(String),
+    #[error("reply to call {0} not sent by the called delegate")]
+    UnexpectedSender(u64),
+    #[error(transparent)]
+    Context(#[from] ContextError),
+}
+
+impl From<bincode::Error> for RpcError {
+    fn from(err: bincode::Error) -> Self {
+        RpcError::Encoding(format!("{err}"))
+    }
+}
+
+impl From<RpcError> for DelegateError {
+    fn from(err: RpcError) -> Self {
+        match err {
+            RpcError::Encoding(reason) => DelegateError::Deser(reason),
+            other => DelegateError::Other(format!("{other}")),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum RpcFrame {
+    Request {
+        call_id: u64,
+        body: Vec<u8>,
+    },
+    Response {
+        call_id: u64,
+        body: Result<Vec<u8>, String>,
+    },
+}
+
+impl RpcFrame {
+    fn encode(&self) -> Result<Vec<u8>, RpcError> {
+        let mut payload = RPC_MAGIC.to_vec();
+        bincode::serialize_into(&mut payload, self)?;
+        Ok(payload)
+    }
+
+    fn decode(payload: &[u8]) -> Result<Option<Self>, RpcError> {
+        match payload.strip_prefix(&RPC_MAGIC) {
+            Some(frame) => Ok(Some(bincode::deserialize(frame)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Whether a delegate message payload belongs to an RPC call.
+pub fn is_rpc_payload(payload: &[u8]) -> bool {
+    payload.starts_with(&RPC_MAGIC)
+}
+
+/// A call waiting for its reply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingCall<S> {
+    /// The delegate the call was sent to, the only one allowed to answer it.
+    pub target: DelegateKey,
+    /// The state the caller continues from once the call completes.
+    pub state: S,
+    has_timeout: bool,
+}
+
+/// The calls a delegate is waiting replies for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "S: Serialize", deserialize = "S: DeserializeOwned"))]
+pub struct PendingCalls<S> {
+    next_call: u64,
+    pending: BTreeMap<u64, PendingCall<S>>,
+}
+
+impl<S> Default for PendingCalls<S> {
+    fn default() -> Self {
+        Self {
+            next_call: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S> PendingCalls<S>
+where
+    S: Serialize + DeserializeOwned,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes the pending calls kept in `context`, an empty context has none.
+    pub fn from_context(context: &DelegateContext) -> Result<Self, RpcError> {
+        if context.as_ref().is_empty() {
+            return Ok(Self::new());
+        }
+        Ok(bincode::deserialize(context.as_ref())?)
+    }
+
+    /// Encodes the pending calls into a context to carry to the next message.
+    pub fn to_context(&self) -> Result<DelegateContext, RpcError> {
+        Ok(DelegateContext::try_new(bincode::serialize(self)?)?)
+    }
+
+    /// Calls `target` with `request` on behalf of `caller`, the delegate's own key.
+    ///
+    /// Returns the messages to send: the request and, with a `timeout`, the timer that
+    /// completes the call as [`RpcOutcome::TimedOut`] if no reply comes in time. `state`
+    /// is handed back when the call completes.
+    pub fn call<Req: Serialize>(
+        &mut self,
+        caller: &DelegateKey,
+        target: DelegateKey,
+        request: &Req,
+        state: S,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<OutboundDelegateMsg>, RpcError> {
+        let call_id = self.next_call;
+        let payload = RpcFrame::Request {
+            call_id,
+            body: bincode::serialize(request)?,
+        }
+        .encode()?;
+        self.next_call = (call_id + 1) & !RPC_TIMER_FLAG;
+
+        let mut outbound =
+            vec![DelegateMessage::new(target.clone(), caller.clone(), payload).into()];
+        if let Some(timeout) = timeout {
+            outbound.push(ScheduleTimer::after(timer_id(call_id), timeout, vec![]).into());
+        }
+        self.pending.insert(
+            call_id,
+            PendingCall {
+                target,
+                state,
+                has_timeout: timeout.is_some(),
+            },
+        );
+        Ok(outbound)
+    }
+
+    /// Matches a delegate message to the pending call it answers.
+    ///
+    /// Returns `None` for messages that are not RPC replies and for replies to unknown
+    /// calls, e.g. ones which already timed out. Replies must come from the called
+    /// delegate, anything else is rejected and the call stays pending.
+    pub fn handle_reply<Resp: DeserializeOwned>(
+        &mut self,
+        msg: &DelegateMessage,
+    ) -> Result<Option<RpcCompletion<Resp, S>>, RpcError> {
+        let Some(RpcFrame::Response { call_id, body }) = RpcFrame::decode(&msg.payload)? else {
+            return Ok(None);
+        };
+        let Some(call) = self.pending.get(&call_id) else {
+            return Ok(None);
+        };
+        if call.target != msg.sender {
+            return Err(RpcError::UnexpectedSender(call_id));
+        }
+        let outcome = match body {
+            Ok(body) => RpcOutcome::Reply(bincode::deserialize(&body)?),
+            Err(reason) => RpcOutcome::Failed(reason),
+        };
+        let call = self.pending.remove(&call_id).expect("checked above");
+        Ok(Some(RpcCompletion {
+            call_id,
+            state: call.state,
+            outcome,
+            has_timeout: call.has_timeout,
+        }))
+    }
+
+    /// Completes the pending call a fired timer belongs to as timed out.
+    ///
+    /// Returns `None` for timers not scheduled by [`Self::call`] or whose call already
+    /// completed.
+    pub fn handle_timer<Resp>(&mut self, fired: &TimerFired) -> Option<RpcCompletion<Resp, S>> {
+        let id = fired.timer_id.as_u64();
+        if id & RPC_TIMER_FLAG == 0 {
+            return None;
+        }
+        let call_id = id & !RPC_TIMER_FLAG;
+        let call = self.pending.remove(&call_id)?;
+        Some(RpcCompletion {
+            call_id,
+            state: call.state,
+            outcome: RpcOutcome::TimedOut,
+            has_timeout: false,
+        })
+    }
+
+    /// The call with this id, if still pending.
+    pub fn get(&self, call_id: u64) -> Option<&PendingCall<S>> {
+        self.pending.get(&call_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+fn timer_id(call_id: u64) -> TimerId {
+    TimerId::new(call_id | RPC_TIMER_FLAG)
+}
+
+/// How a call ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcOutcome<R> {
+    Reply(R),
+    /// The callee answered with an error.
+    Failed(String),
+    /// No reply arrived before the timeout.
+    TimedOut,
+}
+
+/// A call which is no longer pending.
+#[derive(Debug)]
+pub struct RpcCompletion<R, S> {
+    pub call_id: u64,
+    pub state: S,
+    pub outcome: RpcOutcome<R>,
+    has_timeout: bool,
+}
+
+impl<R, S> RpcCompletion<R, S> {
+    /// The message cancelling the timeout of a call answered in time, if it had one.
+    pub fn cancel_timer(&self) -> Option<OutboundDelegateMsg> {
+        self.has_timeout
+            .then(|| OutboundDelegateMsg::CancelTimer(timer_id(self.call_id)))
+    }
+}
+
+/// A call received by the callee.
+#[derive(Debug, Clone)]
+pub struct RpcRequest<T> {
+    pub call_id: u64,
+    /// The calling delegate, as attested by the runtime.
+    pub caller: DelegateKey,
+    pub body: T,
+}
+
+impl<T: DeserializeOwned> RpcRequest<T> {
+    /// Decodes the call carried by `msg`, `None` if it is not an RPC request.
+    pub fn decode(msg: &DelegateMessage) -> Result<Option<Self>, RpcError> {
+        let Some(RpcFrame::Request { call_id, body }) = RpcFrame::decode(&msg.payload)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            call_id,
+            caller: msg.sender.clone(),
+            body: bincode::deserialize(&body)?,
+        }))
+    }
+}
+
+impl<T> RpcRequest<T> {
+    /// The reply to send back, `callee` being the delegate's own key.
+    pub fn reply<R: Serialize>(
+        &self,
+        callee: &DelegateKey,
+        response: &R,
+    ) -> Result<OutboundDelegateMsg, RpcError> {
+        self.respond(callee, Ok(bincode::serialize(response)?))
+    }
+
+    /// Answers the call with an error, reported to the caller as [`RpcOutcome::Failed`].
+    pub fn reply_err(
+        &self,
+        callee: &DelegateKey,
+        reason: impl Into<String>,
+    ) -> Result<OutboundDelegateMsg, RpcError> {
+        self.respond(callee, Err(reason.into()))
+    }
+
+    fn respond(
+        &self,
+        callee: &DelegateKey,
+        body: Result<Vec<u8>, String>,
+    ) -> Result<OutboundDelegateMsg, RpcError> {
+        let payload = RpcFrame::Response {
+            call_id: self.call_id,
+            body,
+        }
+        .encode()?;
+        Ok(DelegateMessage::new(self.caller.clone(), callee.clone(), payload).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::code_hash::CodeHash;
+
+    fn key(byte: u8) -> DelegateKey {
+        DelegateKey::new([byte; 32], CodeHash::new([0; 32]))
+    }
+
+    fn sent_message(msg: OutboundDelegateMsg) -> DelegateMessage {
+        match msg {
+            OutboundDelegateMsg::SendDelegateMessage(msg) => msg,
+            other => panic!("unexpected message: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reply_is_routed_to_pending_call() {
+        let (caller, callee) = (key(1), key(2));
+        let mut calls = PendingCalls::<String>::new();
+        let outbound = calls
+            .call(
+                &caller,
+                callee.clone(),
+                &21u32,
+                "doubling".into(),
+                Some(Duration::from_secs(5)),
+            )
+            .unwrap();
+        let [request, OutboundDelegateMsg::ScheduleTimer(_)] =
+            <[_; 2]>::try_from(outbound).unwrap()
+        else {
+            panic!("a call with a timeout schedules a timer");
+        };
+
+        // the pending calls survive a round-trip through the context
+        let mut calls = PendingCalls::<String>::from_context(&calls.to_context().unwrap()).unwrap();
+
+        let request = RpcRequest::<u32>::decode(&sent_message(request))
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.caller, caller);
+        let reply = sent_message(request.reply(&callee, &(request.body * 2)).unwrap());
+        assert_eq!(reply.target, caller);
+
+        let done = calls.handle_reply::<u32>(&reply).unwrap().unwrap();
+        assert_eq!(done.state, "doubling");
+        assert_eq!(done.outcome, RpcOutcome::Reply(42));
+        assert!(matches!(
+            done.cancel_timer(),
+            Some(OutboundDelegateMsg::CancelTimer(id)) if id.as_u64() == RPC_TIMER_FLAG
+        ));
+        assert!(calls.is_empty());
+        // a duplicated reply no longer matches anything
+        assert!(calls.handle_reply::<u32>(&reply).unwrap().is_none());
+    }
+
+    #[test]
+    fn reply_from_another_delegate_is_rejected() {
+        let (caller, callee) = (key(1), key(2));
+        let mut calls = PendingCalls::<()>::new();
+        let outbound = calls.call(&caller, callee, &(), (), None).unwrap();
+        assert_eq!(outbound.len(), 1);
+        let request = RpcRequest::<()>::decode(&sent_message(outbound.into_iter().next().unwrap()))
+            .unwrap()
+            .unwrap();
+
+        let forged = sent_message(request.reply_err(&key(3), "nope").unwrap());
+        assert!(matches!(
+            calls.handle_reply::<()>(&forged),
+            Err(RpcError::UnexpectedSender(0))
+        ));
+        assert_eq!(calls.len(), 1);
+    }
+
+    #[test]
+    fn timer_completes_call_as_timed_out() {
+        let mut calls = PendingCalls::<u8>::new();
+        calls
+            .call(&key(1), key(2), &(), 7, Some(Duration::from_secs(1)))
+            .unwrap();
+        let fired = |id| TimerFired {
+            timer_id: TimerId::new(id),
+            fired_at: DateTime::from_timestamp_millis(0).unwrap(),
+            payload: vec![],
+            context: DelegateContext::default(),
+        };
+        // the delegate's own timers are not ours
+        assert!(calls.handle_timer::<()>(&fired(0)).is_none());
+
+        let done = calls.handle_timer::<()>(&fired(RPC_TIMER_FLAG)).unwrap();
+        assert!(done.cancel_timer().is_none());
+        assert_eq!((done.state, done.outcome), (7, RpcOutcome::TimedOut));
+        assert!(calls.is_empty());
+    }
+
+    #[test]
+    fn plain_messages_are_ignored() {
+        let msg = DelegateMessage::new(key(1), key(2), b"hello".to_vec());
+        assert!(!is_rpc_payload(&msg.payload));
+        assert!(RpcRequest::<()>::decode(&msg).unwrap().is_none());
+        assert!(PendingCalls::<()>::new()
+            .handle_reply::<()>(&msg)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,620 @@
+//! Recording and deterministic replay of delegate executions (feature `trace`).
+//!
+//! With the `trace` feature enabled in the delegate crate, the `#[delegate]` macro runs every
+//! `process()` call through [`record`]. It captures the parameters, the origin, the inbound
+//! message, every [`DelegateCtx`] host call together with its result, and the outcome into a
+//! [`DelegateTrace`] and hands it to the trace sink installed with [`set_trace_sink`], for
+//! example [`log_trace`]. Until a sink is installed nothing is recorded.
+//!
+//! [`replay`] re-runs a delegate natively against a trace. Host calls are answered from the
+//! recording instead of the host, and the [`ReplayReport`] lists every place where the run
+//! diverged from it, either in the host calls it made or in the messages it returned:
+//!
+//! ```ignore
+//! let trace = DelegateTrace::from_text(&logged)?;
+//! let report = replay::<MyDelegate>(&trace)?;
+//! assert!(report.is_faithful(), "{:#?}", report.divergences);
+//! ```
+//!
+//! Traces contain the secrets and contract states the delegate read, handle them with the
+//! same care as the secrets themselves.
+//!
+//! # Format
+//!
+//! A trace is encoded as [`TRACE_MAGIC`], the [`TRACE_VERSION`] byte and the bincode encoded
+//! trace. The parameters, the inbound message and the outcome are kept as the bincode bytes
+//! exchanged with the host, and each host call result as the bincode encoding of the value
+//! the [`DelegateCtx`] method returned. The text form is the lowercase hex encoding of it.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::delegate_host::DelegateCtx;
+use crate::delegate_interface::{
+    DelegateError, DelegateInterface, InboundDelegateMsg, MessageOrigin, OutboundDelegateMsg,
+};
+use crate::parameters::Parameters;
+
+/// Prefix of every encoded trace.
+pub const TRACE_MAGIC: [u8; 4] = *b"FNDT";
+
+/// Current version of the trace encoding.
+pub const TRACE_VERSION: u8 = 1;
+
+/// A host call made through [`DelegateCtx`], with its arguments.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HostCall {
+    ContextLen,
+    ContextRead,
+    ContextReadInto {
+        max_len: usize,
+    },
+    ContextWrite {
+        data: Vec<u8>,
+    },
+    GetSecretLen {
+        key: Vec<u8>,
+    },
+    GetSecret {
+        key: Vec<u8>,
+    },
+    SetSecret {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    HasSecret {
+        key: Vec<u8>,
+    },
+    RemoveSecret {
+        key: Vec<u8>,
+    },
+    SetSecretWithTtl {
+        key: Vec<u8>,
+        value: Vec<u8>,
+        ttl: Duration,
+    },
+    SecretMetadata {
+        key: Vec<u8>,
+    },
+    ListSecrets {
+        prefix: Vec<u8>,
+    },
+    /// The operations encoded with [`encode_secret_ops`](crate::delegate_host::encode_secret_ops).
+    CommitSecrets {
+        ops: Vec<u8>,
+    },
+    GetContractState {
+        id: [u8; 32],
+    },
+    PutContractState {
+        id: [u8; 32],
+        state: Vec<u8>,
+    },
+    UpdateContractState {
+        id: [u8; 32],
+        state: Vec<u8>,
+    },
+    /// The bincode encoded `UpdateData`.
+    UpdateContract {
+        id: [u8; 32],
+        update: Vec<u8>,
+    },
+    SubscribeContract {
+        id: [u8; 32],
+    },
+    /// The code is identified by its blake3 hash, the cipher and nonce are not recorded.
+    CreateDelegate {
+        code_hash: [u8; 32],
+        params: Vec<u8>,
+    },
+}
+
+/// A recorded host call and the bincode encoded value it returned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostCallRecord {
+    pub call: HostCall,
+    pub result: Vec<u8>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum TraceError {
+    #[error("not a delegate trace")]
+    Malformed,
+    #[error("unsupported delegate trace version {0}")]
+    UnsupportedVersion(u8),
+    #[error("failed to decode the delegate trace: {0}")]
+    Decode(String),
+}
+
+/// Everything needed to re-run one `process()` call of a delegate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegateTrace {
+    parameters: Vec<u8>,
+    origin: Option<MessageOrigin>,
+    inbound: Vec<u8>,
+    host_calls: Vec<HostCallRecord>,
+    outcome: Vec<u8>,
+}
+
+impl DelegateTrace {
+    pub fn parameters(&self) -> Parameters<'_> {
+        Parameters::from(self.parameters.as_slice())
+    }
+
+    pub fn origin(&self) -> Option<&MessageOrigin> {
+        self.origin.as_ref()
+    }
+
+    pub fn inbound(&self) -> Result<InboundDelegateMsg<'_>, TraceError> {
+        bincode::deserialize(&self.inbound).map_err(|err| TraceError::Decode(format!("{err}")))
+    }
+
+    /// The host calls in the order the delegate made them.
+    pub fn host_calls(&self) -> &[HostCallRecord] {
+        &self.host_calls
+    }
+
+    /// What the recorded `process()` call returned.
+    pub fn outcome(&self) -> Result<Result<Vec<OutboundDelegateMsg>, DelegateError>, TraceError> {
+        bincode::deserialize(&self.outcome).map_err(|err| TraceError::Decode(format!("{err}")))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = TRACE_MAGIC.to_vec();
+        bytes.push(TRACE_VERSION);
+        bytes.extend(bincode::serialize(self).expect("serializing a trace can't fail"));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TraceError> {
+        let rest = bytes
+            .strip_prefix(&TRACE_MAGIC)
+            .ok_or(TraceError::Malformed)?;
+        let (&version, body) = rest.split_first().ok_or(TraceError::Malformed)?;
+        if version != TRACE_VERSION {
+            return Err(TraceError::UnsupportedVersion(version));
+        }
+        bincode::deserialize(body).map_err(|err| TraceError::Decode(format!("{err}")))
+    }
+
+    /// The encoded trace as lowercase hex, suitable for logs.
+    pub fn to_text(&self) -> String {
+        let bytes = self.to_bytes();
+        let mut text = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            let _ = write!(text, "{byte:02x}");
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, TraceError> {
+        let text = text.trim();
+        if !text.is_ascii() || text.len() % 2 != 0 {
+            return Err(TraceError::Malformed);
+        }
+        let bytes = (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| TraceError::Malformed)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Runs `D::process` while recording it, then passes the trace to the trace sink.
+///
+/// Without a sink installed on this thread `D::process` just runs. Called by the code
+/// generated by `#[delegate]` when the `trace` feature is enabled.
+pub fn record<D: DelegateInterface>(
+    ctx: &mut DelegateCtx,
+    parameters: Parameters<'static>,
+    origin: Option<MessageOrigin>,
+    inbound: InboundDelegateMsg,
+) -> Result<Vec<OutboundDelegateMsg>, DelegateError> {
+    let Some(sink) = SINK.with(Cell::get) else {
+        return D::process(ctx, parameters, origin, inbound);
+    };
+    let mut trace = DelegateTrace {
+        parameters: parameters.as_ref().to_vec(),
+        origin: origin.clone(),
+        inbound: bincode::serialize(&inbound).unwrap_or_default(),
+        host_calls: Vec::new(),
+        outcome: Vec::new(),
+    };
+    start(Mode::Recording(Vec::new()));
+    let result = D::process(ctx, parameters, origin, inbound);
+    if let Some(Mode::Recording(calls)) = finish() {
+        trace.host_calls = calls;
+    }
+    trace.outcome = bincode::serialize(&result).unwrap_or_default();
+    sink(&trace);
+    result
+}
+
+/// Installs the function receiving the traces made by [`record`] on this thread.
+pub fn set_trace_sink(sink: fn(&DelegateTrace)) {
+    SINK.with(|current| current.set(Some(sink)));
+}
+
+/// A trace sink logging traces in their text form ([`DelegateTrace::to_text`]).
+///
+/// Traces include the secrets and states the delegate read, only install it where the
+/// logs are as protected as the secrets.
+pub fn log_trace(trace: &DelegateTrace) {
+    tracing::info!(
+        target: "freenet_stdlib::delegate_trace",
+        trace = %trace.to_text(),
+        "recorded delegate execution"
+    );
+}
+
+/// A difference between a replay and the trace it replayed.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum Divergence {
+    /// The delegate made a different host call than the one recorded at `index`, or
+    /// more calls than were recorded. Host calls after it are answered by the native
+    /// stubs and no longer compared.
+    HostCall {
+        index: usize,
+        expected: Option<HostCall>,
+        actual: HostCall,
+    },
+    /// The recorded result at `index` doesn't decode as the type the method returns.
+    CorruptResult { index: usize },
+    /// The delegate returned without making the recorded host calls from `index` on.
+    UnusedHostCalls { index: usize, calls: Vec<HostCall> },
+    /// The outbound message at `index` differs, `None` when one side has fewer messages.
+    Outbound {
+        index: usize,
+        expected: Option<Box<OutboundDelegateMsg>>,
+        actual: Option<Box<OutboundDelegateMsg>>,
+    },
+    /// One run failed and the other didn't, or they failed differently.
+    Outcome { expected: String, actual: String },
+}
+
+/// The result of [`replay`].
+#[derive(Debug)]
+pub struct ReplayReport {
+    /// What `process()` returned during the replay.
+    pub outcome: Result<Vec<OutboundDelegateMsg>, DelegateError>,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    /// Whether the replay made the same host calls and returned the same outcome.
+    pub fn is_faithful(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Re-runs `D::process` natively against `trace`, answering the host calls from it.
+pub fn replay<D: DelegateInterface>(trace: &DelegateTrace) -> Result<ReplayReport, TraceError> {
+    let inbound = trace.inbound()?.into_owned();
+    let expected = trace.outcome()?;
+    start(Mode::Replaying(Replayer {
+        calls: trace.host_calls.clone(),
+        next: 0,
+        diverged: false,
+        divergences: Vec::new(),
+    }));
+    let mut ctx = DelegateCtx::default();
+    let outcome = D::process(
+        &mut ctx,
+        Parameters::from(trace.parameters.clone()),
+        trace.origin.clone(),
+        inbound,
+    );
+    let mut divergences = Vec::new();
+    if let Some(Mode::Replaying(replayer)) = finish() {
+        divergences = replayer.divergences;
+        if !replayer.diverged && replayer.next < replayer.calls.len() {
+            divergences.push(Divergence::UnusedHostCalls {
+                index: replayer.next,
+                calls: replayer.calls[replayer.next..]
+                    .iter()
+                    .map(|record| record.call.clone())
+                    .collect(),
+            });
+        }
+    }
+    divergences.extend(diff_outcomes(&expected, &outcome));
+    Ok(ReplayReport {
+        outcome,
+        divergences,
+    })
+}
+
+fn diff_outcomes(
+    expected: &Result<Vec<OutboundDelegateMsg>, DelegateError>,
+    actual: &Result<Vec<OutboundDelegateMsg>, DelegateError>,
+) -> Vec<Divergence> {
+    fn describe(outcome: &Result<Vec<OutboundDelegateMsg>, DelegateError>) -> String {
+        match outcome {
+            Ok(messages) => format!("{} outbound messages", messages.len()),
+            Err(err) => format!("error: {err}"),
+        }
+    }
+    match (expected, actual) {
+        (Ok(expected), Ok(actual)) => (0..expected.len().max(actual.len()))
+            .filter_map(|index| {
+                let (expected, actual) = (expected.get(index), actual.get(index));
+                let same = bincode::serialize(&expected).ok() == bincode::serialize(&actual).ok();
+                (!same).then(|| Divergence::Outbound {
+                    index,
+                    expected: expected.cloned().map(Box::new),
+                    actual: actual.cloned().map(Box::new),
+                })
+            })
+            .collect(),
+        (Err(e), Err(a)) if e.to_string() == a.to_string() => Vec::new(),
+        _ => vec![Divergence::Outcome {
+            expected: describe(expected),
+            actual: describe(actual),
+        }],
+    }
+}
+
+// ============================================================================
+// Trace sessions
+// ============================================================================
+
+struct Session {
+    /// Host calls made by a recorded host call (`get_secret` reading the length first)
+    /// are part of it and not recorded on their own.
+    depth: usize,
+    mode: Mode,
+}
+
+enum Mode {
+    Recording(Vec<HostCallRecord>),
+    Replaying(Replayer),
+}
+
+struct Replayer {
+    calls: Vec<HostCallRecord>,
+    next: usize,
+    diverged: bool,
+    divergences: Vec<Divergence>,
+}
+
+impl Replayer {
+    fn answer(&mut self, call: HostCall) -> Step {
+        if self.diverged {
+            return Step::Run;
+        }
+        let index = self.next;
+        match self.calls.get(index) {
+            Some(record) if record.call == call => {
+                self.next += 1;
+                Step::Replay(index, record.result.clone())
+            }
+            expected => {
+                self.diverged = true;
+                self.divergences.push(Divergence::HostCall {
+                    index,
+                    expected: expected.map(|record| record.call.clone()),
+                    actual: call,
+                });
+                Step::Run
+            }
+        }
+    }
+}
+
+enum Step {
+    Run,
+    Record(HostCall),
+    Replay(usize, Vec<u8>),
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+    static SINK: Cell<Option<fn(&DelegateTrace)>> = const { Cell::new(None) };
+}
+
+fn start(mode: Mode) {
+    SESSION.with(|session| *session.borrow_mut() = Some(Session { depth: 0, mode }));
+}
+
+fn finish() -> Option<Mode> {
+    SESSION.with(|session| session.borrow_mut().take().map(|session| session.mode))
+}
+
+fn with_session(f: impl FnOnce(&mut Session)) {
+    SESSION.with(|session| {
+        if let Some(session) = session.borrow_mut().as_mut() {
+            f(session)
+        }
+    });
+}
+
+fn nested<R>(run: impl FnOnce() -> R) -> R {
+    with_session(|session| session.depth += 1);
+    let result = run();
+    with_session(|session| session.depth -= 1);
+    result
+}
+
+/// Runs a host call, recording it or answering it from the trace when a session is active.
+pub(crate) fn host_call<R>(call: impl FnOnce() -> HostCall, run: impl FnOnce() -> R) -> R
+where
+    R: Serialize + DeserializeOwned,
+{
+    let step = SESSION.with(|session| {
+        let mut session = session.borrow_mut();
+        match session.as_mut() {
+            Some(Session { depth: 0, mode }) => match mode {
+                Mode::Recording(_) => Step::Record(call()),
+                Mode::Replaying(replayer) => replayer.answer(call()),
+            },
+            _ => Step::Run,
+        }
+    });
+    match step {
+        Step::Run => run(),
+        Step::Record(call) => {
+            let result = nested(run);
+            let record = HostCallRecord {
+                call,
+                result: bincode::serialize(&result).unwrap_or_default(),
+            };
+            with_session(|session| {
+                if let Mode::Recording(calls) = &mut session.mode {
+                    calls.push(record)
+                }
+            });
+            result
+        }
+        Step::Replay(index, result) => match bincode::deserialize(&result) {
+            Ok(result) => result,
+            Err(_) => {
+                with_session(|session| {
+                    if let Mode::Replaying(replayer) = &mut session.mode {
+                        replayer.diverged = true;
+                        replayer
+                            .divergences
+                            .push(Divergence::CorruptResult { index });
+                    }
+                });
+                run()
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::ApplicationMessage;
+
+    /// Echoes the secret named by the message payload and counts calls in the context.
+    struct SecretEcho;
+
+    impl DelegateInterface for SecretEcho {
+        fn process(
+            ctx: &mut DelegateCtx,
+            _parameters: Parameters<'static>,
+            _origin: Option<MessageOrigin>,
+            message: InboundDelegateMsg,
+        ) -> Result<Vec<OutboundDelegateMsg>, DelegateError> {
+            let InboundDelegateMsg::ApplicationMessage(msg) = message else {
+                return Err(DelegateError::Other("unexpected message".into()));
+            };
+            let secret = ctx.get_secret(&msg.payload).unwrap_or_default();
+            ctx.write(&[ctx.read().len() as u8 + 1]);
+            Ok(vec![OutboundDelegateMsg::ApplicationMessage(
+                ApplicationMessage::new(secret),
+            )])
+        }
+    }
+
+    thread_local! {
+        static LAST: RefCell<Option<DelegateTrace>> = const { RefCell::new(None) };
+    }
+
+    fn recorded<D: DelegateInterface>(payload: &[u8]) -> DelegateTrace {
+        set_trace_sink(|trace| LAST.with(|last| *last.borrow_mut() = Some(trace.clone())));
+        let inbound =
+            InboundDelegateMsg::ApplicationMessage(ApplicationMessage::new(payload.to_vec()));
+        let _ = record::<D>(
+            &mut DelegateCtx::default(),
+            Parameters::from(vec![7]),
+            None,
+            inbound,
+        );
+        LAST.with(|last| last.borrow_mut().take()).unwrap()
+    }
+
+    #[test]
+    fn nothing_is_recorded_without_a_sink() {
+        std::thread::spawn(|| {
+            assert!(SINK.with(Cell::get).is_none());
+            let inbound =
+                InboundDelegateMsg::ApplicationMessage(ApplicationMessage::new(b"key".to_vec()));
+            let result = record::<SecretEcho>(
+                &mut DelegateCtx::default(),
+                Parameters::from(vec![7]),
+                None,
+                inbound,
+            );
+            assert!(result.is_ok());
+            assert!(SESSION.with(|session| session.borrow().is_none()));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn records_host_calls_and_replays_faithfully() {
+        let trace = recorded::<SecretEcho>(b"key");
+        let calls: Vec<_> = trace.host_calls().iter().map(|r| r.call.clone()).collect();
+        assert_eq!(
+            calls,
+            vec![
+                HostCall::GetSecret {
+                    key: b"key".to_vec()
+                },
+                HostCall::ContextRead,
+                HostCall::ContextWrite { data: vec![1] },
+            ]
+        );
+        assert_eq!(trace.parameters().as_ref(), &[7]);
+
+        let decoded = DelegateTrace::from_text(&trace.to_text()).unwrap();
+        let report = replay::<SecretEcho>(&decoded).unwrap();
+        assert!(report.is_faithful(), "{:#?}", report.divergences);
+    }
+
+    #[test]
+    fn replay_answers_from_the_trace_and_reports_divergences() {
+        let mut trace = recorded::<SecretEcho>(b"key");
+        // What the host returned in production.
+        trace.host_calls[0].result = bincode::serialize(&Some(b"hunter2".to_vec())).unwrap();
+        trace.host_calls[1].result = bincode::serialize(&vec![0u8; 4]).unwrap();
+        let report = replay::<SecretEcho>(&trace).unwrap();
+        let Ok(outbound) = &report.outcome else {
+            panic!("replay failed");
+        };
+        let OutboundDelegateMsg::ApplicationMessage(msg) = &outbound[0] else {
+            panic!("unexpected message");
+        };
+        assert_eq!(msg.payload, b"hunter2");
+        assert!(matches!(
+            &report.divergences[..],
+            [
+                Divergence::HostCall {
+                    index: 2,
+                    expected: Some(HostCall::ContextWrite { .. }),
+                    actual: HostCall::ContextWrite { data },
+                },
+                Divergence::Outbound { index: 0, .. },
+            ] if data == &[5]
+        ));
+    }
+
+    #[test]
+    fn rejects_foreign_traces() {
+        let trace = recorded::<SecretEcho>(b"key");
+        let mut bytes = trace.to_bytes();
+        bytes[4] = TRACE_VERSION + 1;
+        assert!(matches!(
+            DelegateTrace::from_bytes(&bytes),
+            Err(TraceError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            DelegateTrace::from_bytes(b"FRPC"),
+            Err(TraceError::Malformed)
+        ));
+        assert!(matches!(
+            DelegateTrace::from_text("fnd"),
+            Err(TraceError::Malformed)
+        ));
+    }
+}
@@ -109,18 +109,19 @@ pub mod client_request {
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
-    pub const ENUM_MAX_CONTRACT_REQUEST_TYPE: u8 = 4;
+    pub const ENUM_MAX_CONTRACT_REQUEST_TYPE: u8 = 5;
     #[deprecated(
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
     #[allow(non_camel_case_types)]
-    pub const ENUM_VALUES_CONTRACT_REQUEST_TYPE: [ContractRequestType; 5] = [
+    pub const ENUM_VALUES_CONTRACT_REQUEST_TYPE: [ContractRequestType; 6] = [
         ContractRequestType::NONE,
         ContractRequestType::Put,
         ContractRequestType::Update,
         ContractRequestType::Get,
         ContractRequestType::Subscribe,
+        ContractRequestType::Query,
     ];
 
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -133,15 +134,17 @@ pub mod client_request {
         pub const Update: Self = Self(2);
         pub const Get: Self = Self(3);
         pub const Subscribe: Self = Self(4);
+        pub const Query: Self = Self(5);
 
         pub const ENUM_MIN: u8 = 0;
-        pub const ENUM_MAX: u8 = 4;
+        pub const ENUM_MAX: u8 = 5;
         pub const ENUM_VALUES: &'static [Self] = &[
             Self::NONE,
             Self::Put,
             Self::Update,
             Self::Get,
             Self::Subscribe,
+            Self::Query,
         ];
         /// Returns the variant's name or "" if unknown.
         pub fn variant_name(self) -> Option<&'static str> {
@@ -151,6 +154,7 @@ pub mod client_request {
                 Self::Update => Some("Update"),
                 Self::Get => Some("Get"),
                 Self::Subscribe => Some("Subscribe"),
+                Self::Query => Some("Query"),
                 _ => None,
             }
         }
@@ -217,16 +221,17 @@ pub mod client_request {
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
-    pub const ENUM_MAX_INBOUND_DELEGATE_MSG_TYPE: u8 = 2;
+    pub const ENUM_MAX_INBOUND_DELEGATE_MSG_TYPE: u8 = 3;
     #[deprecated(
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
     #[allow(non_camel_case_types)]
-    pub const ENUM_VALUES_INBOUND_DELEGATE_MSG_TYPE: [InboundDelegateMsgType; 3] = [
+    pub const ENUM_VALUES_INBOUND_DELEGATE_MSG_TYPE: [InboundDelegateMsgType; 4] = [
         InboundDelegateMsgType::NONE,
         InboundDelegateMsgType::common_ApplicationMessage,
         InboundDelegateMsgType::UserInputResponse,
+        InboundDelegateMsgType::TimerFired,
     ];
 
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -237,13 +242,15 @@ pub mod client_request {
         pub const NONE: Self = Self(0);
         pub const common_ApplicationMessage: Self = Self(1);
         pub const UserInputResponse: Self = Self(2);
+        pub const TimerFired: Self = Self(3);
 
         pub const ENUM_MIN: u8 = 0;
-        pub const ENUM_MAX: u8 = 2;
+        pub const ENUM_MAX: u8 = 3;
         pub const ENUM_VALUES: &'static [Self] = &[
             Self::NONE,
             Self::common_ApplicationMessage,
             Self::UserInputResponse,
+            Self::TimerFired,
         ];
         /// Returns the variant's name or "" if unknown.
         pub fn variant_name(self) -> Option<&'static str> {
@@ -251,6 +258,7 @@ pub mod client_request {
                 Self::NONE => Some("NONE"),
                 Self::common_ApplicationMessage => Some("common_ApplicationMessage"),
                 Self::UserInputResponse => Some("UserInputResponse"),
+                Self::TimerFired => Some("TimerFired"),
                 _ => None,
             }
         }
@@ -1906,6 +1914,8 @@ pub mod client_request {
     impl<'a> Update<'a> {
         pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
         pub const VT_DATA: ::flatbuffers::VOffsetT = 6;
+        pub const VT_EXPECTED_SUMMARY: ::flatbuffers::VOffsetT = 8;
+        pub const VT_EXPECTED_STATE_HASH: ::flatbuffers::VOffsetT = 10;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
@@ -1922,6 +1932,12 @@ pub mod client_request {
             args: &'args UpdateArgs<'args>,
         ) -> ::flatbuffers::WIPOffset<Update<'bldr>> {
             let mut builder = UpdateBuilder::new(_fbb);
+            if let Some(x) = args.expected_state_hash {
+                builder.add_expected_state_hash(x);
+            }
+            if let Some(x) = args.expected_summary {
+                builder.add_expected_summary(x);
+            }
             if let Some(x) = args.data {
                 builder.add_data(x);
             }
@@ -1959,6 +1975,32 @@ pub mod client_request {
                     .unwrap()
             }
         }
+        #[inline]
+        pub fn expected_summary(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        Update::VT_EXPECTED_SUMMARY,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn expected_state_hash(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        Update::VT_EXPECTED_STATE_HASH,
+                        None,
+                    )
+            }
+        }
     }
 
     impl ::flatbuffers::Verifiable for Update<'_> {
@@ -1978,6 +2020,16 @@ pub mod client_request {
                     Self::VT_DATA,
                     true,
                 )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "expected_summary",
+                    Self::VT_EXPECTED_SUMMARY,
+                    false,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "expected_state_hash",
+                    Self::VT_EXPECTED_STATE_HASH,
+                    false,
+                )?
                 .finish();
             Ok(())
         }
@@ -1985,6 +2037,8 @@ pub mod client_request {
     pub struct UpdateArgs<'a> {
         pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
         pub data: Option<::flatbuffers::WIPOffset<super::common::UpdateData<'a>>>,
+        pub expected_summary: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub expected_state_hash: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
     }
     impl<'a> Default for UpdateArgs<'a> {
         #[inline]
@@ -1992,6 +2046,8 @@ pub mod client_request {
             UpdateArgs {
                 key: None,  // required field
                 data: None, // required field
+                expected_summary: None,
+                expected_state_hash: None,
             }
         }
     }
@@ -2018,6 +2074,26 @@ pub mod client_request {
                 );
         }
         #[inline]
+        pub fn add_expected_summary(
+            &mut self,
+            expected_summary: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                Update::VT_EXPECTED_SUMMARY,
+                expected_summary,
+            );
+        }
+        #[inline]
+        pub fn add_expected_state_hash(
+            &mut self,
+            expected_state_hash: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                Update::VT_EXPECTED_STATE_HASH,
+                expected_state_hash,
+            );
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> UpdateBuilder<'a, 'b, A> {
@@ -2041,6 +2117,8 @@ pub mod client_request {
             let mut ds = f.debug_struct("Update");
             ds.field("key", &self.key());
             ds.field("data", &self.data());
+            ds.field("expected_summary", &self.expected_summary());
+            ds.field("expected_state_hash", &self.expected_state_hash());
             ds.finish()
         }
     }
@@ -2066,6 +2144,9 @@ pub mod client_request {
         pub const VT_FETCH_CONTRACT: ::flatbuffers::VOffsetT = 6;
         pub const VT_SUBSCRIBE: ::flatbuffers::VOffsetT = 8;
         pub const VT_BLOCKING_SUBSCRIBE: ::flatbuffers::VOffsetT = 10;
+        pub const VT_METADATA_ONLY: ::flatbuffers::VOffsetT = 12;
+        pub const VT_RANGE_OFFSET: ::flatbuffers::VOffsetT = 14;
+        pub const VT_RANGE_LEN: ::flatbuffers::VOffsetT = 16;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
@@ -2082,9 +2163,12 @@ pub mod client_request {
             args: &'args GetArgs<'args>,
         ) -> ::flatbuffers::WIPOffset<Get<'bldr>> {
             let mut builder = GetBuilder::new(_fbb);
+            builder.add_range_len(args.range_len);
+            builder.add_range_offset(args.range_offset);
             if let Some(x) = args.key {
                 builder.add_key(x);
             }
+            builder.add_metadata_only(args.metadata_only);
             builder.add_blocking_subscribe(args.blocking_subscribe);
             builder.add_subscribe(args.subscribe);
             builder.add_fetch_contract(args.fetch_contract);
@@ -2138,6 +2222,31 @@ pub mod client_request {
                     .unwrap()
             }
         }
+        #[inline]
+        pub fn metadata_only(&self) -> bool {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<bool>(Get::VT_METADATA_ONLY, Some(false))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn range_offset(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(Get::VT_RANGE_OFFSET, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn range_len(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u64>(Get::VT_RANGE_LEN, Some(0)).unwrap() }
+        }
     }
 
     impl ::flatbuffers::Verifiable for Get<'_> {
@@ -2155,6 +2264,9 @@ pub mod client_request {
                 .visit_field::<bool>("fetch_contract", Self::VT_FETCH_CONTRACT, false)?
                 .visit_field::<bool>("subscribe", Self::VT_SUBSCRIBE, false)?
                 .visit_field::<bool>("blocking_subscribe", Self::VT_BLOCKING_SUBSCRIBE, false)?
+                .visit_field::<bool>("metadata_only", Self::VT_METADATA_ONLY, false)?
+                .visit_field::<u64>("range_offset", Self::VT_RANGE_OFFSET, false)?
+                .visit_field::<u64>("range_len", Self::VT_RANGE_LEN, false)?
                 .finish();
             Ok(())
         }
@@ -2164,6 +2276,9 @@ pub mod client_request {
         pub fetch_contract: bool,
         pub subscribe: bool,
         pub blocking_subscribe: bool,
+        pub metadata_only: bool,
+        pub range_offset: u64,
+        pub range_len: u64,
     }
     impl<'a> Default for GetArgs<'a> {
         #[inline]
@@ -2173,6 +2288,9 @@ pub mod client_request {
                 fetch_contract: false,
                 subscribe: false,
                 blocking_subscribe: false,
+                metadata_only: false,
+                range_offset: 0,
+                range_len: 0,
             }
         }
     }
@@ -2206,6 +2324,20 @@ pub mod client_request {
                 .push_slot::<bool>(Get::VT_BLOCKING_SUBSCRIBE, blocking_subscribe, false);
         }
         #[inline]
+        pub fn add_metadata_only(&mut self, metadata_only: bool) {
+            self.fbb_
+                .push_slot::<bool>(Get::VT_METADATA_ONLY, metadata_only, false);
+        }
+        #[inline]
+        pub fn add_range_offset(&mut self, range_offset: u64) {
+            self.fbb_
+                .push_slot::<u64>(Get::VT_RANGE_OFFSET, range_offset, 0);
+        }
+        #[inline]
+        pub fn add_range_len(&mut self, range_len: u64) {
+            self.fbb_.push_slot::<u64>(Get::VT_RANGE_LEN, range_len, 0);
+        }
+        #[inline]
         pub fn new(_fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>) -> GetBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
             GetBuilder {
@@ -2228,6 +2360,9 @@ pub mod client_request {
             ds.field("fetch_contract", &self.fetch_contract());
             ds.field("subscribe", &self.subscribe());
             ds.field("blocking_subscribe", &self.blocking_subscribe());
+            ds.field("metadata_only", &self.metadata_only());
+            ds.field("range_offset", &self.range_offset());
+            ds.field("range_len", &self.range_len());
             ds.finish()
         }
     }
@@ -2387,6 +2522,164 @@ pub mod client_request {
             ds.finish()
         }
     }
+    pub enum QueryOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct Query<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for Query<'a> {
+        type Inner = Query<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> Query<'a> {
+        pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
+        pub const VT_QUERY: ::flatbuffers::VOffsetT = 6;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            Query { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args QueryArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<Query<'bldr>> {
+            let mut builder = QueryBuilder::new(_fbb);
+            if let Some(x) = args.query {
+                builder.add_query(x);
+            }
+            if let Some(x) = args.key {
+                builder.add_key(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn key(&self) -> super::common::ContractKey<'a> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                        Query::VT_KEY,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn query(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        Query::VT_QUERY,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for Query<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "query",
+                    Self::VT_QUERY,
+                    true,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct QueryArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
+        pub query: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for QueryArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            QueryArgs {
+                key: None,   // required field
+                query: None, // required field
+            }
+        }
+    }
+
+    pub struct QueryBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> QueryBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<super::common::ContractKey<'b>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<super::common::ContractKey>>(
+                    Query::VT_KEY,
+                    key,
+                );
+        }
+        #[inline]
+        pub fn add_query(
+            &mut self,
+            query: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(Query::VT_QUERY, query);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> QueryBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            QueryBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<Query<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, Query::VT_KEY, "key");
+            self.fbb_.required(o, Query::VT_QUERY, "query");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for Query<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("Query");
+            ds.field("key", &self.key());
+            ds.field("query", &self.query());
+            ds.finish()
+        }
+    }
     pub enum ClientResponseOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
@@ -2694,6 +2987,211 @@ pub mod client_request {
             ds.finish()
         }
     }
+    pub enum TimerFiredOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct TimerFired<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for TimerFired<'a> {
+        type Inner = TimerFired<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> TimerFired<'a> {
+        pub const VT_TIMER_ID: ::flatbuffers::VOffsetT = 4;
+        pub const VT_FIRED_AT_MS: ::flatbuffers::VOffsetT = 6;
+        pub const VT_PAYLOAD: ::flatbuffers::VOffsetT = 8;
+        pub const VT_DELEGATE_CONTEXT: ::flatbuffers::VOffsetT = 10;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            TimerFired { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args TimerFiredArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<TimerFired<'bldr>> {
+            let mut builder = TimerFiredBuilder::new(_fbb);
+            builder.add_fired_at_ms(args.fired_at_ms);
+            builder.add_timer_id(args.timer_id);
+            if let Some(x) = args.delegate_context {
+                builder.add_delegate_context(x);
+            }
+            if let Some(x) = args.payload {
+                builder.add_payload(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn timer_id(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<u64>(TimerFired::VT_TIMER_ID, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn fired_at_ms(&self) -> i64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<i64>(TimerFired::VT_FIRED_AT_MS, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn payload(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        TimerFired::VT_PAYLOAD,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn delegate_context(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        TimerFired::VT_DELEGATE_CONTEXT,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for TimerFired<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<u64>("timer_id", Self::VT_TIMER_ID, false)?
+                .visit_field::<i64>("fired_at_ms", Self::VT_FIRED_AT_MS, false)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "payload",
+                    Self::VT_PAYLOAD,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "delegate_context",
+                    Self::VT_DELEGATE_CONTEXT,
+                    true,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct TimerFiredArgs<'a> {
+        pub timer_id: u64,
+        pub fired_at_ms: i64,
+        pub payload: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub delegate_context: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for TimerFiredArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            TimerFiredArgs {
+                timer_id: 0,
+                fired_at_ms: 0,
+                payload: None,          // required field
+                delegate_context: None, // required field
+            }
+        }
+    }
+
+    pub struct TimerFiredBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> TimerFiredBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_timer_id(&mut self, timer_id: u64) {
+            self.fbb_
+                .push_slot::<u64>(TimerFired::VT_TIMER_ID, timer_id, 0);
+        }
+        #[inline]
+        pub fn add_fired_at_ms(&mut self, fired_at_ms: i64) {
+            self.fbb_
+                .push_slot::<i64>(TimerFired::VT_FIRED_AT_MS, fired_at_ms, 0);
+        }
+        #[inline]
+        pub fn add_payload(
+            &mut self,
+            payload: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(TimerFired::VT_PAYLOAD, payload);
+        }
+        #[inline]
+        pub fn add_delegate_context(
+            &mut self,
+            delegate_context: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                TimerFired::VT_DELEGATE_CONTEXT,
+                delegate_context,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> TimerFiredBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            TimerFiredBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<TimerFired<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, TimerFired::VT_PAYLOAD, "payload");
+            self.fbb_
+                .required(o, TimerFired::VT_DELEGATE_CONTEXT, "delegate_context");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for TimerFired<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("TimerFired");
+            ds.field("timer_id", &self.timer_id());
+            ds.field("fired_at_ms", &self.fired_at_ms());
+            ds.field("payload", &self.payload());
+            ds.field("delegate_context", &self.delegate_context());
+            ds.finish()
+        }
+    }
     pub enum InboundDelegateMsgOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
@@ -2794,6 +3292,20 @@ pub mod client_request {
                 None
             }
         }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn inbound_as_timer_fired(&self) -> Option<TimerFired<'a>> {
+            if self.inbound_type() == InboundDelegateMsgType::TimerFired {
+                let u = self.inbound();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { TimerFired::init_from_table(u) })
+            } else {
+                None
+            }
+        }
     }
 
     impl ::flatbuffers::Verifiable for InboundDelegateMsg<'_> {
@@ -2807,6 +3319,7 @@ pub mod client_request {
         match key {
           InboundDelegateMsgType::common_ApplicationMessage => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<super::common::ApplicationMessage>>("InboundDelegateMsgType::common_ApplicationMessage", pos),
           InboundDelegateMsgType::UserInputResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UserInputResponse>>("InboundDelegateMsgType::UserInputResponse", pos),
+          InboundDelegateMsgType::TimerFired => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<TimerFired>>("InboundDelegateMsgType::TimerFired", pos),
           _ => Ok(()),
         }
      })?
@@ -2895,6 +3408,16 @@ pub mod client_request {
                         )
                     }
                 }
+                InboundDelegateMsgType::TimerFired => {
+                    if let Some(x) = self.inbound_as_timer_fired() {
+                        ds.field("inbound", &x)
+                    } else {
+                        ds.field(
+                            "inbound",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
                 _ => {
                     let x: Option<()> = None;
                     ds.field("inbound", &x)
@@ -3556,6 +4079,20 @@ pub mod client_request {
                 None
             }
         }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_request_as_query(&self) -> Option<Query<'a>> {
+            if self.contract_request_type() == ContractRequestType::Query {
+                let u = self.contract_request();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { Query::init_from_table(u) })
+            } else {
+                None
+            }
+        }
     }
 
     impl ::flatbuffers::Verifiable for ContractRequest<'_> {
@@ -3592,6 +4129,11 @@ pub mod client_request {
                                 "ContractRequestType::Subscribe",
                                 pos,
                             ),
+                        ContractRequestType::Query => v
+                            .verify_union_variant::<::flatbuffers::ForwardsUOffset<Query>>(
+                                "ContractRequestType::Query",
+                                pos,
+                            ),
                         _ => Ok(()),
                     },
                 )?
@@ -3700,6 +4242,16 @@ pub mod client_request {
                         )
                     }
                 }
+                ContractRequestType::Query => {
+                    if let Some(x) = self.contract_request_as_query() {
+                        ds.field("contract_request", &x)
+                    } else {
+                        ds.field(
+                            "contract_request",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
                 _ => {
                     let x: Option<()> = None;
                     ds.field("contract_request", &x)
@@ -18,13 +18,13 @@ pub mod host_response {
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
-    pub const ENUM_MAX_CONTRACT_RESPONSE_TYPE: u8 = 6;
+    pub const ENUM_MAX_CONTRACT_RESPONSE_TYPE: u8 = 10;
     #[deprecated(
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
     #[allow(non_camel_case_types)]
-    pub const ENUM_VALUES_CONTRACT_RESPONSE_TYPE: [ContractResponseType; 7] = [
+    pub const ENUM_VALUES_CONTRACT_RESPONSE_TYPE: [ContractResponseType; 11] = [
         ContractResponseType::NONE,
         ContractResponseType::GetResponse,
         ContractResponseType::PutResponse,
@@ -32,6 +32,10 @@ pub mod host_response {
         ContractResponseType::UpdateResponse,
         ContractResponseType::NotFound,
         ContractResponseType::SubscribeResponse,
+        ContractResponseType::QueryResponse,
+        ContractResponseType::UpdatePreconditionFailed,
+        ContractResponseType::GetMetadataResponse,
+        ContractResponseType::GetRangeResponse,
     ];
 
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -46,9 +50,13 @@ pub mod host_response {
         pub const UpdateResponse: Self = Self(4);
         pub const NotFound: Self = Self(5);
         pub const SubscribeResponse: Self = Self(6);
+        pub const QueryResponse: Self = Self(7);
+        pub const UpdatePreconditionFailed: Self = Self(8);
+        pub const GetMetadataResponse: Self = Self(9);
+        pub const GetRangeResponse: Self = Self(10);
 
         pub const ENUM_MIN: u8 = 0;
-        pub const ENUM_MAX: u8 = 6;
+        pub const ENUM_MAX: u8 = 10;
         pub const ENUM_VALUES: &'static [Self] = &[
             Self::NONE,
             Self::GetResponse,
@@ -57,6 +65,10 @@ pub mod host_response {
             Self::UpdateResponse,
             Self::NotFound,
             Self::SubscribeResponse,
+            Self::QueryResponse,
+            Self::UpdatePreconditionFailed,
+            Self::GetMetadataResponse,
+            Self::GetRangeResponse,
         ];
         /// Returns the variant's name or "" if unknown.
         pub fn variant_name(self) -> Option<&'static str> {
@@ -68,6 +80,10 @@ pub mod host_response {
                 Self::UpdateResponse => Some("UpdateResponse"),
                 Self::NotFound => Some("NotFound"),
                 Self::SubscribeResponse => Some("SubscribeResponse"),
+                Self::QueryResponse => Some("QueryResponse"),
+                Self::UpdatePreconditionFailed => Some("UpdatePreconditionFailed"),
+                Self::GetMetadataResponse => Some("GetMetadataResponse"),
+                Self::GetRangeResponse => Some("GetRangeResponse"),
                 _ => None,
             }
         }
@@ -134,17 +150,19 @@ pub mod host_response {
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
-    pub const ENUM_MAX_OUTBOUND_DELEGATE_MSG_TYPE: u8 = 3;
+    pub const ENUM_MAX_OUTBOUND_DELEGATE_MSG_TYPE: u8 = 5;
     #[deprecated(
         since = "2.0.0",
         note = "Use associated constants instead. This will no longer be generated in 2021."
     )]
     #[allow(non_camel_case_types)]
-    pub const ENUM_VALUES_OUTBOUND_DELEGATE_MSG_TYPE: [OutboundDelegateMsgType; 4] = [
+    pub const ENUM_VALUES_OUTBOUND_DELEGATE_MSG_TYPE: [OutboundDelegateMsgType; 6] = [
         OutboundDelegateMsgType::NONE,
         OutboundDelegateMsgType::common_ApplicationMessage,
         OutboundDelegateMsgType::RequestUserInput,
         OutboundDelegateMsgType::ContextUpdated,
+        OutboundDelegateMsgType::ScheduleTimer,
+        OutboundDelegateMsgType::CancelTimer,
     ];
 
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -156,14 +174,18 @@ pub mod host_response {
         pub const common_ApplicationMessage: Self = Self(1);
         pub const RequestUserInput: Self = Self(2);
         pub const ContextUpdated: Self = Self(3);
+        pub const ScheduleTimer: Self = Self(4);
+        pub const CancelTimer: Self = Self(5);
 
         pub const ENUM_MIN: u8 = 0;
-        pub const ENUM_MAX: u8 = 3;
+        pub const ENUM_MAX: u8 = 5;
         pub const ENUM_VALUES: &'static [Self] = &[
             Self::NONE,
             Self::common_ApplicationMessage,
             Self::RequestUserInput,
             Self::ContextUpdated,
+            Self::ScheduleTimer,
+            Self::CancelTimer,
         ];
         /// Returns the variant's name or "" if unknown.
         pub fn variant_name(self) -> Option<&'static str> {
@@ -172,6 +194,8 @@ pub mod host_response {
                 Self::common_ApplicationMessage => Some("common_ApplicationMessage"),
                 Self::RequestUserInput => Some("RequestUserInput"),
                 Self::ContextUpdated => Some("ContextUpdated"),
+                Self::ScheduleTimer => Some("ScheduleTimer"),
+                Self::CancelTimer => Some("CancelTimer"),
                 _ => None,
             }
         }
@@ -1256,15 +1280,15 @@ pub mod host_response {
             ds.finish()
         }
     }
-    pub enum ContractResponseOffset {}
+    pub enum QueryResponseOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct ContractResponse<'a> {
+    pub struct QueryResponse<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for ContractResponse<'a> {
-        type Inner = ContractResponse<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for QueryResponse<'a> {
+        type Inner = QueryResponse<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -1273,13 +1297,13 @@ pub mod host_response {
         }
     }
 
-    impl<'a> ContractResponse<'a> {
-        pub const VT_CONTRACT_RESPONSE_TYPE: ::flatbuffers::VOffsetT = 4;
-        pub const VT_CONTRACT_RESPONSE: ::flatbuffers::VOffsetT = 6;
+    impl<'a> QueryResponse<'a> {
+        pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
+        pub const VT_RESULT: ::flatbuffers::VOffsetT = 6;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            ContractResponse { _tab: table }
+            QueryResponse { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -1289,292 +1313,140 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args ContractResponseArgs,
-        ) -> ::flatbuffers::WIPOffset<ContractResponse<'bldr>> {
-            let mut builder = ContractResponseBuilder::new(_fbb);
-            if let Some(x) = args.contract_response {
-                builder.add_contract_response(x);
+            args: &'args QueryResponseArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<QueryResponse<'bldr>> {
+            let mut builder = QueryResponseBuilder::new(_fbb);
+            if let Some(x) = args.result {
+                builder.add_result(x);
+            }
+            if let Some(x) = args.key {
+                builder.add_key(x);
             }
-            builder.add_contract_response_type(args.contract_response_type);
             builder.finish()
         }
 
         #[inline]
-        pub fn contract_response_type(&self) -> ContractResponseType {
+        pub fn key(&self) -> super::common::ContractKey<'a> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<ContractResponseType>(
-                        ContractResponse::VT_CONTRACT_RESPONSE_TYPE,
-                        Some(ContractResponseType::NONE),
+                    .get::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                        QueryResponse::VT_KEY,
+                        None,
                     )
                     .unwrap()
             }
         }
         #[inline]
-        pub fn contract_response(&self) -> ::flatbuffers::Table<'a> {
+        pub fn result(&self) -> ::flatbuffers::Vector<'a, u8> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Table<'a>>>(
-                        ContractResponse::VT_CONTRACT_RESPONSE,
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        QueryResponse::VT_RESULT,
                         None,
                     )
                     .unwrap()
             }
         }
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_get_response(&self) -> Option<GetResponse<'a>> {
-            if self.contract_response_type() == ContractResponseType::GetResponse {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { GetResponse::init_from_table(u) })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_put_response(&self) -> Option<PutResponse<'a>> {
-            if self.contract_response_type() == ContractResponseType::PutResponse {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { PutResponse::init_from_table(u) })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_update_notification(&self) -> Option<UpdateNotification<'a>> {
-            if self.contract_response_type() == ContractResponseType::UpdateNotification {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { UpdateNotification::init_from_table(u) })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_update_response(&self) -> Option<UpdateResponse<'a>> {
-            if self.contract_response_type() == ContractResponseType::UpdateResponse {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { UpdateResponse::init_from_table(u) })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_not_found(&self) -> Option<NotFound<'a>> {
-            if self.contract_response_type() == ContractResponseType::NotFound {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { NotFound::init_from_table(u) })
-            } else {
-                None
-            }
-        }
-
-        #[inline]
-        #[allow(non_snake_case)]
-        pub fn contract_response_as_subscribe_response(&self) -> Option<SubscribeResponse<'a>> {
-            if self.contract_response_type() == ContractResponseType::SubscribeResponse {
-                let u = self.contract_response();
-                // Safety:
-                // Created from a valid Table for this object
-                // Which contains a valid union in this slot
-                Some(unsafe { SubscribeResponse::init_from_table(u) })
-            } else {
-                None
-            }
-        }
     }
 
-    impl ::flatbuffers::Verifiable for ContractResponse<'_> {
+    impl ::flatbuffers::Verifiable for QueryResponse<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
             pos: usize,
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
-     .visit_union::<ContractResponseType, _>("contract_response_type", Self::VT_CONTRACT_RESPONSE_TYPE, "contract_response", Self::VT_CONTRACT_RESPONSE, true, |key, v, pos| {
-        match key {
-          ContractResponseType::GetResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<GetResponse>>("ContractResponseType::GetResponse", pos),
-          ContractResponseType::PutResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<PutResponse>>("ContractResponseType::PutResponse", pos),
-          ContractResponseType::UpdateNotification => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UpdateNotification>>("ContractResponseType::UpdateNotification", pos),
-          ContractResponseType::UpdateResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UpdateResponse>>("ContractResponseType::UpdateResponse", pos),
-          ContractResponseType::NotFound => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<NotFound>>("ContractResponseType::NotFound", pos),
-          ContractResponseType::SubscribeResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<SubscribeResponse>>("ContractResponseType::SubscribeResponse", pos),
-          _ => Ok(()),
-        }
-     })?
-     .finish();
+                .visit_field::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "result",
+                    Self::VT_RESULT,
+                    true,
+                )?
+                .finish();
             Ok(())
         }
     }
-    pub struct ContractResponseArgs {
-        pub contract_response_type: ContractResponseType,
-        pub contract_response: Option<::flatbuffers::WIPOffset<::flatbuffers::UnionWIPOffset>>,
+    pub struct QueryResponseArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
+        pub result: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
     }
-    impl<'a> Default for ContractResponseArgs {
+    impl<'a> Default for QueryResponseArgs<'a> {
         #[inline]
         fn default() -> Self {
-            ContractResponseArgs {
-                contract_response_type: ContractResponseType::NONE,
-                contract_response: None, // required field
+            QueryResponseArgs {
+                key: None,    // required field
+                result: None, // required field
             }
         }
     }
 
-    pub struct ContractResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct QueryResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ContractResponseBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> QueryResponseBuilder<'a, 'b, A> {
         #[inline]
-        pub fn add_contract_response_type(&mut self, contract_response_type: ContractResponseType) {
-            self.fbb_.push_slot::<ContractResponseType>(
-                ContractResponse::VT_CONTRACT_RESPONSE_TYPE,
-                contract_response_type,
-                ContractResponseType::NONE,
-            );
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<super::common::ContractKey<'b>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<super::common::ContractKey>>(
+                    QueryResponse::VT_KEY,
+                    key,
+                );
         }
         #[inline]
-        pub fn add_contract_response(
+        pub fn add_result(
             &mut self,
-            contract_response: ::flatbuffers::WIPOffset<::flatbuffers::UnionWIPOffset>,
+            result: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
         ) {
-            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                ContractResponse::VT_CONTRACT_RESPONSE,
-                contract_response,
-            );
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(QueryResponse::VT_RESULT, result);
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> ContractResponseBuilder<'a, 'b, A> {
+        ) -> QueryResponseBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            ContractResponseBuilder {
+            QueryResponseBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<ContractResponse<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<QueryResponse<'a>> {
             let o = self.fbb_.end_table(self.start_);
-            self.fbb_.required(
-                o,
-                ContractResponse::VT_CONTRACT_RESPONSE,
-                "contract_response",
-            );
+            self.fbb_.required(o, QueryResponse::VT_KEY, "key");
+            self.fbb_.required(o, QueryResponse::VT_RESULT, "result");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for ContractResponse<'_> {
+    impl ::core::fmt::Debug for QueryResponse<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("ContractResponse");
-            ds.field("contract_response_type", &self.contract_response_type());
-            match self.contract_response_type() {
-                ContractResponseType::GetResponse => {
-                    if let Some(x) = self.contract_response_as_get_response() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                ContractResponseType::PutResponse => {
-                    if let Some(x) = self.contract_response_as_put_response() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                ContractResponseType::UpdateNotification => {
-                    if let Some(x) = self.contract_response_as_update_notification() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                ContractResponseType::UpdateResponse => {
-                    if let Some(x) = self.contract_response_as_update_response() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                ContractResponseType::NotFound => {
-                    if let Some(x) = self.contract_response_as_not_found() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                ContractResponseType::SubscribeResponse => {
-                    if let Some(x) = self.contract_response_as_subscribe_response() {
-                        ds.field("contract_response", &x)
-                    } else {
-                        ds.field(
-                            "contract_response",
-                            &"InvalidFlatbuffer: Union discriminant does not match value.",
-                        )
-                    }
-                }
-                _ => {
-                    let x: Option<()> = None;
-                    ds.field("contract_response", &x)
-                }
-            };
+            let mut ds = f.debug_struct("QueryResponse");
+            ds.field("key", &self.key());
+            ds.field("result", &self.result());
             ds.finish()
         }
     }
-    pub enum DelegateKeyOffset {}
+    pub enum UpdatePreconditionFailedOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct DelegateKey<'a> {
+    pub struct UpdatePreconditionFailed<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for DelegateKey<'a> {
-        type Inner = DelegateKey<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for UpdatePreconditionFailed<'a> {
+        type Inner = UpdatePreconditionFailed<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -1583,13 +1455,14 @@ pub mod host_response {
         }
     }
 
-    impl<'a> DelegateKey<'a> {
+    impl<'a> UpdatePreconditionFailed<'a> {
         pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
-        pub const VT_CODE_HASH: ::flatbuffers::VOffsetT = 6;
+        pub const VT_CURRENT_SUMMARY: ::flatbuffers::VOffsetT = 6;
+        pub const VT_CURRENT_STATE_HASH: ::flatbuffers::VOffsetT = 8;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            DelegateKey { _tab: table }
+            UpdatePreconditionFailed { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -1599,11 +1472,14 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args DelegateKeyArgs<'args>,
-        ) -> ::flatbuffers::WIPOffset<DelegateKey<'bldr>> {
-            let mut builder = DelegateKeyBuilder::new(_fbb);
-            if let Some(x) = args.code_hash {
-                builder.add_code_hash(x);
+            args: &'args UpdatePreconditionFailedArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<UpdatePreconditionFailed<'bldr>> {
+            let mut builder = UpdatePreconditionFailedBuilder::new(_fbb);
+            if let Some(x) = args.current_state_hash {
+                builder.add_current_state_hash(x);
+            }
+            if let Some(x) = args.current_summary {
+                builder.add_current_summary(x);
             }
             if let Some(x) = args.key {
                 builder.add_key(x);
@@ -1612,7 +1488,990 @@ pub mod host_response {
         }
 
         #[inline]
-        pub fn key(&self) -> ::flatbuffers::Vector<'a, u8> {
+        pub fn key(&self) -> super::common::ContractKey<'a> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                        UpdatePreconditionFailed::VT_KEY,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn current_summary(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        UpdatePreconditionFailed::VT_CURRENT_SUMMARY,
+                        None,
+                    )
+            }
+        }
+        #[inline]
+        pub fn current_state_hash(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        UpdatePreconditionFailed::VT_CURRENT_STATE_HASH,
+                        None,
+                    )
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for UpdatePreconditionFailed<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "current_summary",
+                    Self::VT_CURRENT_SUMMARY,
+                    false,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "current_state_hash",
+                    Self::VT_CURRENT_STATE_HASH,
+                    false,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct UpdatePreconditionFailedArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
+        pub current_summary: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub current_state_hash: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for UpdatePreconditionFailedArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            UpdatePreconditionFailedArgs {
+                key: None, // required field
+                current_summary: None,
+                current_state_hash: None,
+            }
+        }
+    }
+
+    pub struct UpdatePreconditionFailedBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> UpdatePreconditionFailedBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<super::common::ContractKey<'b>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<super::common::ContractKey>>(
+                    UpdatePreconditionFailed::VT_KEY,
+                    key,
+                );
+        }
+        #[inline]
+        pub fn add_current_summary(
+            &mut self,
+            current_summary: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                UpdatePreconditionFailed::VT_CURRENT_SUMMARY,
+                current_summary,
+            );
+        }
+        #[inline]
+        pub fn add_current_state_hash(
+            &mut self,
+            current_state_hash: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                UpdatePreconditionFailed::VT_CURRENT_STATE_HASH,
+                current_state_hash,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> UpdatePreconditionFailedBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            UpdatePreconditionFailedBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<UpdatePreconditionFailed<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_
+                .required(o, UpdatePreconditionFailed::VT_KEY, "key");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for UpdatePreconditionFailed<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("UpdatePreconditionFailed");
+            ds.field("key", &self.key());
+            ds.field("current_summary", &self.current_summary());
+            ds.field("current_state_hash", &self.current_state_hash());
+            ds.finish()
+        }
+    }
+    pub enum GetMetadataResponseOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct GetMetadataResponse<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for GetMetadataResponse<'a> {
+        type Inner = GetMetadataResponse<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> GetMetadataResponse<'a> {
+        pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
+        pub const VT_STATE_SIZE: ::flatbuffers::VOffsetT = 6;
+        pub const VT_SUMMARY: ::flatbuffers::VOffsetT = 8;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            GetMetadataResponse { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args GetMetadataResponseArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<GetMetadataResponse<'bldr>> {
+            let mut builder = GetMetadataResponseBuilder::new(_fbb);
+            builder.add_state_size(args.state_size);
+            if let Some(x) = args.summary {
+                builder.add_summary(x);
+            }
+            if let Some(x) = args.key {
+                builder.add_key(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn key(&self) -> super::common::ContractKey<'a> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                        GetMetadataResponse::VT_KEY,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn state_size(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<u64>(GetMetadataResponse::VT_STATE_SIZE, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn summary(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        GetMetadataResponse::VT_SUMMARY,
+                        None,
+                    )
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for GetMetadataResponse<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<u64>("state_size", Self::VT_STATE_SIZE, false)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "summary",
+                    Self::VT_SUMMARY,
+                    false,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct GetMetadataResponseArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
+        pub state_size: u64,
+        pub summary: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for GetMetadataResponseArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            GetMetadataResponseArgs {
+                key: None, // required field
+                state_size: 0,
+                summary: None,
+            }
+        }
+    }
+
+    pub struct GetMetadataResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> GetMetadataResponseBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<super::common::ContractKey<'b>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<super::common::ContractKey>>(
+                    GetMetadataResponse::VT_KEY,
+                    key,
+                );
+        }
+        #[inline]
+        pub fn add_state_size(&mut self, state_size: u64) {
+            self.fbb_
+                .push_slot::<u64>(GetMetadataResponse::VT_STATE_SIZE, state_size, 0);
+        }
+        #[inline]
+        pub fn add_summary(
+            &mut self,
+            summary: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                GetMetadataResponse::VT_SUMMARY,
+                summary,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> GetMetadataResponseBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            GetMetadataResponseBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<GetMetadataResponse<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, GetMetadataResponse::VT_KEY, "key");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for GetMetadataResponse<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("GetMetadataResponse");
+            ds.field("key", &self.key());
+            ds.field("state_size", &self.state_size());
+            ds.field("summary", &self.summary());
+            ds.finish()
+        }
+    }
+    pub enum GetRangeResponseOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct GetRangeResponse<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for GetRangeResponse<'a> {
+        type Inner = GetRangeResponse<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> GetRangeResponse<'a> {
+        pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
+        pub const VT_OFFSET: ::flatbuffers::VOffsetT = 6;
+        pub const VT_TOTAL_SIZE: ::flatbuffers::VOffsetT = 8;
+        pub const VT_DATA: ::flatbuffers::VOffsetT = 10;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            GetRangeResponse { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args GetRangeResponseArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<GetRangeResponse<'bldr>> {
+            let mut builder = GetRangeResponseBuilder::new(_fbb);
+            builder.add_total_size(args.total_size);
+            builder.add_offset(args.offset);
+            if let Some(x) = args.data {
+                builder.add_data(x);
+            }
+            if let Some(x) = args.key {
+                builder.add_key(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn key(&self) -> super::common::ContractKey<'a> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                        GetRangeResponse::VT_KEY,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn offset(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<u64>(GetRangeResponse::VT_OFFSET, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn total_size(&self) -> u64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<u64>(GetRangeResponse::VT_TOTAL_SIZE, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn data(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        GetRangeResponse::VT_DATA,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for GetRangeResponse<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<super::common::ContractKey>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<u64>("offset", Self::VT_OFFSET, false)?
+                .visit_field::<u64>("total_size", Self::VT_TOTAL_SIZE, false)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "data",
+                    Self::VT_DATA,
+                    true,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct GetRangeResponseArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<super::common::ContractKey<'a>>>,
+        pub offset: u64,
+        pub total_size: u64,
+        pub data: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for GetRangeResponseArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            GetRangeResponseArgs {
+                key: None, // required field
+                offset: 0,
+                total_size: 0,
+                data: None, // required field
+            }
+        }
+    }
+
+    pub struct GetRangeResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> GetRangeResponseBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<super::common::ContractKey<'b>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<super::common::ContractKey>>(
+                    GetRangeResponse::VT_KEY,
+                    key,
+                );
+        }
+        #[inline]
+        pub fn add_offset(&mut self, offset: u64) {
+            self.fbb_
+                .push_slot::<u64>(GetRangeResponse::VT_OFFSET, offset, 0);
+        }
+        #[inline]
+        pub fn add_total_size(&mut self, total_size: u64) {
+            self.fbb_
+                .push_slot::<u64>(GetRangeResponse::VT_TOTAL_SIZE, total_size, 0);
+        }
+        #[inline]
+        pub fn add_data(&mut self, data: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(GetRangeResponse::VT_DATA, data);
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> GetRangeResponseBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            GetRangeResponseBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<GetRangeResponse<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, GetRangeResponse::VT_KEY, "key");
+            self.fbb_.required(o, GetRangeResponse::VT_DATA, "data");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for GetRangeResponse<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("GetRangeResponse");
+            ds.field("key", &self.key());
+            ds.field("offset", &self.offset());
+            ds.field("total_size", &self.total_size());
+            ds.field("data", &self.data());
+            ds.finish()
+        }
+    }
+    pub enum ContractResponseOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct ContractResponse<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for ContractResponse<'a> {
+        type Inner = ContractResponse<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> ContractResponse<'a> {
+        pub const VT_CONTRACT_RESPONSE_TYPE: ::flatbuffers::VOffsetT = 4;
+        pub const VT_CONTRACT_RESPONSE: ::flatbuffers::VOffsetT = 6;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            ContractResponse { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args ContractResponseArgs,
+        ) -> ::flatbuffers::WIPOffset<ContractResponse<'bldr>> {
+            let mut builder = ContractResponseBuilder::new(_fbb);
+            if let Some(x) = args.contract_response {
+                builder.add_contract_response(x);
+            }
+            builder.add_contract_response_type(args.contract_response_type);
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn contract_response_type(&self) -> ContractResponseType {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<ContractResponseType>(
+                        ContractResponse::VT_CONTRACT_RESPONSE_TYPE,
+                        Some(ContractResponseType::NONE),
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn contract_response(&self) -> ::flatbuffers::Table<'a> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Table<'a>>>(
+                        ContractResponse::VT_CONTRACT_RESPONSE,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_get_response(&self) -> Option<GetResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::GetResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { GetResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_put_response(&self) -> Option<PutResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::PutResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { PutResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_update_notification(&self) -> Option<UpdateNotification<'a>> {
+            if self.contract_response_type() == ContractResponseType::UpdateNotification {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { UpdateNotification::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_update_response(&self) -> Option<UpdateResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::UpdateResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { UpdateResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_not_found(&self) -> Option<NotFound<'a>> {
+            if self.contract_response_type() == ContractResponseType::NotFound {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { NotFound::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_subscribe_response(&self) -> Option<SubscribeResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::SubscribeResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { SubscribeResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_query_response(&self) -> Option<QueryResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::QueryResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { QueryResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_update_precondition_failed(
+            &self,
+        ) -> Option<UpdatePreconditionFailed<'a>> {
+            if self.contract_response_type() == ContractResponseType::UpdatePreconditionFailed {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { UpdatePreconditionFailed::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_get_metadata_response(
+            &self,
+        ) -> Option<GetMetadataResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::GetMetadataResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { GetMetadataResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn contract_response_as_get_range_response(&self) -> Option<GetRangeResponse<'a>> {
+            if self.contract_response_type() == ContractResponseType::GetRangeResponse {
+                let u = self.contract_response();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { GetRangeResponse::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for ContractResponse<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+     .visit_union::<ContractResponseType, _>("contract_response_type", Self::VT_CONTRACT_RESPONSE_TYPE, "contract_response", Self::VT_CONTRACT_RESPONSE, true, |key, v, pos| {
+        match key {
+          ContractResponseType::GetResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<GetResponse>>("ContractResponseType::GetResponse", pos),
+          ContractResponseType::PutResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<PutResponse>>("ContractResponseType::PutResponse", pos),
+          ContractResponseType::UpdateNotification => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UpdateNotification>>("ContractResponseType::UpdateNotification", pos),
+          ContractResponseType::UpdateResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UpdateResponse>>("ContractResponseType::UpdateResponse", pos),
+          ContractResponseType::NotFound => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<NotFound>>("ContractResponseType::NotFound", pos),
+          ContractResponseType::SubscribeResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<SubscribeResponse>>("ContractResponseType::SubscribeResponse", pos),
+          ContractResponseType::QueryResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<QueryResponse>>("ContractResponseType::QueryResponse", pos),
+          ContractResponseType::UpdatePreconditionFailed => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<UpdatePreconditionFailed>>("ContractResponseType::UpdatePreconditionFailed", pos),
+          ContractResponseType::GetMetadataResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<GetMetadataResponse>>("ContractResponseType::GetMetadataResponse", pos),
+          ContractResponseType::GetRangeResponse => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<GetRangeResponse>>("ContractResponseType::GetRangeResponse", pos),
+          _ => Ok(()),
+        }
+     })?
+     .finish();
+            Ok(())
+        }
+    }
+    pub struct ContractResponseArgs {
+        pub contract_response_type: ContractResponseType,
+        pub contract_response: Option<::flatbuffers::WIPOffset<::flatbuffers::UnionWIPOffset>>,
+    }
+    impl<'a> Default for ContractResponseArgs {
+        #[inline]
+        fn default() -> Self {
+            ContractResponseArgs {
+                contract_response_type: ContractResponseType::NONE,
+                contract_response: None, // required field
+            }
+        }
+    }
+
+    pub struct ContractResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ContractResponseBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_contract_response_type(&mut self, contract_response_type: ContractResponseType) {
+            self.fbb_.push_slot::<ContractResponseType>(
+                ContractResponse::VT_CONTRACT_RESPONSE_TYPE,
+                contract_response_type,
+                ContractResponseType::NONE,
+            );
+        }
+        #[inline]
+        pub fn add_contract_response(
+            &mut self,
+            contract_response: ::flatbuffers::WIPOffset<::flatbuffers::UnionWIPOffset>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                ContractResponse::VT_CONTRACT_RESPONSE,
+                contract_response,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> ContractResponseBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            ContractResponseBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<ContractResponse<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(
+                o,
+                ContractResponse::VT_CONTRACT_RESPONSE,
+                "contract_response",
+            );
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for ContractResponse<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("ContractResponse");
+            ds.field("contract_response_type", &self.contract_response_type());
+            match self.contract_response_type() {
+                ContractResponseType::GetResponse => {
+                    if let Some(x) = self.contract_response_as_get_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::PutResponse => {
+                    if let Some(x) = self.contract_response_as_put_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::UpdateNotification => {
+                    if let Some(x) = self.contract_response_as_update_notification() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::UpdateResponse => {
+                    if let Some(x) = self.contract_response_as_update_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::NotFound => {
+                    if let Some(x) = self.contract_response_as_not_found() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::SubscribeResponse => {
+                    if let Some(x) = self.contract_response_as_subscribe_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::QueryResponse => {
+                    if let Some(x) = self.contract_response_as_query_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::UpdatePreconditionFailed => {
+                    if let Some(x) = self.contract_response_as_update_precondition_failed() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::GetMetadataResponse => {
+                    if let Some(x) = self.contract_response_as_get_metadata_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                ContractResponseType::GetRangeResponse => {
+                    if let Some(x) = self.contract_response_as_get_range_response() {
+                        ds.field("contract_response", &x)
+                    } else {
+                        ds.field(
+                            "contract_response",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                _ => {
+                    let x: Option<()> = None;
+                    ds.field("contract_response", &x)
+                }
+            };
+            ds.finish()
+        }
+    }
+    pub enum DelegateKeyOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct DelegateKey<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for DelegateKey<'a> {
+        type Inner = DelegateKey<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> DelegateKey<'a> {
+        pub const VT_KEY: ::flatbuffers::VOffsetT = 4;
+        pub const VT_CODE_HASH: ::flatbuffers::VOffsetT = 6;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            DelegateKey { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args DelegateKeyArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<DelegateKey<'bldr>> {
+            let mut builder = DelegateKeyBuilder::new(_fbb);
+            if let Some(x) = args.code_hash {
+                builder.add_code_hash(x);
+            }
+            if let Some(x) = args.key {
+                builder.add_key(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn key(&self) -> ::flatbuffers::Vector<'a, u8> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
@@ -1626,22 +2485,352 @@ pub mod host_response {
             }
         }
         #[inline]
-        pub fn code_hash(&self) -> ::flatbuffers::Vector<'a, u8> {
+        pub fn code_hash(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        DelegateKey::VT_CODE_HASH,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for DelegateKey<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "key",
+                    Self::VT_KEY,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "code_hash",
+                    Self::VT_CODE_HASH,
+                    true,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct DelegateKeyArgs<'a> {
+        pub key: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub code_hash: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    }
+    impl<'a> Default for DelegateKeyArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            DelegateKeyArgs {
+                key: None,       // required field
+                code_hash: None, // required field
+            }
+        }
+    }
+
+    pub struct DelegateKeyBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> DelegateKeyBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(DelegateKey::VT_KEY, key);
+        }
+        #[inline]
+        pub fn add_code_hash(
+            &mut self,
+            code_hash: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                DelegateKey::VT_CODE_HASH,
+                code_hash,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> DelegateKeyBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            DelegateKeyBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<DelegateKey<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, DelegateKey::VT_KEY, "key");
+            self.fbb_
+                .required(o, DelegateKey::VT_CODE_HASH, "code_hash");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for DelegateKey<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("DelegateKey");
+            ds.field("key", &self.key());
+            ds.field("code_hash", &self.code_hash());
+            ds.finish()
+        }
+    }
+    pub enum UserInputRequestOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct UserInputRequest<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for UserInputRequest<'a> {
+        type Inner = UserInputRequest<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> UserInputRequest<'a> {
+        pub const VT_REQUEST_ID: ::flatbuffers::VOffsetT = 4;
+        pub const VT_MESSAGE: ::flatbuffers::VOffsetT = 6;
+        pub const VT_RESPONSES: ::flatbuffers::VOffsetT = 8;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            UserInputRequest { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args UserInputRequestArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<UserInputRequest<'bldr>> {
+            let mut builder = UserInputRequestBuilder::new(_fbb);
+            if let Some(x) = args.responses {
+                builder.add_responses(x);
+            }
+            if let Some(x) = args.message {
+                builder.add_message(x);
+            }
+            builder.add_request_id(args.request_id);
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn request_id(&self) -> u32 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<u32>(UserInputRequest::VT_REQUEST_ID, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn message(&self) -> ::flatbuffers::Vector<'a, u8> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        UserInputRequest::VT_MESSAGE,
+                        None,
+                    )
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn responses(
+            &self,
+        ) -> ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse<'a>>> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<
+                        ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
+                    >>(UserInputRequest::VT_RESPONSES, None)
+                    .unwrap()
+            }
+        }
+    }
+
+    impl ::flatbuffers::Verifiable for UserInputRequest<'_> {
+        #[inline]
+        fn run_verifier(
+            v: &mut ::flatbuffers::Verifier,
+            pos: usize,
+        ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<u32>("request_id", Self::VT_REQUEST_ID, false)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "message",
+                    Self::VT_MESSAGE,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<
+                    ::flatbuffers::Vector<'_, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
+                >>("responses", Self::VT_RESPONSES, true)?
+                .finish();
+            Ok(())
+        }
+    }
+    pub struct UserInputRequestArgs<'a> {
+        pub request_id: u32,
+        pub message: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub responses: Option<
+            ::flatbuffers::WIPOffset<
+                ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse<'a>>>,
+            >,
+        >,
+    }
+    impl<'a> Default for UserInputRequestArgs<'a> {
+        #[inline]
+        fn default() -> Self {
+            UserInputRequestArgs {
+                request_id: 0,
+                message: None,   // required field
+                responses: None, // required field
+            }
+        }
+    }
+
+    pub struct UserInputRequestBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+        fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
+    }
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> UserInputRequestBuilder<'a, 'b, A> {
+        #[inline]
+        pub fn add_request_id(&mut self, request_id: u32) {
+            self.fbb_
+                .push_slot::<u32>(UserInputRequest::VT_REQUEST_ID, request_id, 0);
+        }
+        #[inline]
+        pub fn add_message(
+            &mut self,
+            message: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                UserInputRequest::VT_MESSAGE,
+                message,
+            );
+        }
+        #[inline]
+        pub fn add_responses(
+            &mut self,
+            responses: ::flatbuffers::WIPOffset<
+                ::flatbuffers::Vector<'b, ::flatbuffers::ForwardsUOffset<ClientResponse<'b>>>,
+            >,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                UserInputRequest::VT_RESPONSES,
+                responses,
+            );
+        }
+        #[inline]
+        pub fn new(
+            _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
+        ) -> UserInputRequestBuilder<'a, 'b, A> {
+            let start = _fbb.start_table();
+            UserInputRequestBuilder {
+                fbb_: _fbb,
+                start_: start,
+            }
+        }
+        #[inline]
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<UserInputRequest<'a>> {
+            let o = self.fbb_.end_table(self.start_);
+            self.fbb_
+                .required(o, UserInputRequest::VT_MESSAGE, "message");
+            self.fbb_
+                .required(o, UserInputRequest::VT_RESPONSES, "responses");
+            ::flatbuffers::WIPOffset::new(o.value())
+        }
+    }
+
+    impl ::core::fmt::Debug for UserInputRequest<'_> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            let mut ds = f.debug_struct("UserInputRequest");
+            ds.field("request_id", &self.request_id());
+            ds.field("message", &self.message());
+            ds.field("responses", &self.responses());
+            ds.finish()
+        }
+    }
+    pub enum ClientResponseOffset {}
+    #[derive(Copy, Clone, PartialEq)]
+
+    pub struct ClientResponse<'a> {
+        pub _tab: ::flatbuffers::Table<'a>,
+    }
+
+    impl<'a> ::flatbuffers::Follow<'a> for ClientResponse<'a> {
+        type Inner = ClientResponse<'a>;
+        #[inline]
+        unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+            Self {
+                _tab: unsafe { ::flatbuffers::Table::new(buf, loc) },
+            }
+        }
+    }
+
+    impl<'a> ClientResponse<'a> {
+        pub const VT_DATA: ::flatbuffers::VOffsetT = 4;
+
+        #[inline]
+        pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
+            ClientResponse { _tab: table }
+        }
+        #[allow(unused_mut)]
+        pub fn create<
+            'bldr: 'args,
+            'args: 'mut_bldr,
+            'mut_bldr,
+            A: ::flatbuffers::Allocator + 'bldr,
+        >(
+            _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
+            args: &'args ClientResponseArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<ClientResponse<'bldr>> {
+            let mut builder = ClientResponseBuilder::new(_fbb);
+            if let Some(x) = args.data {
+                builder.add_data(x);
+            }
+            builder.finish()
+        }
+
+        #[inline]
+        pub fn data(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
                     .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
-                        DelegateKey::VT_CODE_HASH,
+                        ClientResponse::VT_DATA,
                         None,
                     )
-                    .unwrap()
             }
         }
     }
 
-    impl ::flatbuffers::Verifiable for DelegateKey<'_> {
+    impl ::flatbuffers::Verifiable for ClientResponse<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
@@ -1649,90 +2838,67 @@ pub mod host_response {
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
                 .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
-                    "key",
-                    Self::VT_KEY,
-                    true,
-                )?
-                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
-                    "code_hash",
-                    Self::VT_CODE_HASH,
-                    true,
+                    "data",
+                    Self::VT_DATA,
+                    false,
                 )?
                 .finish();
             Ok(())
         }
     }
-    pub struct DelegateKeyArgs<'a> {
-        pub key: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
-        pub code_hash: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    pub struct ClientResponseArgs<'a> {
+        pub data: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
     }
-    impl<'a> Default for DelegateKeyArgs<'a> {
+    impl<'a> Default for ClientResponseArgs<'a> {
         #[inline]
         fn default() -> Self {
-            DelegateKeyArgs {
-                key: None,       // required field
-                code_hash: None, // required field
-            }
+            ClientResponseArgs { data: None }
         }
     }
 
-    pub struct DelegateKeyBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct ClientResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> DelegateKeyBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ClientResponseBuilder<'a, 'b, A> {
         #[inline]
-        pub fn add_key(&mut self, key: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>) {
+        pub fn add_data(&mut self, data: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>) {
             self.fbb_
-                .push_slot_always::<::flatbuffers::WIPOffset<_>>(DelegateKey::VT_KEY, key);
-        }
-        #[inline]
-        pub fn add_code_hash(
-            &mut self,
-            code_hash: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
-        ) {
-            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                DelegateKey::VT_CODE_HASH,
-                code_hash,
-            );
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(ClientResponse::VT_DATA, data);
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> DelegateKeyBuilder<'a, 'b, A> {
+        ) -> ClientResponseBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            DelegateKeyBuilder {
+            ClientResponseBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<DelegateKey<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<ClientResponse<'a>> {
             let o = self.fbb_.end_table(self.start_);
-            self.fbb_.required(o, DelegateKey::VT_KEY, "key");
-            self.fbb_
-                .required(o, DelegateKey::VT_CODE_HASH, "code_hash");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for DelegateKey<'_> {
+    impl ::core::fmt::Debug for ClientResponse<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("DelegateKey");
-            ds.field("key", &self.key());
-            ds.field("code_hash", &self.code_hash());
+            let mut ds = f.debug_struct("ClientResponse");
+            ds.field("data", &self.data());
             ds.finish()
         }
     }
-    pub enum UserInputRequestOffset {}
+    pub enum RequestUserInputOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct UserInputRequest<'a> {
+    pub struct RequestUserInput<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for UserInputRequest<'a> {
-        type Inner = UserInputRequest<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for RequestUserInput<'a> {
+        type Inner = RequestUserInput<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -1741,14 +2907,14 @@ pub mod host_response {
         }
     }
 
-    impl<'a> UserInputRequest<'a> {
+    impl<'a> RequestUserInput<'a> {
         pub const VT_REQUEST_ID: ::flatbuffers::VOffsetT = 4;
         pub const VT_MESSAGE: ::flatbuffers::VOffsetT = 6;
         pub const VT_RESPONSES: ::flatbuffers::VOffsetT = 8;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            UserInputRequest { _tab: table }
+            RequestUserInput { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -1758,9 +2924,9 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args UserInputRequestArgs<'args>,
-        ) -> ::flatbuffers::WIPOffset<UserInputRequest<'bldr>> {
-            let mut builder = UserInputRequestBuilder::new(_fbb);
+            args: &'args RequestUserInputArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<RequestUserInput<'bldr>> {
+            let mut builder = RequestUserInputBuilder::new(_fbb);
             if let Some(x) = args.responses {
                 builder.add_responses(x);
             }
@@ -1778,22 +2944,21 @@ pub mod host_response {
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<u32>(UserInputRequest::VT_REQUEST_ID, Some(0))
+                    .get::<u32>(RequestUserInput::VT_REQUEST_ID, Some(0))
                     .unwrap()
             }
         }
         #[inline]
-        pub fn message(&self) -> ::flatbuffers::Vector<'a, u8> {
+        pub fn message(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
                     .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
-                        UserInputRequest::VT_MESSAGE,
+                        RequestUserInput::VT_MESSAGE,
                         None,
                     )
-                    .unwrap()
             }
         }
         #[inline]
@@ -1807,13 +2972,13 @@ pub mod host_response {
                 self._tab
                     .get::<::flatbuffers::ForwardsUOffset<
                         ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
-                    >>(UserInputRequest::VT_RESPONSES, None)
+                    >>(RequestUserInput::VT_RESPONSES, None)
                     .unwrap()
             }
         }
     }
 
-    impl ::flatbuffers::Verifiable for UserInputRequest<'_> {
+    impl ::flatbuffers::Verifiable for RequestUserInput<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
@@ -1824,7 +2989,7 @@ pub mod host_response {
                 .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
                     "message",
                     Self::VT_MESSAGE,
-                    true,
+                    false,
                 )?
                 .visit_field::<::flatbuffers::ForwardsUOffset<
                     ::flatbuffers::Vector<'_, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
@@ -1833,7 +2998,7 @@ pub mod host_response {
             Ok(())
         }
     }
-    pub struct UserInputRequestArgs<'a> {
+    pub struct RequestUserInputArgs<'a> {
         pub request_id: u32,
         pub message: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
         pub responses: Option<
@@ -1842,26 +3007,26 @@ pub mod host_response {
             >,
         >,
     }
-    impl<'a> Default for UserInputRequestArgs<'a> {
+    impl<'a> Default for RequestUserInputArgs<'a> {
         #[inline]
         fn default() -> Self {
-            UserInputRequestArgs {
+            RequestUserInputArgs {
                 request_id: 0,
-                message: None,   // required field
+                message: None,
                 responses: None, // required field
             }
         }
     }
 
-    pub struct UserInputRequestBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct RequestUserInputBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> UserInputRequestBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> RequestUserInputBuilder<'a, 'b, A> {
         #[inline]
         pub fn add_request_id(&mut self, request_id: u32) {
             self.fbb_
-                .push_slot::<u32>(UserInputRequest::VT_REQUEST_ID, request_id, 0);
+                .push_slot::<u32>(RequestUserInput::VT_REQUEST_ID, request_id, 0);
         }
         #[inline]
         pub fn add_message(
@@ -1869,7 +3034,7 @@ pub mod host_response {
             message: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
         ) {
             self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                UserInputRequest::VT_MESSAGE,
+                RequestUserInput::VT_MESSAGE,
                 message,
             );
         }
@@ -1881,49 +3046,47 @@ pub mod host_response {
             >,
         ) {
             self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                UserInputRequest::VT_RESPONSES,
+                RequestUserInput::VT_RESPONSES,
                 responses,
             );
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> UserInputRequestBuilder<'a, 'b, A> {
+        ) -> RequestUserInputBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            UserInputRequestBuilder {
+            RequestUserInputBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<UserInputRequest<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<RequestUserInput<'a>> {
             let o = self.fbb_.end_table(self.start_);
             self.fbb_
-                .required(o, UserInputRequest::VT_MESSAGE, "message");
-            self.fbb_
-                .required(o, UserInputRequest::VT_RESPONSES, "responses");
+                .required(o, RequestUserInput::VT_RESPONSES, "responses");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for UserInputRequest<'_> {
+    impl ::core::fmt::Debug for RequestUserInput<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("UserInputRequest");
+            let mut ds = f.debug_struct("RequestUserInput");
             ds.field("request_id", &self.request_id());
             ds.field("message", &self.message());
             ds.field("responses", &self.responses());
             ds.finish()
         }
     }
-    pub enum ClientResponseOffset {}
+    pub enum ContextUpdatedOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct ClientResponse<'a> {
+    pub struct ContextUpdated<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for ClientResponse<'a> {
-        type Inner = ClientResponse<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for ContextUpdated<'a> {
+        type Inner = ContextUpdated<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -1932,12 +3095,12 @@ pub mod host_response {
         }
     }
 
-    impl<'a> ClientResponse<'a> {
-        pub const VT_DATA: ::flatbuffers::VOffsetT = 4;
+    impl<'a> ContextUpdated<'a> {
+        pub const VT_CONTEXT: ::flatbuffers::VOffsetT = 4;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            ClientResponse { _tab: table }
+            ContextUpdated { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -1947,31 +3110,32 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args ClientResponseArgs<'args>,
-        ) -> ::flatbuffers::WIPOffset<ClientResponse<'bldr>> {
-            let mut builder = ClientResponseBuilder::new(_fbb);
-            if let Some(x) = args.data {
-                builder.add_data(x);
+            args: &'args ContextUpdatedArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<ContextUpdated<'bldr>> {
+            let mut builder = ContextUpdatedBuilder::new(_fbb);
+            if let Some(x) = args.context {
+                builder.add_context(x);
             }
             builder.finish()
         }
 
         #[inline]
-        pub fn data(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+        pub fn context(&self) -> ::flatbuffers::Vector<'a, u8> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
                     .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
-                        ClientResponse::VT_DATA,
+                        ContextUpdated::VT_CONTEXT,
                         None,
                     )
+                    .unwrap()
             }
         }
     }
 
-    impl ::flatbuffers::Verifiable for ClientResponse<'_> {
+    impl ::flatbuffers::Verifiable for ContextUpdated<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
@@ -1979,67 +3143,75 @@ pub mod host_response {
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
                 .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
-                    "data",
-                    Self::VT_DATA,
-                    false,
+                    "context",
+                    Self::VT_CONTEXT,
+                    true,
                 )?
                 .finish();
             Ok(())
         }
     }
-    pub struct ClientResponseArgs<'a> {
-        pub data: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    pub struct ContextUpdatedArgs<'a> {
+        pub context: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
     }
-    impl<'a> Default for ClientResponseArgs<'a> {
+    impl<'a> Default for ContextUpdatedArgs<'a> {
         #[inline]
         fn default() -> Self {
-            ClientResponseArgs { data: None }
+            ContextUpdatedArgs {
+                context: None, // required field
+            }
         }
     }
 
-    pub struct ClientResponseBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct ContextUpdatedBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ClientResponseBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ContextUpdatedBuilder<'a, 'b, A> {
         #[inline]
-        pub fn add_data(&mut self, data: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>) {
-            self.fbb_
-                .push_slot_always::<::flatbuffers::WIPOffset<_>>(ClientResponse::VT_DATA, data);
+        pub fn add_context(
+            &mut self,
+            context: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+        ) {
+            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
+                ContextUpdated::VT_CONTEXT,
+                context,
+            );
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> ClientResponseBuilder<'a, 'b, A> {
+        ) -> ContextUpdatedBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            ClientResponseBuilder {
+            ContextUpdatedBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<ClientResponse<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<ContextUpdated<'a>> {
             let o = self.fbb_.end_table(self.start_);
+            self.fbb_.required(o, ContextUpdated::VT_CONTEXT, "context");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for ClientResponse<'_> {
+    impl ::core::fmt::Debug for ContextUpdated<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("ClientResponse");
-            ds.field("data", &self.data());
+            let mut ds = f.debug_struct("ContextUpdated");
+            ds.field("context", &self.context());
             ds.finish()
         }
     }
-    pub enum RequestUserInputOffset {}
+    pub enum ScheduleTimerOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct RequestUserInput<'a> {
+    pub struct ScheduleTimer<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for RequestUserInput<'a> {
-        type Inner = RequestUserInput<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for ScheduleTimer<'a> {
+        type Inner = ScheduleTimer<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -2048,14 +3220,16 @@ pub mod host_response {
         }
     }
 
-    impl<'a> RequestUserInput<'a> {
-        pub const VT_REQUEST_ID: ::flatbuffers::VOffsetT = 4;
-        pub const VT_MESSAGE: ::flatbuffers::VOffsetT = 6;
-        pub const VT_RESPONSES: ::flatbuffers::VOffsetT = 8;
+    impl<'a> ScheduleTimer<'a> {
+        pub const VT_TIMER_ID: ::flatbuffers::VOffsetT = 4;
+        pub const VT_DEADLINE_MS: ::flatbuffers::VOffsetT = 6;
+        pub const VT_ABSOLUTE: ::flatbuffers::VOffsetT = 8;
+        pub const VT_PAYLOAD: ::flatbuffers::VOffsetT = 10;
+        pub const VT_CONTEXT: ::flatbuffers::VOffsetT = 12;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            RequestUserInput { _tab: table }
+            ScheduleTimer { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -2065,169 +3239,207 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args RequestUserInputArgs<'args>,
-        ) -> ::flatbuffers::WIPOffset<RequestUserInput<'bldr>> {
-            let mut builder = RequestUserInputBuilder::new(_fbb);
-            if let Some(x) = args.responses {
-                builder.add_responses(x);
+            args: &'args ScheduleTimerArgs<'args>,
+        ) -> ::flatbuffers::WIPOffset<ScheduleTimer<'bldr>> {
+            let mut builder = ScheduleTimerBuilder::new(_fbb);
+            builder.add_deadline_ms(args.deadline_ms);
+            builder.add_timer_id(args.timer_id);
+            if let Some(x) = args.context {
+                builder.add_context(x);
             }
-            if let Some(x) = args.message {
-                builder.add_message(x);
+            if let Some(x) = args.payload {
+                builder.add_payload(x);
             }
-            builder.add_request_id(args.request_id);
+            builder.add_absolute(args.absolute);
             builder.finish()
         }
 
         #[inline]
-        pub fn request_id(&self) -> u32 {
+        pub fn timer_id(&self) -> u64 {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<u32>(RequestUserInput::VT_REQUEST_ID, Some(0))
+                    .get::<u64>(ScheduleTimer::VT_TIMER_ID, Some(0))
                     .unwrap()
             }
         }
         #[inline]
-        pub fn message(&self) -> Option<::flatbuffers::Vector<'a, u8>> {
+        pub fn deadline_ms(&self) -> i64 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<i64>(ScheduleTimer::VT_DEADLINE_MS, Some(0))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn absolute(&self) -> bool {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<bool>(ScheduleTimer::VT_ABSOLUTE, Some(false))
+                    .unwrap()
+            }
+        }
+        #[inline]
+        pub fn payload(&self) -> ::flatbuffers::Vector<'a, u8> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
                     .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
-                        RequestUserInput::VT_MESSAGE,
+                        ScheduleTimer::VT_PAYLOAD,
                         None,
                     )
+                    .unwrap()
             }
         }
         #[inline]
-        pub fn responses(
-            &self,
-        ) -> ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse<'a>>> {
+        pub fn context(&self) -> ::flatbuffers::Vector<'a, u8> {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<::flatbuffers::ForwardsUOffset<
-                        ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
-                    >>(RequestUserInput::VT_RESPONSES, None)
+                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
+                        ScheduleTimer::VT_CONTEXT,
+                        None,
+                    )
                     .unwrap()
             }
         }
     }
 
-    impl ::flatbuffers::Verifiable for RequestUserInput<'_> {
+    impl ::flatbuffers::Verifiable for ScheduleTimer<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
             pos: usize,
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
-                .visit_field::<u32>("request_id", Self::VT_REQUEST_ID, false)?
+                .visit_field::<u64>("timer_id", Self::VT_TIMER_ID, false)?
+                .visit_field::<i64>("deadline_ms", Self::VT_DEADLINE_MS, false)?
+                .visit_field::<bool>("absolute", Self::VT_ABSOLUTE, false)?
                 .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
-                    "message",
-                    Self::VT_MESSAGE,
-                    false,
+                    "payload",
+                    Self::VT_PAYLOAD,
+                    true,
+                )?
+                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
+                    "context",
+                    Self::VT_CONTEXT,
+                    true,
                 )?
-                .visit_field::<::flatbuffers::ForwardsUOffset<
-                    ::flatbuffers::Vector<'_, ::flatbuffers::ForwardsUOffset<ClientResponse>>,
-                >>("responses", Self::VT_RESPONSES, true)?
                 .finish();
             Ok(())
         }
     }
-    pub struct RequestUserInputArgs<'a> {
-        pub request_id: u32,
-        pub message: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
-        pub responses: Option<
-            ::flatbuffers::WIPOffset<
-                ::flatbuffers::Vector<'a, ::flatbuffers::ForwardsUOffset<ClientResponse<'a>>>,
-            >,
-        >,
+    pub struct ScheduleTimerArgs<'a> {
+        pub timer_id: u64,
+        pub deadline_ms: i64,
+        pub absolute: bool,
+        pub payload: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+        pub context: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
     }
-    impl<'a> Default for RequestUserInputArgs<'a> {
+    impl<'a> Default for ScheduleTimerArgs<'a> {
         #[inline]
         fn default() -> Self {
-            RequestUserInputArgs {
-                request_id: 0,
-                message: None,
-                responses: None, // required field
+            ScheduleTimerArgs {
+                timer_id: 0,
+                deadline_ms: 0,
+                absolute: false,
+                payload: None, // required field
+                context: None, // required field
             }
         }
     }
 
-    pub struct RequestUserInputBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct ScheduleTimerBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> RequestUserInputBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ScheduleTimerBuilder<'a, 'b, A> {
         #[inline]
-        pub fn add_request_id(&mut self, request_id: u32) {
+        pub fn add_timer_id(&mut self, timer_id: u64) {
             self.fbb_
-                .push_slot::<u32>(RequestUserInput::VT_REQUEST_ID, request_id, 0);
+                .push_slot::<u64>(ScheduleTimer::VT_TIMER_ID, timer_id, 0);
         }
         #[inline]
-        pub fn add_message(
+        pub fn add_deadline_ms(&mut self, deadline_ms: i64) {
+            self.fbb_
+                .push_slot::<i64>(ScheduleTimer::VT_DEADLINE_MS, deadline_ms, 0);
+        }
+        #[inline]
+        pub fn add_absolute(&mut self, absolute: bool) {
+            self.fbb_
+                .push_slot::<bool>(ScheduleTimer::VT_ABSOLUTE, absolute, false);
+        }
+        #[inline]
+        pub fn add_payload(
             &mut self,
-            message: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
+            payload: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
         ) {
             self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                RequestUserInput::VT_MESSAGE,
-                message,
+                ScheduleTimer::VT_PAYLOAD,
+                payload,
             );
         }
         #[inline]
-        pub fn add_responses(
+        pub fn add_context(
             &mut self,
-            responses: ::flatbuffers::WIPOffset<
-                ::flatbuffers::Vector<'b, ::flatbuffers::ForwardsUOffset<ClientResponse<'b>>>,
-            >,
+            context: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
         ) {
             self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                RequestUserInput::VT_RESPONSES,
-                responses,
+                ScheduleTimer::VT_CONTEXT,
+                context,
             );
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> RequestUserInputBuilder<'a, 'b, A> {
+        ) -> ScheduleTimerBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            RequestUserInputBuilder {
+            ScheduleTimerBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<RequestUserInput<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<ScheduleTimer<'a>> {
             let o = self.fbb_.end_table(self.start_);
-            self.fbb_
-                .required(o, RequestUserInput::VT_RESPONSES, "responses");
+            self.fbb_.required(o, ScheduleTimer::VT_PAYLOAD, "payload");
+            self.fbb_.required(o, ScheduleTimer::VT_CONTEXT, "context");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for RequestUserInput<'_> {
+    impl ::core::fmt::Debug for ScheduleTimer<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("RequestUserInput");
-            ds.field("request_id", &self.request_id());
-            ds.field("message", &self.message());
-            ds.field("responses", &self.responses());
+            let mut ds = f.debug_struct("ScheduleTimer");
+            ds.field("timer_id", &self.timer_id());
+            ds.field("deadline_ms", &self.deadline_ms());
+            ds.field("absolute", &self.absolute());
+            ds.field("payload", &self.payload());
+            ds.field("context", &self.context());
             ds.finish()
         }
     }
-    pub enum ContextUpdatedOffset {}
+    pub enum CancelTimerOffset {}
     #[derive(Copy, Clone, PartialEq)]
 
-    pub struct ContextUpdated<'a> {
+    pub struct CancelTimer<'a> {
         pub _tab: ::flatbuffers::Table<'a>,
     }
 
-    impl<'a> ::flatbuffers::Follow<'a> for ContextUpdated<'a> {
-        type Inner = ContextUpdated<'a>;
+    impl<'a> ::flatbuffers::Follow<'a> for CancelTimer<'a> {
+        type Inner = CancelTimer<'a>;
         #[inline]
         unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
             Self {
@@ -2236,12 +3448,12 @@ pub mod host_response {
         }
     }
 
-    impl<'a> ContextUpdated<'a> {
-        pub const VT_CONTEXT: ::flatbuffers::VOffsetT = 4;
+    impl<'a> CancelTimer<'a> {
+        pub const VT_TIMER_ID: ::flatbuffers::VOffsetT = 4;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
-            ContextUpdated { _tab: table }
+            CancelTimer { _tab: table }
         }
         #[allow(unused_mut)]
         pub fn create<
@@ -2251,96 +3463,79 @@ pub mod host_response {
             A: ::flatbuffers::Allocator + 'bldr,
         >(
             _fbb: &'mut_bldr mut ::flatbuffers::FlatBufferBuilder<'bldr, A>,
-            args: &'args ContextUpdatedArgs<'args>,
-        ) -> ::flatbuffers::WIPOffset<ContextUpdated<'bldr>> {
-            let mut builder = ContextUpdatedBuilder::new(_fbb);
-            if let Some(x) = args.context {
-                builder.add_context(x);
-            }
+            args: &'args CancelTimerArgs,
+        ) -> ::flatbuffers::WIPOffset<CancelTimer<'bldr>> {
+            let mut builder = CancelTimerBuilder::new(_fbb);
+            builder.add_timer_id(args.timer_id);
             builder.finish()
         }
 
         #[inline]
-        pub fn context(&self) -> ::flatbuffers::Vector<'a, u8> {
+        pub fn timer_id(&self) -> u64 {
             // Safety:
             // Created from valid Table for this object
             // which contains a valid value in this slot
             unsafe {
                 self._tab
-                    .get::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'a, u8>>>(
-                        ContextUpdated::VT_CONTEXT,
-                        None,
-                    )
+                    .get::<u64>(CancelTimer::VT_TIMER_ID, Some(0))
                     .unwrap()
             }
         }
     }
 
-    impl ::flatbuffers::Verifiable for ContextUpdated<'_> {
+    impl ::flatbuffers::Verifiable for CancelTimer<'_> {
         #[inline]
         fn run_verifier(
             v: &mut ::flatbuffers::Verifier,
             pos: usize,
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
-                .visit_field::<::flatbuffers::ForwardsUOffset<::flatbuffers::Vector<'_, u8>>>(
-                    "context",
-                    Self::VT_CONTEXT,
-                    true,
-                )?
+                .visit_field::<u64>("timer_id", Self::VT_TIMER_ID, false)?
                 .finish();
             Ok(())
         }
     }
-    pub struct ContextUpdatedArgs<'a> {
-        pub context: Option<::flatbuffers::WIPOffset<::flatbuffers::Vector<'a, u8>>>,
+    pub struct CancelTimerArgs {
+        pub timer_id: u64,
     }
-    impl<'a> Default for ContextUpdatedArgs<'a> {
+    impl<'a> Default for CancelTimerArgs {
         #[inline]
         fn default() -> Self {
-            ContextUpdatedArgs {
-                context: None, // required field
-            }
+            CancelTimerArgs { timer_id: 0 }
         }
     }
 
-    pub struct ContextUpdatedBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
+    pub struct CancelTimerBuilder<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> {
         fbb_: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         start_: ::flatbuffers::WIPOffset<::flatbuffers::TableUnfinishedWIPOffset>,
     }
-    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> ContextUpdatedBuilder<'a, 'b, A> {
+    impl<'a: 'b, 'b, A: ::flatbuffers::Allocator + 'a> CancelTimerBuilder<'a, 'b, A> {
         #[inline]
-        pub fn add_context(
-            &mut self,
-            context: ::flatbuffers::WIPOffset<::flatbuffers::Vector<'b, u8>>,
-        ) {
-            self.fbb_.push_slot_always::<::flatbuffers::WIPOffset<_>>(
-                ContextUpdated::VT_CONTEXT,
-                context,
-            );
+        pub fn add_timer_id(&mut self, timer_id: u64) {
+            self.fbb_
+                .push_slot::<u64>(CancelTimer::VT_TIMER_ID, timer_id, 0);
         }
         #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
-        ) -> ContextUpdatedBuilder<'a, 'b, A> {
+        ) -> CancelTimerBuilder<'a, 'b, A> {
             let start = _fbb.start_table();
-            ContextUpdatedBuilder {
+            CancelTimerBuilder {
                 fbb_: _fbb,
                 start_: start,
             }
         }
         #[inline]
-        pub fn finish(self) -> ::flatbuffers::WIPOffset<ContextUpdated<'a>> {
+        pub fn finish(self) -> ::flatbuffers::WIPOffset<CancelTimer<'a>> {
             let o = self.fbb_.end_table(self.start_);
-            self.fbb_.required(o, ContextUpdated::VT_CONTEXT, "context");
             ::flatbuffers::WIPOffset::new(o.value())
         }
     }
 
-    impl ::core::fmt::Debug for ContextUpdated<'_> {
+    impl ::core::fmt::Debug for CancelTimer<'_> {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            let mut ds = f.debug_struct("ContextUpdated");
-            ds.field("context", &self.context());
+            let mut ds = f.debug_struct("CancelTimer");
+            ds.field("timer_id", &self.timer_id());
             ds.finish()
         }
     }
@@ -2458,6 +3653,34 @@ pub mod host_response {
                 None
             }
         }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn inbound_as_schedule_timer(&self) -> Option<ScheduleTimer<'a>> {
+            if self.inbound_type() == OutboundDelegateMsgType::ScheduleTimer {
+                let u = self.inbound();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { ScheduleTimer::init_from_table(u) })
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        #[allow(non_snake_case)]
+        pub fn inbound_as_cancel_timer(&self) -> Option<CancelTimer<'a>> {
+            if self.inbound_type() == OutboundDelegateMsgType::CancelTimer {
+                let u = self.inbound();
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                Some(unsafe { CancelTimer::init_from_table(u) })
+            } else {
+                None
+            }
+        }
     }
 
     impl ::flatbuffers::Verifiable for OutboundDelegateMsg<'_> {
@@ -2472,6 +3695,8 @@ pub mod host_response {
           OutboundDelegateMsgType::common_ApplicationMessage => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<super::common::ApplicationMessage>>("OutboundDelegateMsgType::common_ApplicationMessage", pos),
           OutboundDelegateMsgType::RequestUserInput => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<RequestUserInput>>("OutboundDelegateMsgType::RequestUserInput", pos),
           OutboundDelegateMsgType::ContextUpdated => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<ContextUpdated>>("OutboundDelegateMsgType::ContextUpdated", pos),
+          OutboundDelegateMsgType::ScheduleTimer => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<ScheduleTimer>>("OutboundDelegateMsgType::ScheduleTimer", pos),
+          OutboundDelegateMsgType::CancelTimer => v.verify_union_variant::<::flatbuffers::ForwardsUOffset<CancelTimer>>("OutboundDelegateMsgType::CancelTimer", pos),
           _ => Ok(()),
         }
      })?
@@ -2570,6 +3795,26 @@ pub mod host_response {
                         )
                     }
                 }
+                OutboundDelegateMsgType::ScheduleTimer => {
+                    if let Some(x) = self.inbound_as_schedule_timer() {
+                        ds.field("inbound", &x)
+                    } else {
+                        ds.field(
+                            "inbound",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
+                OutboundDelegateMsgType::CancelTimer => {
+                    if let Some(x) = self.inbound_as_cancel_timer() {
+                        ds.field("inbound", &x)
+                    } else {
+                        ds.field(
+                            "inbound",
+                            &"InvalidFlatbuffer: Union discriminant does not match value.",
+                        )
+                    }
+                }
                 _ => {
                     let x: Option<()> = None;
                     ds.field("inbound", &x)
@@ -3187,6 +4432,8 @@ pub mod host_response {
 
     impl<'a> Error<'a> {
         pub const VT_MSG: ::flatbuffers::VOffsetT = 4;
+        pub const VT_CODE: ::flatbuffers::VOffsetT = 6;
+        pub const VT_FIELD: ::flatbuffers::VOffsetT = 8;
 
         #[inline]
         pub unsafe fn init_from_table(table: ::flatbuffers::Table<'a>) -> Self {
@@ -3203,6 +4450,10 @@ pub mod host_response {
             args: &'args ErrorArgs<'args>,
         ) -> ::flatbuffers::WIPOffset<Error<'bldr>> {
             let mut builder = ErrorBuilder::new(_fbb);
+            if let Some(x) = args.field {
+                builder.add_field(x);
+            }
+            builder.add_code(args.code);
             if let Some(x) = args.msg {
                 builder.add_msg(x);
             }
@@ -3220,6 +4471,23 @@ pub mod host_response {
                     .unwrap()
             }
         }
+        #[inline]
+        pub fn code(&self) -> u32 {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe { self._tab.get::<u32>(Error::VT_CODE, Some(0)).unwrap() }
+        }
+        #[inline]
+        pub fn field(&self) -> Option<&'a str> {
+            // Safety:
+            // Created from valid Table for this object
+            // which contains a valid value in this slot
+            unsafe {
+                self._tab
+                    .get::<::flatbuffers::ForwardsUOffset<&str>>(Error::VT_FIELD, None)
+            }
+        }
     }
 
     impl ::flatbuffers::Verifiable for Error<'_> {
@@ -3230,18 +4498,28 @@ pub mod host_response {
         ) -> Result<(), ::flatbuffers::InvalidFlatbuffer> {
             v.visit_table(pos)?
                 .visit_field::<::flatbuffers::ForwardsUOffset<&str>>("msg", Self::VT_MSG, true)?
+                .visit_field::<u32>("code", Self::VT_CODE, false)?
+                .visit_field::<::flatbuffers::ForwardsUOffset<&str>>(
+                    "field",
+                    Self::VT_FIELD,
+                    false,
+                )?
                 .finish();
             Ok(())
         }
     }
     pub struct ErrorArgs<'a> {
         pub msg: Option<::flatbuffers::WIPOffset<&'a str>>,
+        pub code: u32,
+        pub field: Option<::flatbuffers::WIPOffset<&'a str>>,
     }
     impl<'a> Default for ErrorArgs<'a> {
         #[inline]
         fn default() -> Self {
             ErrorArgs {
                 msg: None, // required field
+                code: 0,
+                field: None,
             }
         }
     }
@@ -3257,6 +4535,15 @@ pub mod host_response {
                 .push_slot_always::<::flatbuffers::WIPOffset<_>>(Error::VT_MSG, msg);
         }
         #[inline]
+        pub fn add_code(&mut self, code: u32) {
+            self.fbb_.push_slot::<u32>(Error::VT_CODE, code, 0);
+        }
+        #[inline]
+        pub fn add_field(&mut self, field: ::flatbuffers::WIPOffset<&'b str>) {
+            self.fbb_
+                .push_slot_always::<::flatbuffers::WIPOffset<_>>(Error::VT_FIELD, field);
+        }
+        #[inline]
         pub fn new(
             _fbb: &'b mut ::flatbuffers::FlatBufferBuilder<'a, A>,
         ) -> ErrorBuilder<'a, 'b, A> {
@@ -3278,6 +4565,8 @@ pub mod host_response {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
             let mut ds = f.debug_struct("Error");
             ds.field("msg", &self.msg());
+            ds.field("code", &self.code());
+            ds.field("field", &self.field());
             ds.finish()
         }
     }
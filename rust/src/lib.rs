@@ -1,16 +1,31 @@
 //! Standard library provided by the Freenet project to be able to write Locutus-compatible contracts.
 mod code_hash;
-#[cfg(feature = "unstable")]
 pub mod contract_composition;
 mod contract_interface;
+pub mod crdt;
+#[cfg(feature = "crypto")]
+pub mod crypto;
 pub mod delegate_host;
 mod delegate_interface;
+pub mod delegate_policy;
+pub mod delegate_rpc;
+#[cfg(feature = "trace")]
+pub mod delegate_trace;
 pub(crate) mod global;
 pub mod memory;
+pub mod merkle;
+#[cfg(feature = "crypto")]
+pub mod owned_contract;
 mod parameters;
+#[cfg(feature = "crypto")]
+pub mod secret_backup;
+pub mod secret_store;
+pub mod summary;
 mod versioning;
 
 pub use contract_interface::encoding as typed_contract;
+pub mod typed_delegate;
+pub mod user_prompt;
 
 #[allow(dead_code, unused_imports, clippy::all, mismatched_lifetime_syntaxes)]
 pub(crate) mod generated {
@@ -39,7 +54,9 @@ pub mod prelude {
     pub use crate::contract_interface::wasm_interface::ContractInterfaceResult;
     pub use crate::contract_interface::*;
     pub use crate::delegate_host::{
-        decode_secret_key_list, encode_secret_key_list, error_codes, DelegateCtx,
+        decode_secret_key_list, decode_secret_ops, encode_secret_key_list, encode_secret_ops,
+        error_codes, ContractAccessError, DelegateCtx, HostError, SecretMetadata, SecretOp,
+        SecretTransaction,
     };
     pub use crate::delegate_interface::wasm_interface::DelegateInterfaceResult;
     pub use crate::delegate_interface::*;
@@ -52,6 +69,7 @@ pub mod prelude {
 
     pub use bincode;
     pub use blake3;
+    pub use serde;
     pub use serde_json;
     pub use tracing;
     pub use tracing_subscriber;
@@ -24,10 +24,205 @@ impl WasmLinearMem {
     }
 }
 
-#[cfg(feature = "contract")]
 pub mod wasm_interface {
+    #[cfg(feature = "contract")]
     use crate::prelude::*;
 
+    use super::WasmLinearMem;
+
+    /// Name of the export through which a contract module publishes its [`AbiDescriptor`].
+    ///
+    /// Modules built before the descriptor existed do not have this export; hosts should
+    /// treat them as [`AbiDescriptor::LEGACY`].
+    pub const ABI_DESCRIPTOR_EXPORT: &str = "__frnt__contract_abi";
+
+    /// Revision of the host <-> contract ABI implemented by this stdlib.
+    ///
+    /// Bumped whenever the layout of [`ContractInterfaceResult`](crate::prelude::ContractInterfaceResult)
+    /// or the way inputs are handed to the contract functions changes in an incompatible way.
+    ///
+    /// Revision 2 added [`RelatedContract::expires_after`](crate::prelude::RelatedContract::expires_after)
+    /// to update results; decode those of older modules with
+    /// [`ContractInterfaceResult::unwrap_update_state_for`](crate::prelude::ContractInterfaceResult::unwrap_update_state_for).
+    pub const CONTRACT_ABI_VERSION: u32 = 2;
+
+    /// Oldest ABI revision a host built against this stdlib is able to drive.
+    ///
+    /// Revision 0 is [`AbiDescriptor::LEGACY`], the one of the modules deployed before the
+    /// descriptor existed, so those keep running.
+    pub const MIN_SUPPORTED_ABI_VERSION: u32 = 0;
+
+    /// Capability flags advertised by a contract module in [`AbiDescriptor::features`].
+    pub mod abi_features {
+        /// Inputs are read through the `[total_len: u32]` refill protocol of
+        /// [`StreamingBuffer`](crate::memory::buf::StreamingBuffer), so the host may hand
+        /// over payloads larger than the initial buffer and serve the rest through
+        /// `freenet_contract_io::__frnt__fill_buffer`.
+        pub const STREAMING_INPUT: u32 = 1 << 0;
+
+        /// The module exports `query_state`, answering read-only client queries through
+        /// [`ContractInterface::query_state`](crate::prelude::ContractInterface::query_state).
+        /// Hosts must not call the export on modules which do not advertise this flag.
+        pub const QUERY: u32 = 1 << 1;
+    }
+
+    /// Encoding used by a contract for its typed state, parameters, deltas and summaries.
+    #[non_exhaustive]
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AbiEncoder {
+        /// The contract implements `ContractInterface` directly and handles raw bytes.
+        Raw = 0,
+        /// [`BincodeEncoder`](crate::prelude::BincodeEncoder).
+        Bincode = 1,
+        /// [`JsonEncoder`](crate::prelude::JsonEncoder).
+        Json = 2,
+        /// A user supplied encoder, or an `EncodingAdapter` implemented by hand.
+        Custom = 3,
+        /// A discriminant this stdlib does not know about.
+        Unknown = u32::MAX,
+    }
+
+    impl From<u32> for AbiEncoder {
+        fn from(v: u32) -> Self {
+            match v {
+                0 => AbiEncoder::Raw,
+                1 => AbiEncoder::Bincode,
+                2 => AbiEncoder::Json,
+                3 => AbiEncoder::Custom,
+                _ => AbiEncoder::Unknown,
+            }
+        }
+    }
+
+    /// Reasons for a host to refuse a contract module based on its [`AbiDescriptor`].
+    #[derive(Debug, thiserror::Error, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum AbiError {
+        #[error("contract ABI version {found} is not supported (supported: {min}..={max})")]
+        UnsupportedVersion { found: u32, min: u32, max: u32 },
+    }
+
+    /// Describes which ABI revision, capabilities and encoder a contract module was built with.
+    ///
+    /// Generated by the `#[contract]` macro and exported through [`ABI_DESCRIPTOR_EXPORT`],
+    /// which returns a pointer to a static instance of this struct in the module's linear
+    /// memory. Hosts read it with [`AbiDescriptor::from_raw`] right after instantiation.
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AbiDescriptor {
+        abi_version: u32,
+        features: u32,
+        encoder: u32,
+        stdlib_major: u16,
+        stdlib_minor: u16,
+        stdlib_patch: u16,
+        _reserved: u16,
+    }
+
+    impl AbiDescriptor {
+        /// Descriptor assumed for modules which do not export [`ABI_DESCRIPTOR_EXPORT`].
+        ///
+        /// Version 0 predates the streaming input protocol, so no features are advertised.
+        /// It is still supported, so [`check_compatible`](Self::check_compatible) accepts it.
+        pub const LEGACY: Self = Self {
+            abi_version: 0,
+            features: 0,
+            encoder: AbiEncoder::Unknown as u32,
+            stdlib_major: 0,
+            stdlib_minor: 0,
+            stdlib_patch: 0,
+            _reserved: 0,
+        };
+
+        /// Descriptor for a module compiled against this version of the stdlib.
+        pub const fn current(encoder: AbiEncoder) -> Self {
+            Self {
+                abi_version: CONTRACT_ABI_VERSION,
+                features: abi_features::STREAMING_INPUT,
+                encoder: encoder as u32,
+                stdlib_major: parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")),
+                stdlib_minor: parse_version_part(env!("CARGO_PKG_VERSION_MINOR")),
+                stdlib_patch: parse_version_part(env!("CARGO_PKG_VERSION_PATCH")),
+                _reserved: 0,
+            }
+        }
+
+        /// Advertises the capability flags in `features` on top of the current ones.
+        pub const fn with_features(mut self, features: u32) -> Self {
+            self.features |= features;
+            self
+        }
+
+        pub fn abi_version(&self) -> u32 {
+            self.abi_version
+        }
+
+        /// Raw capability flags, see [`abi_features`].
+        pub fn features(&self) -> u32 {
+            self.features
+        }
+
+        /// Whether all the bits in `feature` are advertised by the module.
+        pub fn supports(&self, feature: u32) -> bool {
+            self.features & feature == feature
+        }
+
+        pub fn encoder(&self) -> AbiEncoder {
+            AbiEncoder::from(self.encoder)
+        }
+
+        /// Version (`major`, `minor`, `patch`) of the stdlib the module was built against.
+        pub fn stdlib_version(&self) -> (u16, u16, u16) {
+            (self.stdlib_major, self.stdlib_minor, self.stdlib_patch)
+        }
+
+        /// Checks that a host built against this stdlib can drive the module.
+        pub fn check_compatible(&self) -> Result<(), AbiError> {
+            if (MIN_SUPPORTED_ABI_VERSION..=CONTRACT_ABI_VERSION).contains(&self.abi_version) {
+                Ok(())
+            } else {
+                Err(AbiError::UnsupportedVersion {
+                    found: self.abi_version,
+                    min: MIN_SUPPORTED_ABI_VERSION,
+                    max: CONTRACT_ABI_VERSION,
+                })
+            }
+        }
+
+        /// Returns the pointer to pass back to the host from the exported descriptor function.
+        #[doc(hidden)]
+        pub fn as_raw(&'static self) -> i64 {
+            self as *const Self as i64
+        }
+
+        /// Read the descriptor returned by the [`ABI_DESCRIPTOR_EXPORT`] function.
+        ///
+        /// # Safety
+        ///
+        /// The caller must ensure that:
+        /// - `ptr` is the value returned by the module's [`ABI_DESCRIPTOR_EXPORT`] function
+        /// - `mem` is the linear memory of that same module instance
+        pub unsafe fn from_raw(ptr: i64, mem: &WasmLinearMem) -> Self {
+            let ptr = super::buf::compute_ptr(ptr as *mut Self, mem);
+            // The descriptor is a static owned by the module; copy it out instead
+            // of taking ownership like `ContractInterfaceResult::from_raw` does.
+            std::ptr::read_unaligned(ptr)
+        }
+    }
+
+    const fn parse_version_part(part: &str) -> u16 {
+        let bytes = part.as_bytes();
+        let mut value = 0u16;
+        let mut i = 0;
+        while i < bytes.len() {
+            value = value * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+        value
+    }
+
+    #[cfg(feature = "contract")]
     fn set_logger() -> Result<(), ContractInterfaceResult> {
         #[cfg(feature = "trace")]
         {
@@ -45,9 +240,11 @@ pub mod wasm_interface {
         Ok(())
     }
 
+    #[cfg(feature = "contract")]
     use std::io::Read;
 
     /// Read all bytes from a streaming buffer into a Vec.
+    #[cfg(feature = "contract")]
     fn read_streaming_bytes(ptr: i64) -> Result<Vec<u8>, ContractInterfaceResult> {
         let mut reader = unsafe { super::buf::StreamingBuffer::from_ptr(ptr) };
         let mut bytes = Vec::with_capacity(reader.total_remaining());
@@ -59,6 +256,7 @@ pub mod wasm_interface {
         Ok(bytes)
     }
 
+    #[cfg(feature = "contract")]
     pub fn inner_validate_state<T: ContractInterface>(
         parameters: i64,
         state: i64,
@@ -93,6 +291,7 @@ pub mod wasm_interface {
         ContractInterfaceResult::from(result).into_raw()
     }
 
+    #[cfg(feature = "contract")]
     pub fn inner_update_state<T: ContractInterface>(
         parameters: i64,
         state: i64,
@@ -127,6 +326,7 @@ pub mod wasm_interface {
         ContractInterfaceResult::from(result).into_raw()
     }
 
+    #[cfg(feature = "contract")]
     pub fn inner_summarize_state<T: ContractInterface>(parameters: i64, state: i64) -> i64 {
         if let Err(e) = set_logger().map_err(|e| e.into_raw()) {
             return e;
@@ -143,6 +343,7 @@ pub mod wasm_interface {
         ContractInterfaceResult::from(summary).into_raw()
     }
 
+    #[cfg(feature = "contract")]
     pub fn inner_get_state_delta<T: ContractInterface>(
         parameters: i64,
         state: i64,
@@ -166,4 +367,77 @@ pub mod wasm_interface {
         let new_delta = <T as ContractInterface>::get_state_delta(parameters, state, summary);
         ContractInterfaceResult::from(new_delta).into_raw()
     }
+
+    #[cfg(feature = "contract")]
+    pub fn inner_query_state<T: ContractInterface>(parameters: i64, state: i64, query: i64) -> i64 {
+        if let Err(e) = set_logger().map_err(|e| e.into_raw()) {
+            return e;
+        }
+        let parameters = match read_streaming_bytes(parameters) {
+            Ok(bytes) => Parameters::from(bytes),
+            Err(e) => return e.into_raw(),
+        };
+        let state = match read_streaming_bytes(state) {
+            Ok(bytes) => State::from(bytes),
+            Err(e) => return e.into_raw(),
+        };
+        let query = match read_streaming_bytes(query) {
+            Ok(bytes) => StateQuery::from(bytes),
+            Err(e) => return e.into_raw(),
+        };
+        let result = <T as ContractInterface>::query_state(parameters, state, query);
+        ContractInterfaceResult::from(result).into_raw()
+    }
+}
+
+#[cfg(test)]
+mod abi_descriptor_tests {
+    use super::wasm_interface::*;
+    use super::WasmLinearMem;
+
+    #[test]
+    fn read_descriptor_from_linear_memory() {
+        static DESCRIPTOR: AbiDescriptor = AbiDescriptor::current(AbiEncoder::Bincode);
+        // A null-based linear memory maps module pointers onto host addresses unchanged.
+        let mem = unsafe { WasmLinearMem::new(std::ptr::null(), 0) };
+        let read = unsafe { AbiDescriptor::from_raw(DESCRIPTOR.as_raw(), &mem) };
+        assert_eq!(read, DESCRIPTOR);
+        assert_eq!(read.abi_version(), CONTRACT_ABI_VERSION);
+        assert_eq!(read.encoder(), AbiEncoder::Bincode);
+        assert!(read.supports(abi_features::STREAMING_INPUT));
+        assert_eq!(
+            read.stdlib_version(),
+            (
+                env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+                env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+                env!("CARGO_PKG_VERSION_PATCH").parse().unwrap(),
+            )
+        );
+        assert!(read.check_compatible().is_ok());
+        assert!(!read.supports(abi_features::QUERY));
+
+        let queryable = DESCRIPTOR.with_features(abi_features::QUERY);
+        assert!(queryable.supports(abi_features::QUERY | abi_features::STREAMING_INPUT));
+    }
+
+    #[test]
+    fn legacy_descriptor_is_compatible() {
+        let legacy = AbiDescriptor::LEGACY;
+        assert!(!legacy.supports(abi_features::STREAMING_INPUT));
+        assert_eq!(legacy.encoder(), AbiEncoder::Unknown);
+        assert_eq!(legacy.check_compatible(), Ok(()));
+
+        // abi_version, features, encoder and the packed stdlib version of a newer module
+        static FUTURE: [u32; 5] = [CONTRACT_ABI_VERSION + 1, 0, 0, 0, 0];
+        let mem = unsafe { WasmLinearMem::new(std::ptr::null(), 0) };
+        let future = unsafe { AbiDescriptor::from_raw(FUTURE.as_ptr() as i64, &mem) };
+        assert_eq!(
+            future.check_compatible(),
+            Err(AbiError::UnsupportedVersion {
+                found: CONTRACT_ABI_VERSION + 1,
+                min: MIN_SUPPORTED_ABI_VERSION,
+                max: CONTRACT_ABI_VERSION,
+            })
+        );
+    }
 }
@@ -0,0 +1,588 @@
+//! Merkle trees over the keyed entries of a state, to summarize large states and compute
+//! the deltas peers are missing.
+//!
+//! Entries are placed in a 16-ary trie by the blake3 hash of their key, so a tree only
+//! depends on its entries and not on the order they were inserted in. A [`MerkleSummary`]
+//! holds the root hash and a frontier of subtree hashes bounded to a number of nodes. The
+//! peer holding the state compares it with its own tree in [`MerkleTree::diff`], which
+//! picks the entries under every subtree whose hash differs.
+//!
+//! When those subtrees hold more entries than fit in a delta, the diff asks to narrow them
+//! instead: the peer replies with a finer summary of those prefixes, built with
+//! [`MerkleTree::summary_of`], and the next diff only picks the entries that differ there.
+//!
+//! ```
+//! use freenet_stdlib::merkle::MerkleTree;
+//!
+//! let local: MerkleTree = (0..100u32).map(|i| (i.to_be_bytes(), [1])).collect();
+//! let mut remote: MerkleTree = (0..90u32).map(|i| (i.to_be_bytes(), [1])).collect();
+//!
+//! let diff = local.diff(&remote.summary(16), 100);
+//! assert!(diff.is_complete());
+//! for key in diff.keys() {
+//!     remote.insert(key.clone(), [1]);
+//! }
+//! assert_eq!(remote.root(), local.root());
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::contract_interface::{ContractError, StateDelta, StateSummary};
+
+/// Nibbles in a blake3 hash, the maximum depth of a tree.
+const MAX_DEPTH: usize = 64;
+
+/// The path to a subtree: the leading nibbles of the key hashes under it.
+///
+/// Prefixes received from peers are checked when deserialized: at most 64 nibbles, each
+/// below 16.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Vec<u8>")]
+pub struct Prefix(Vec<u8>);
+
+impl TryFrom<Vec<u8>> for Prefix {
+    type Error = String;
+
+    fn try_from(nibbles: Vec<u8>) -> Result<Self, Self::Error> {
+        if nibbles.len() > MAX_DEPTH {
+            return Err(format!(
+                "prefix of {} nibbles exceeds the maximum depth of {MAX_DEPTH}",
+                nibbles.len()
+            ));
+        }
+        if let Some(nibble) = nibbles.iter().find(|nibble| **nibble > 0x0f) {
+            return Err(format!("invalid nibble {nibble:#x} in prefix"));
+        }
+        Ok(Self(nibbles))
+    }
+}
+
+impl Prefix {
+    pub fn root() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Number of nibbles in the prefix.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn child(&self, nibble: u8) -> Self {
+        let mut nibbles = self.0.clone();
+        nibbles.push(nibble);
+        Self(nibbles)
+    }
+
+    /// The lowest and highest key hashes under the prefix.
+    fn bounds(&self) -> ([u8; 32], [u8; 32]) {
+        let (mut low, mut high) = ([0; 32], [0xff; 32]);
+        for (depth, nibble) in self.0.iter().enumerate() {
+            let shift = if depth % 2 == 0 { 4 } else { 0 };
+            let mask = 0x0f << shift;
+            low[depth / 2] = (low[depth / 2] & !mask) | (nibble << shift);
+            high[depth / 2] = (high[depth / 2] & !mask) | (nibble << shift);
+        }
+        (low, high)
+    }
+}
+
+fn nibble(hash: &[u8; 32], depth: usize) -> u8 {
+    let byte = hash[depth / 2];
+    if depth % 2 == 0 {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Leaf {
+    key: Vec<u8>,
+    hash: [u8; 32],
+}
+
+type Leaves<'a> = Vec<(&'a [u8; 32], &'a Leaf)>;
+
+/// Merkle tree over the keys of a state and the hashes of their values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// By hash of the key.
+    leaves: BTreeMap<[u8; 32], Leaf>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the entry for `key`, replacing the previous value.
+    pub fn insert(&mut self, key: impl Into<Vec<u8>>, value: impl AsRef<[u8]>) {
+        let key = key.into();
+        let hash = *blake3::hash(value.as_ref()).as_bytes();
+        self.leaves
+            .insert(*blake3::hash(&key).as_bytes(), Leaf { key, hash });
+    }
+
+    /// Removes the entry for `key`, returns whether it was present.
+    pub fn remove(&mut self, key: &[u8]) -> bool {
+        self.leaves.remove(blake3::hash(key).as_bytes()).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Hash of the whole tree, `None` if it is empty.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        hash_leaves(&self.under(&Prefix::root()), 0)
+    }
+
+    /// Summary of the whole tree, with at most `max_nodes` subtrees.
+    pub fn summary(&self, max_nodes: usize) -> MerkleSummary {
+        self.summary_of(&[Prefix::root()], max_nodes)
+    }
+
+    /// Summary of the subtrees under `scopes`, with at most `max_nodes` subtrees unless
+    /// splitting every scope once already exceeds it.
+    ///
+    /// Used to reply to the prefixes a diff asked to narrow. Scopes are always split at
+    /// least one level, so each round of narrowing gets closer to the differing entries.
+    pub fn summary_of(&self, scopes: &[Prefix], max_nodes: usize) -> MerkleSummary {
+        let mut frontier: Vec<(Prefix, Leaves)> = scopes
+            .iter()
+            .flat_map(|scope| split(scope, &self.under(scope)))
+            .filter(|(_, leaves)| !leaves.is_empty())
+            .collect();
+        // descend a level at a time while the frontier fits
+        while frontier.iter().any(|(_, leaves)| leaves.len() > 1) {
+            let expanded: Vec<_> = frontier
+                .iter()
+                .flat_map(|(prefix, leaves)| split(prefix, leaves))
+                .collect();
+            if expanded.len() > max_nodes {
+                break;
+            }
+            frontier = expanded;
+        }
+        let nodes = frontier
+            .into_iter()
+            .map(|(prefix, leaves)| match leaves.as_slice() {
+                [(key_hash, leaf)] => SummaryNode::Entry {
+                    prefix,
+                    key_hash: **key_hash,
+                    hash: leaf.hash,
+                },
+                _ => SummaryNode::Subtree {
+                    hash: hash_leaves(&leaves, prefix.depth()).expect("not empty"),
+                    prefix,
+                },
+            })
+            .collect();
+        MerkleSummary {
+            root: self.root(),
+            scopes: scopes.to_vec(),
+            nodes,
+        }
+    }
+
+    /// Picks the keys of the entries the peer with `summary` is missing or has a different
+    /// value for, at most `max_entries` of them.
+    ///
+    /// Subtrees which don't fit are returned in [`MerkleDiff::narrow`]. Entries are never
+    /// removed by a diff: a peer having entries this tree lacks has no effect.
+    pub fn diff(&self, summary: &MerkleSummary, max_entries: usize) -> MerkleDiff {
+        let whole_tree = summary.scopes.iter().any(Prefix::is_root);
+        if whole_tree && summary.root == self.root() {
+            return MerkleDiff::default();
+        }
+
+        struct Group<'a> {
+            prefix: &'a Prefix,
+            keys: Vec<&'a [u8]>,
+            narrowable: bool,
+        }
+        let mut groups = Vec::new();
+        let mut covered = BTreeSet::new();
+        for node in &summary.nodes {
+            let leaves = self.under(node.prefix());
+            covered.insert(node.prefix());
+            let (keys, narrowable) = match node {
+                SummaryNode::Subtree { prefix, hash } => {
+                    if hash_leaves(&leaves, prefix.depth()) == Some(*hash) {
+                        continue;
+                    }
+                    (
+                        leaves.iter().map(|(_, leaf)| leaf.key.as_slice()).collect(),
+                        true,
+                    )
+                }
+                SummaryNode::Entry { key_hash, hash, .. } => {
+                    let keys = leaves
+                        .iter()
+                        .filter(|(kh, leaf)| *kh != key_hash || leaf.hash != *hash)
+                        .map(|(_, leaf)| leaf.key.as_slice())
+                        .collect();
+                    (keys, false)
+                }
+            };
+            groups.push(Group {
+                prefix: node.prefix(),
+                keys,
+                narrowable,
+            });
+        }
+        // entries in scope under no node are in subtrees the peer doesn't have
+        for scope in &summary.scopes {
+            let keys = self
+                .under(scope)
+                .into_iter()
+                .filter(|(key_hash, _)| {
+                    let mut prefix = Prefix::root();
+                    for depth in 0..=MAX_DEPTH {
+                        if covered.contains(&prefix) {
+                            return false;
+                        }
+                        if depth < MAX_DEPTH {
+                            prefix = prefix.child(nibble(key_hash, depth));
+                        }
+                    }
+                    true
+                })
+                .map(|(_, leaf)| leaf.key.as_slice())
+                .collect();
+            groups.push(Group {
+                prefix: scope,
+                keys,
+                narrowable: false,
+            });
+        }
+
+        groups.retain(|group| !group.keys.is_empty());
+        groups.sort_by_key(|group| group.keys.len());
+        let mut diff = MerkleDiff::default();
+        for group in groups {
+            let room = max_entries.saturating_sub(diff.keys.len());
+            if group.keys.len() <= room {
+                diff.keys.extend(group.keys.into_iter().map(<[u8]>::to_vec));
+            } else if group.narrowable {
+                diff.narrow.push(group.prefix.clone());
+            } else {
+                let keys = group.keys.into_iter().take(room);
+                diff.keys.extend(keys.map(<[u8]>::to_vec));
+                diff.truncated = true;
+            }
+        }
+        diff
+    }
+
+    fn under(&self, prefix: &Prefix) -> Leaves<'_> {
+        let (low, high) = prefix.bounds();
+        self.leaves.range(low..=high).collect()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for MerkleTree
+where
+    K: Into<Vec<u8>>,
+    V: AsRef<[u8]>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+/// Splits the leaves under `prefix` by their next nibble, single leaves are kept as is.
+fn split<'a>(prefix: &Prefix, leaves: &Leaves<'a>) -> Vec<(Prefix, Leaves<'a>)> {
+    if leaves.len() <= 1 {
+        return vec![(prefix.clone(), leaves.clone())];
+    }
+    let mut children: Vec<(Prefix, Leaves)> = Vec::new();
+    for (key_hash, leaf) in leaves {
+        let child = prefix.child(nibble(key_hash, prefix.depth()));
+        match children.last_mut() {
+            Some((last, leaves)) if *last == child => leaves.push((key_hash, leaf)),
+            _ => children.push((child, vec![(key_hash, leaf)])),
+        }
+    }
+    children
+}
+
+/// Hash of the subtree with `leaves` at `depth`, sorted by key hash.
+fn hash_leaves(leaves: &[(&[u8; 32], &Leaf)], depth: usize) -> Option<[u8; 32]> {
+    match leaves {
+        [] => None,
+        [(key_hash, leaf)] => {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&[0]);
+            hasher.update(*key_hash);
+            hasher.update(&leaf.hash);
+            Some(*hasher.finalize().as_bytes())
+        }
+        _ => {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&[1]);
+            let mut rest = leaves;
+            while let Some((key_hash, _)) = rest.first() {
+                let child = nibble(key_hash, depth);
+                let len = rest
+                    .iter()
+                    .take_while(|(key_hash, _)| nibble(key_hash, depth) == child)
+                    .count();
+                let (children, next) = rest.split_at(len);
+                hasher.update(&[child]);
+                hasher.update(&hash_leaves(children, depth + 1).expect("not empty"));
+                rest = next;
+            }
+            Some(*hasher.finalize().as_bytes())
+        }
+    }
+}
+
+/// Root hash and a frontier of subtrees of a [`MerkleTree`], see [`MerkleTree::summary`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleSummary {
+    root: Option<[u8; 32]>,
+    scopes: Vec<Prefix>,
+    nodes: Vec<SummaryNode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum SummaryNode {
+    Subtree {
+        prefix: Prefix,
+        hash: [u8; 32],
+    },
+    /// Subtree holding a single entry.
+    Entry {
+        prefix: Prefix,
+        key_hash: [u8; 32],
+        hash: [u8; 32],
+    },
+}
+
+impl SummaryNode {
+    fn prefix(&self) -> &Prefix {
+        match self {
+            SummaryNode::Subtree { prefix, .. } | SummaryNode::Entry { prefix, .. } => prefix,
+        }
+    }
+}
+
+impl MerkleSummary {
+    /// Hash of the whole summarized tree.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.root
+    }
+
+    /// Number of subtrees in the frontier.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn to_summary(&self) -> Result<StateSummary<'static>, ContractError> {
+        Ok(bincode::serialize(self)?.into())
+    }
+
+    pub fn from_summary(summary: &StateSummary<'_>) -> Result<Self, ContractError> {
+        Ok(bincode::deserialize(summary.as_ref())?)
+    }
+}
+
+/// Result of [`MerkleTree::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleDiff {
+    keys: Vec<Vec<u8>>,
+    narrow: Vec<Prefix>,
+    truncated: bool,
+}
+
+impl MerkleDiff {
+    /// Keys of the entries to send.
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    /// Subtrees which differ but didn't fit, the peer should reply with a summary of them.
+    pub fn narrow(&self) -> &[Prefix] {
+        &self.narrow
+    }
+
+    /// Whether the keys cover every difference found.
+    pub fn is_complete(&self) -> bool {
+        self.narrow.is_empty() && !self.truncated
+    }
+
+    /// Builds the delta to send, looking up the entry for every key.
+    pub fn into_delta<E>(self, mut entry: impl FnMut(&[u8]) -> Option<E>) -> MerkleDelta<E> {
+        MerkleDelta {
+            entries: self.keys.iter().filter_map(|key| entry(key)).collect(),
+            narrow: self.narrow,
+        }
+    }
+}
+
+/// Entries picked by a [`MerkleDiff`] along with the subtrees to narrow.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleDelta<E> {
+    pub entries: Vec<E>,
+    pub narrow: Vec<Prefix>,
+}
+
+impl<E: Serialize> MerkleDelta<E> {
+    pub fn to_delta(&self) -> Result<StateDelta<'static>, ContractError> {
+        Ok(bincode::serialize(self)?.into())
+    }
+}
+
+impl<E: DeserializeOwned> MerkleDelta<E> {
+    pub fn from_delta(delta: &StateDelta<'_>) -> Result<Self, ContractError> {
+        Ok(bincode::deserialize(delta.as_ref())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(range: std::ops::Range<u32>, version: u8) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        range
+            .map(|i| (i.to_be_bytes().to_vec(), vec![version; 8]))
+            .collect()
+    }
+
+    #[test]
+    fn root_ignores_insertion_order() {
+        let forward: MerkleTree = entries(0..50, 0).into_iter().collect();
+        let backward: MerkleTree = entries(0..50, 0).into_iter().rev().collect();
+        assert_eq!(forward.root(), backward.root());
+        assert!(forward.diff(&backward.summary(8), 10).keys().is_empty());
+
+        let mut changed = backward.clone();
+        changed.insert(7u32.to_be_bytes(), [1; 8]);
+        assert_ne!(forward.root(), changed.root());
+        assert!(changed.remove(&7u32.to_be_bytes()));
+        assert!(changed.root() != forward.root() && changed.len() == 49);
+    }
+
+    #[test]
+    fn summary_is_bounded() {
+        let tree: MerkleTree = entries(0..1000, 0).into_iter().collect();
+        let summary = tree.summary(40);
+        assert!(summary.len() <= 40 && summary.len() > 1);
+        let decoded = MerkleSummary::from_summary(&summary.to_summary().unwrap()).unwrap();
+        assert_eq!(decoded, summary);
+        assert_eq!(decoded.root(), tree.root());
+    }
+
+    /// Syncs `remote` with `local` a round at a time, returns the number of rounds.
+    fn sync(
+        local: &BTreeMap<Vec<u8>, Vec<u8>>,
+        remote: &mut BTreeMap<Vec<u8>, Vec<u8>>,
+        max_nodes: usize,
+        max_entries: usize,
+        max_rounds: usize,
+    ) -> usize {
+        let local_tree: MerkleTree = local.clone().into_iter().collect();
+        let mut narrow = Vec::new();
+        let mut rounds = 0;
+        loop {
+            rounds += 1;
+            assert!(rounds < max_rounds, "sync doesn't converge");
+            let remote_tree: MerkleTree = remote.clone().into_iter().collect();
+            let summary = if narrow.is_empty() {
+                remote_tree.summary(max_nodes)
+            } else {
+                remote_tree.summary_of(&narrow, max_nodes)
+            };
+            let diff = local_tree.diff(&summary, max_entries);
+            assert!(diff.keys().len() <= max_entries);
+            if narrow.is_empty() && diff.keys().is_empty() && diff.is_complete() {
+                break;
+            }
+            let delta = diff.into_delta(|key| Some((key.to_vec(), local[key].clone())));
+            let delta = MerkleDelta::from_delta(&delta.to_delta().unwrap()).unwrap();
+            remote.extend(delta.entries);
+            narrow = delta.narrow;
+        }
+        rounds
+    }
+
+    #[test]
+    fn narrows_until_synced() {
+        let local = entries(0..2000, 1);
+        let mut remote = entries(0..1900, 1);
+        for i in (0..1900).step_by(97) {
+            remote.insert(u32::to_be_bytes(i).to_vec(), vec![0; 8]);
+        }
+        sync(&local, &mut remote, 64, 150, 40);
+        assert_eq!(remote, local);
+    }
+
+    #[test]
+    fn coarse_summaries_of_all_different_values_converge() {
+        let local = entries(0..2000, 1);
+        let mut remote = entries(0..2000, 0);
+        // every subtree of the first summary is too large to send, and so are
+        // the 16 prefixes to narrow
+        let remote_tree: MerkleTree = remote.clone().into_iter().collect();
+        let local_tree: MerkleTree = local.clone().into_iter().collect();
+        let diff = local_tree.diff(&remote_tree.summary(16), 100);
+        assert!(diff.keys().is_empty() && diff.narrow().len() == 16);
+        let narrowed = remote_tree.summary_of(diff.narrow(), 16);
+        assert!(narrowed.nodes.iter().all(|node| node.prefix().depth() > 1));
+
+        sync(&local, &mut remote, 16, 100, 200);
+        assert_eq!(remote, local);
+    }
+
+    #[test]
+    fn malicious_prefixes_are_rejected() {
+        let summary_with = |prefix: Vec<u8>| MerkleSummary {
+            root: None,
+            scopes: vec![Prefix::root()],
+            nodes: vec![SummaryNode::Subtree {
+                prefix: Prefix(prefix),
+                hash: [0; 32],
+            }],
+        };
+        let too_deep = summary_with(vec![0; MAX_DEPTH + 1]).to_summary().unwrap();
+        assert!(matches!(
+            MerkleSummary::from_summary(&too_deep),
+            Err(ContractError::Deser(_))
+        ));
+        let bad_nibble = summary_with(vec![1, 0x10]).to_summary().unwrap();
+        assert!(MerkleSummary::from_summary(&bad_nibble).is_err());
+        let deepest = summary_with(vec![0x0f; MAX_DEPTH]).to_summary().unwrap();
+        let summary = MerkleSummary::from_summary(&deepest).unwrap();
+        assert!(!entries(0..10, 1)
+            .into_iter()
+            .collect::<MerkleTree>()
+            .diff(&summary, 100)
+            .keys()
+            .is_empty());
+
+        let delta = MerkleDelta::<()> {
+            entries: vec![],
+            narrow: vec![Prefix(vec![0; 200])],
+        };
+        assert!(MerkleDelta::<()>::from_delta(&delta.to_delta().unwrap()).is_err());
+    }
+}
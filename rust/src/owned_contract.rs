@@ -0,0 +1,585 @@
+//! Building block for contracts whose state can only be changed by the holder of a key.
+//!
+//! Available with the `crypto` feature. The state of the contract is an [`OwnedState`],
+//! which wraps the application value together with a sequence number and the owner's
+//! signature over both. The owner's [`VerifyingKey`] is part of the contract
+//! [`OwnerParameters`], so every instance is bound to exactly one owner.
+//!
+//! Updates travel as [`SignedDelta`]s: the owner applies a delta locally, signs the
+//! resulting state under the next sequence number and ships the delta along with that
+//! signature. Peers apply the delta and check that the signature matches the state they
+//! end up with, so a delta is only accepted on top of the state it was produced from and
+//! the stored state stays verifiable by `validate_state` at any time. Updates whose
+//! sequence number is older than the current state are rejected as replays, and so are
+//! different updates under the current sequence number; receiving the current state or
+//! its last delta again is a no-op.
+//!
+//! Bad signatures and replays are reported as [`ContractError::Rejected`], with the
+//! [`ErrorCode::BAD_SIGNATURE`] and [`ErrorCode::STALE_SEQUENCE`] codes respectively.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use freenet_stdlib::{owned_contract::*, prelude::*};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Profile {
+//!     name: String,
+//! }
+//!
+//! #[contract]
+//! impl OwnerControlled for Profile {
+//!     type Parameters = ();
+//!     type Delta = String;
+//!
+//!     fn instance_id(params: &OwnerParameters<()>) -> ContractInstanceId {
+//!         unimplemented!()
+//!     }
+//!
+//!     fn apply(&mut self, _: &(), new_name: String) -> Result<(), ContractError> {
+//!         self.name = new_name;
+//!         Ok(())
+//!     }
+//! }
+//! ```
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    contract_interface::{
+        ContractError, ContractInstanceId, ContractInterface, ErrorCode, InvalidReason,
+        RelatedContracts, State, StateDelta, StateSummary, UpdateData, UpdateModification,
+        ValidateResult,
+    },
+    crypto::{Signature, SigningKey, VerifyingKey},
+    parameters::Parameters,
+    typed_contract::{
+        BincodeEncoder, EncodingAdapter, MergeResult, RelatedContractsContainer, TypedContract,
+        TypedUpdateData,
+    },
+};
+
+/// Domain separation tag prepended to every signed message.
+const SIGNATURE_DOMAIN: &[u8] = b"freenet-stdlib/owned-state/v1";
+
+/// Application logic of an owner controlled contract.
+///
+/// The value is stored inside an [`OwnedState`], which takes care of the signatures and
+/// sequence numbers. Values are signed over their bincode encoding, so it must be
+/// deterministic: avoid `HashMap`/`HashSet` fields and prefer their `BTree` counterparts.
+pub trait OwnerControlled: Clone + Serialize + DeserializeOwned {
+    /// Application specific parameters, stored next to the owner key.
+    type Parameters: Serialize + DeserializeOwned;
+    /// Change applied by the owner to the value.
+    type Delta: Clone + Serialize + DeserializeOwned;
+
+    /// See [`TypedContract::instance_id`].
+    fn instance_id(params: &OwnerParameters<Self::Parameters>) -> ContractInstanceId;
+
+    /// Additional application level validation of the value.
+    fn verify(&self, _parameters: &Self::Parameters) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Applies a delta which has already been authenticated as coming from the owner.
+    fn apply(
+        &mut self,
+        parameters: &Self::Parameters,
+        delta: Self::Delta,
+    ) -> Result<(), ContractError>;
+}
+
+/// Parameters of an owner controlled contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerParameters<P> {
+    /// Only signatures from this key are accepted.
+    pub owner: VerifyingKey,
+    pub params: P,
+}
+
+impl<P> OwnerParameters<P> {
+    pub fn new(owner: VerifyingKey, params: P) -> Self {
+        Self { owner, params }
+    }
+}
+
+/// The state of an owner controlled contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedState<T> {
+    sequence: u64,
+    value: T,
+    signature: Signature,
+}
+
+impl<T: OwnerControlled> OwnedState<T> {
+    /// Creates the initial state of a contract, signed by the owner.
+    pub fn new(
+        owner: &SigningKey,
+        parameters: &OwnerParameters<T::Parameters>,
+        value: T,
+    ) -> Result<Self, ContractError> {
+        let signature = owner.sign(&signed_message(parameters, 0, &value)?);
+        Ok(Self {
+            sequence: 0,
+            value,
+            signature,
+        })
+    }
+
+    /// Applies `delta` locally and returns the envelope to send to the network.
+    ///
+    /// `self` is updated to the resulting state, which is what peers holding the same
+    /// state end up with once they merge the returned [`SignedDelta`].
+    pub fn sign_delta(
+        &mut self,
+        owner: &SigningKey,
+        parameters: &OwnerParameters<T::Parameters>,
+        delta: T::Delta,
+    ) -> Result<SignedDelta<T::Delta>, ContractError> {
+        let mut value = self.value.clone();
+        value.apply(&parameters.params, delta.clone())?;
+        let sequence =
+            self.sequence
+                .checked_add(1)
+                .ok_or_else(|| ContractError::InvalidUpdateWithInfo {
+                    reason: "sequence number exhausted".into(),
+                })?;
+        let signature = owner.sign(&signed_message(parameters, sequence, &value)?);
+        self.sequence = sequence;
+        self.value = value;
+        self.signature = signature;
+        Ok(SignedDelta {
+            sequence,
+            delta,
+            signature,
+        })
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    fn verify_signature(
+        &self,
+        parameters: &OwnerParameters<T::Parameters>,
+    ) -> Result<(), ContractError> {
+        let message = signed_message(parameters, self.sequence, &self.value)?;
+        parameters
+            .owner
+            .verify(&message, &self.signature)
+            .map_err(|_| {
+                InvalidReason::new(
+                    ErrorCode::BAD_SIGNATURE,
+                    format!(
+                        "state with sequence {} is not signed by the owner",
+                        self.sequence
+                    ),
+                )
+                .at("signature")
+                .into()
+            })
+    }
+
+    /// Checks that an update signed under `sequence` may be applied on top of this state.
+    ///
+    /// Returns `false` for a re-delivery of the current state (same sequence and
+    /// signature), which there is nothing to apply for.
+    fn check_newer(&self, sequence: u64, signature: &Signature) -> Result<bool, ContractError> {
+        if sequence < self.sequence {
+            return Err(InvalidReason::new(
+                ErrorCode::STALE_SEQUENCE,
+                format!(
+                    "replayed update: sequence {sequence} is older than current sequence {}",
+                    self.sequence
+                ),
+            )
+            .at("sequence")
+            .into());
+        }
+        if sequence == self.sequence {
+            if signature == &self.signature {
+                return Ok(false);
+            }
+            return Err(InvalidReason::new(
+                ErrorCode::STALE_SEQUENCE,
+                format!("conflicting update: sequence {sequence} already holds a different state"),
+            )
+            .at("sequence")
+            .into());
+        }
+        Ok(true)
+    }
+
+    fn replace(
+        &mut self,
+        parameters: &OwnerParameters<T::Parameters>,
+        new_state: &Self,
+    ) -> Result<(), ContractError> {
+        if !self.check_newer(new_state.sequence, &new_state.signature)? {
+            return Ok(());
+        }
+        new_state.verify_signature(parameters)?;
+        new_state.value.verify(&parameters.params)?;
+        *self = new_state.clone();
+        Ok(())
+    }
+
+    fn apply_signed(
+        &mut self,
+        parameters: &OwnerParameters<T::Parameters>,
+        signed: &SignedDelta<T::Delta>,
+    ) -> Result<(), ContractError> {
+        if !self.check_newer(signed.sequence, &signed.signature)? {
+            return Ok(());
+        }
+        let mut value = self.value.clone();
+        value.apply(&parameters.params, signed.delta.clone())?;
+        let candidate = Self {
+            sequence: signed.sequence,
+            value,
+            signature: signed.signature,
+        };
+        candidate.verify_signature(parameters).map_err(|_| {
+            InvalidReason::new(
+                ErrorCode::BAD_SIGNATURE,
+                format!(
+                    "delta with sequence {} is not signed by the owner or does not apply \
+                     to the state at sequence {}",
+                    signed.sequence, self.sequence
+                ),
+            )
+            .at("signature")
+        })?;
+        candidate.value.verify(&parameters.params)?;
+        *self = candidate;
+        Ok(())
+    }
+}
+
+/// A delta signed by the owner.
+///
+/// The signature covers the state resulting from applying `delta`, under `sequence`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedDelta<D> {
+    pub sequence: u64,
+    pub delta: D,
+    pub signature: Signature,
+}
+
+/// Delta type of [`OwnedState`] contracts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum OwnedDelta<T: OwnerControlled> {
+    /// The peer is already up to date, there is nothing to apply.
+    UpToDate,
+    /// An incremental change signed by the owner.
+    Signed(SignedDelta<T::Delta>),
+    /// The whole state, sent to peers too far behind for a delta to apply.
+    Snapshot(OwnedState<T>),
+}
+
+impl<T: OwnerControlled> From<SignedDelta<T::Delta>> for OwnedDelta<T> {
+    fn from(value: SignedDelta<T::Delta>) -> Self {
+        OwnedDelta::Signed(value)
+    }
+}
+
+fn signed_message<T: OwnerControlled>(
+    parameters: &OwnerParameters<T::Parameters>,
+    sequence: u64,
+    value: &T,
+) -> Result<Vec<u8>, ContractError> {
+    // Binding the parameters prevents replaying a state into another contract
+    // instance owned by the same key.
+    let mut message = SIGNATURE_DOMAIN.to_vec();
+    bincode::serialize_into(&mut message, parameters)?;
+    message.extend_from_slice(&sequence.to_le_bytes());
+    bincode::serialize_into(&mut message, value)?;
+    Ok(message)
+}
+
+impl<T: OwnerControlled> EncodingAdapter for OwnedState<T> {
+    type Parameters = OwnerParameters<T::Parameters>;
+    type Delta = OwnedDelta<T>;
+    /// The sequence number of the summarized state.
+    type Summary = u64;
+
+    type SelfEncoder = BincodeEncoder<Self>;
+    type ParametersEncoder = BincodeEncoder<Self::Parameters>;
+    type DeltaEncoder = BincodeEncoder<Self::Delta>;
+    type SummaryEncoder = BincodeEncoder<Self::Summary>;
+}
+
+impl<T: OwnerControlled> TypedContract for OwnedState<T> {
+    fn instance_id(params: &Self::Parameters) -> ContractInstanceId {
+        T::instance_id(params)
+    }
+
+    fn verify(
+        &self,
+        parameters: Self::Parameters,
+        _related: RelatedContractsContainer,
+    ) -> Result<ValidateResult, ContractError> {
+        self.verify_signature(&parameters)?;
+        self.value.verify(&parameters.params)?;
+        Ok(ValidateResult::Valid)
+    }
+
+    fn merge(
+        &mut self,
+        parameters: &Self::Parameters,
+        update: TypedUpdateData<Self>,
+        _related: &RelatedContractsContainer,
+    ) -> MergeResult {
+        let result = match &update {
+            TypedUpdateData::RelatedState { state }
+            | TypedUpdateData::RelatedStateAndDelta { state, .. } => {
+                self.replace(parameters, state)
+            }
+            TypedUpdateData::RelatedDelta { delta } => match delta {
+                OwnedDelta::UpToDate => Ok(()),
+                OwnedDelta::Signed(signed) => self.apply_signed(parameters, signed),
+                OwnedDelta::Snapshot(state) => self.replace(parameters, state),
+            },
+        };
+        match result {
+            Ok(()) => MergeResult::Success,
+            Err(err) => MergeResult::Error(err),
+        }
+    }
+
+    fn summarize(&self, _parameters: Self::Parameters) -> Result<Self::Summary, ContractError> {
+        Ok(self.sequence)
+    }
+
+    fn delta(
+        &self,
+        _parameters: Self::Parameters,
+        summary: Self::Summary,
+    ) -> Result<Self::Delta, ContractError> {
+        if summary >= self.sequence {
+            Ok(OwnedDelta::UpToDate)
+        } else {
+            Ok(OwnedDelta::Snapshot(self.clone()))
+        }
+    }
+}
+
+impl<T: OwnerControlled> ContractInterface for OwnedState<T> {
+    fn validate_state(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+        related: RelatedContracts<'static>,
+    ) -> Result<ValidateResult, ContractError> {
+        crate::typed_contract::inner_validate_state::<Self>(parameters, state, related)
+    }
+
+    fn update_state(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+        data: Vec<UpdateData<'static>>,
+    ) -> Result<UpdateModification<'static>, ContractError> {
+        crate::typed_contract::inner_update_state::<Self>(parameters, state, data)
+    }
+
+    fn summarize_state(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+    ) -> Result<StateSummary<'static>, ContractError> {
+        crate::typed_contract::inner_summarize_state::<Self>(parameters, state)
+    }
+
+    fn get_state_delta(
+        parameters: Parameters<'static>,
+        state: State<'static>,
+        summary: StateSummary<'static>,
+    ) -> Result<StateDelta<'static>, ContractError> {
+        crate::typed_contract::inner_state_delta::<Self>(parameters, state, summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct Counter(u64);
+
+    impl OwnerControlled for Counter {
+        type Parameters = ();
+        type Delta = u64;
+
+        fn instance_id(_: &OwnerParameters<()>) -> ContractInstanceId {
+            ContractInstanceId::new([0; 32])
+        }
+
+        fn apply(&mut self, _: &(), delta: u64) -> Result<(), ContractError> {
+            self.0 += delta;
+            Ok(())
+        }
+    }
+
+    fn setup() -> (SigningKey, Parameters<'static>, OwnedState<Counter>) {
+        let owner = SigningKey::from_bytes(&[1; 32]);
+        let params = OwnerParameters::new(owner.verifying_key(), ());
+        let state = OwnedState::new(&owner, &params, Counter(0)).unwrap();
+        let params = Parameters::from(bincode::serialize(&params).unwrap());
+        (owner, params, state)
+    }
+
+    fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+        bincode::serialize(value).unwrap()
+    }
+
+    fn update(
+        params: &Parameters<'static>,
+        state: &OwnedState<Counter>,
+        delta: OwnedDelta<Counter>,
+    ) -> Result<OwnedState<Counter>, ContractError> {
+        let modification = OwnedState::<Counter>::update_state(
+            params.clone(),
+            State::from(encode(state)),
+            vec![UpdateData::Delta(StateDelta::from(encode(&delta)))],
+        )?;
+        Ok(bincode::deserialize(modification.unwrap_valid().as_ref()).unwrap())
+    }
+
+    fn reason(err: ContractError) -> String {
+        match err {
+            ContractError::Rejected(reason) => reason.message,
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn signed_delta_is_applied() {
+        let (owner, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let mut local = state.clone();
+        let delta = local.sign_delta(&owner, &typed_params, 5).unwrap();
+
+        let updated = update(&params, &state, delta.into()).unwrap();
+        assert_eq!(updated, local);
+        assert_eq!(updated.value(), &Counter(5));
+        assert_eq!(
+            OwnedState::<Counter>::validate_state(
+                params,
+                State::from(encode(&updated)),
+                RelatedContracts::new()
+            )
+            .unwrap(),
+            ValidateResult::Valid
+        );
+    }
+
+    #[test]
+    fn redelivered_delta_is_a_noop() {
+        let (owner, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let mut local = state.clone();
+        let delta = local.sign_delta(&owner, &typed_params, 5).unwrap();
+        let updated = update(&params, &state, delta.clone().into()).unwrap();
+
+        assert_eq!(update(&params, &updated, delta.into()).unwrap(), updated);
+        assert_eq!(
+            update(&params, &updated, OwnedDelta::Snapshot(updated.clone())).unwrap(),
+            updated
+        );
+    }
+
+    #[test]
+    fn replayed_delta_is_rejected() {
+        let (owner, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let mut local = state.clone();
+        let first = local.sign_delta(&owner, &typed_params, 5).unwrap();
+        let second = local.sign_delta(&owner, &typed_params, 1).unwrap();
+        let updated = update(&params, &state, first.clone().into()).unwrap();
+        let updated = update(&params, &updated, second.into()).unwrap();
+
+        let err = update(&params, &updated, first.into()).unwrap_err();
+        assert!(reason(err).starts_with("replayed update"));
+    }
+
+    #[test]
+    fn conflicting_delta_at_same_sequence_is_rejected() {
+        let (owner, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let applied = state.clone().sign_delta(&owner, &typed_params, 5).unwrap();
+        let conflicting = state.clone().sign_delta(&owner, &typed_params, 7).unwrap();
+        let updated = update(&params, &state, applied.into()).unwrap();
+
+        let err = update(&params, &updated, conflicting.into()).unwrap_err();
+        assert!(reason(err).starts_with("conflicting update"));
+    }
+
+    #[test]
+    fn delta_from_other_key_is_rejected() {
+        let (_, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let attacker = SigningKey::from_bytes(&[2; 32]);
+        let delta = state
+            .clone()
+            .sign_delta(&attacker, &typed_params, 5)
+            .unwrap();
+
+        let err = update(&params, &state, delta.into()).unwrap_err();
+        assert!(reason(err).contains("not signed by the owner"));
+    }
+
+    #[test]
+    fn forged_state_is_invalid() {
+        let (_, params, state) = setup();
+        let attacker = SigningKey::from_bytes(&[2; 32]);
+        let typed_params = OwnerParameters::new(attacker.verifying_key(), ());
+        let forged = OwnedState::new(&attacker, &typed_params, state.into_value()).unwrap();
+
+        let err = OwnedState::<Counter>::validate_state(
+            params,
+            State::from(encode(&forged)),
+            RelatedContracts::new(),
+        )
+        .unwrap_err();
+        assert!(reason(err).contains("not signed by the owner"));
+    }
+
+    #[test]
+    fn lagging_peer_receives_snapshot() {
+        let (owner, params, state) = setup();
+        let typed_params = bincode::deserialize(params.as_ref()).unwrap();
+        let mut local = state.clone();
+        local.sign_delta(&owner, &typed_params, 1).unwrap();
+        local.sign_delta(&owner, &typed_params, 2).unwrap();
+
+        let summary =
+            OwnedState::<Counter>::summarize_state(params.clone(), State::from(encode(&state)))
+                .unwrap();
+        let delta = OwnedState::<Counter>::get_state_delta(
+            params.clone(),
+            State::from(encode(&local)),
+            summary,
+        )
+        .unwrap();
+        let delta: OwnedDelta<Counter> = bincode::deserialize(delta.as_ref()).unwrap();
+        assert_eq!(update(&params, &state, delta).unwrap(), local);
+
+        let summary = encode(&local.sequence());
+        let delta = OwnedState::<Counter>::get_state_delta(
+            params,
+            State::from(encode(&local)),
+            StateSummary::from(summary),
+        )
+        .unwrap();
+        let delta: OwnedDelta<Counter> = bincode::deserialize(delta.as_ref()).unwrap();
+        assert_eq!(delta, OwnedDelta::UpToDate);
+    }
+}
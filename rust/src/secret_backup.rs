@@ -0,0 +1,502 @@
+//! Portable, encrypted archives of delegate secrets.
+//!
+//! Available with the `crypto` feature. A [`SecretBackup`] holds the secrets stored under
+//! a key prefix, usually a [`SecretNamespace`]. It is sealed into an archive protected
+//! either by a [`SymmetricKey`] or by a password, which a delegate hands to the user
+//! (e.g. with [`SecretBackup::export_message`]) and later restores, possibly in another
+//! node, with [`SecretBackup::open`] and [`SecretBackup::restore`].
+//!
+//! # Archive format
+//!
+//! The format is fixed so that wallets and other UIs can produce and read archives
+//! without the stdlib. All integers are little-endian.
+//!
+//! | Field | Size | Content |
+//! |-------|------|---------|
+//! | magic | 4 | `FNSB` |
+//! | version | 1 | [`BACKUP_FORMAT_VERSION`] |
+//! | protection | 1 | `0` key, `1` password |
+//! | kdf parameters | 28 | password only: argon2id memory in KiB, iterations and parallelism (`u32` each), 16 byte salt |
+//! | nonce | 24 | XChaCha20-Poly1305 nonce |
+//! | ciphertext | .. | the encrypted payload followed by the 16 byte tag |
+//!
+//! Every byte before the nonce is authenticated as associated data. For passwords the
+//! encryption key is the 32 byte argon2id (v0x13) hash of the password with the given
+//! parameters and salt.
+//!
+//! The payload is the key prefix followed by each secret's key and value, each one of
+//! them encoded as a `u32` length and the bytes; the payload is a concatenation of these
+//! fields with no entry count.
+
+use crate::{
+    crypto::{CryptoError, SymmetricKey, NONCE_LENGTH, SECRET_KEY_LENGTH},
+    delegate_host::{HostError, SecretTransaction},
+    delegate_interface::{ApplicationMessage, DelegateError},
+    secret_store::{SecretError, SecretNamespace, SecretStore},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Magic bytes at the start of every archive.
+pub const BACKUP_MAGIC: [u8; 4] = *b"FNSB";
+/// Current version of the archive format.
+pub const BACKUP_FORMAT_VERSION: u8 = 1;
+/// Length of the password salt.
+pub const SALT_LENGTH: usize = 16;
+
+const PROTECTION_KEY: u8 = 0;
+const PROTECTION_PASSWORD: u8 = 1;
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("malformed backup archive: {0}")]
+    Malformed(&'static str),
+    #[error("unsupported backup format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("the archive is protected with a {0}")]
+    WrongProtection(&'static str),
+    #[error("invalid key derivation parameters: {0}")]
+    Kdf(String),
+    #[error("the archive is for another namespace")]
+    NamespaceMismatch,
+    #[error("secret outside of the archive namespace")]
+    OutsideNamespace,
+    #[error(transparent)]
+    Crypto(#[from] CryptoError),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
+    #[error("failed to write the restored secrets: {0}")]
+    Storage(HostError),
+}
+
+impl From<BackupError> for DelegateError {
+    fn from(err: BackupError) -> Self {
+        match err {
+            BackupError::Malformed(_) | BackupError::UnsupportedVersion(_) => {
+                DelegateError::Deser(format!("{err}"))
+            }
+            other => DelegateError::Other(format!("{other}")),
+        }
+    }
+}
+
+/// Cost parameters of the argon2id password hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory size in KiB.
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// Upper bound of the memory accepted when opening an archive, so a crafted archive
+    /// can't exhaust the delegate memory.
+    pub const MAX_MEMORY_KIB: u32 = 256 * 1024;
+    pub const MAX_ITERATIONS: u32 = 64;
+    pub const MAX_PARALLELISM: u32 = 16;
+
+    fn derive_key(
+        &self,
+        password: &[u8],
+        salt: &[u8; SALT_LENGTH],
+    ) -> Result<SymmetricKey, BackupError> {
+        if self.memory_kib > Self::MAX_MEMORY_KIB
+            || self.iterations > Self::MAX_ITERATIONS
+            || self.parallelism > Self::MAX_PARALLELISM
+        {
+            return Err(BackupError::Kdf("cost above the supported maximum".into()));
+        }
+        let params = argon2::Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(SECRET_KEY_LENGTH),
+        )
+        .map_err(|e| BackupError::Kdf(format!("{e}")))?;
+        let hasher =
+            argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+        let mut key = [0u8; SECRET_KEY_LENGTH];
+        hasher
+            .hash_password_into(password, salt, &mut key)
+            .map_err(|e| BackupError::Kdf(format!("{e}")))?;
+        let key = SymmetricKey::from_bytes(key);
+        Ok(key)
+    }
+}
+
+impl Default for KdfParams {
+    /// The argon2id parameters recommended by OWASP: 19 MiB, 2 iterations, 1 lane.
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// How an archive is encrypted.
+#[derive(Debug, Clone, Copy)]
+pub enum BackupProtection<'a> {
+    Key(&'a SymmetricKey),
+    Password {
+        password: &'a [u8],
+        /// Used when sealing, when opening the parameters stored in the archive are used.
+        params: KdfParams,
+    },
+}
+
+impl<'a> BackupProtection<'a> {
+    /// Password protection with the default [`KdfParams`].
+    pub fn password(password: &'a [u8]) -> Self {
+        BackupProtection::Password {
+            password,
+            params: KdfParams::default(),
+        }
+    }
+}
+
+/// Secrets stored under a common key prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretBackup {
+    prefix: Vec<u8>,
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl SecretBackup {
+    /// Collects every secret whose raw key starts with `prefix`.
+    pub fn collect(store: &impl SecretStore, prefix: &[u8]) -> Self {
+        let entries = store
+            .list_secrets(prefix)
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .filter_map(|key| store.get_secret(&key).map(|value| (key, value)))
+            .collect();
+        Self {
+            prefix: prefix.to_vec(),
+            entries,
+        }
+    }
+
+    /// Collects every secret in `namespace`.
+    pub fn from_namespace<T>(store: &impl SecretStore, namespace: &SecretNamespace<T>) -> Self
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Self::collect(store, &namespace.raw_key(&[]))
+    }
+
+    /// The raw key prefix the secrets were collected from.
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// The raw keys and values of the secrets.
+    pub fn entries(&self) -> &[(Vec<u8>, Vec<u8>)] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Encrypts the secrets into an archive using the given random `nonce` and, for
+    /// passwords, `salt`. Both must be fresh for every archive.
+    pub fn seal_with(
+        &self,
+        protection: BackupProtection,
+        nonce: &[u8; NONCE_LENGTH],
+        salt: &[u8; SALT_LENGTH],
+    ) -> Result<Vec<u8>, BackupError> {
+        let mut archive = BACKUP_MAGIC.to_vec();
+        archive.push(BACKUP_FORMAT_VERSION);
+        let key = match protection {
+            BackupProtection::Key(key) => {
+                archive.push(PROTECTION_KEY);
+                key.clone()
+            }
+            BackupProtection::Password { password, params } => {
+                archive.push(PROTECTION_PASSWORD);
+                archive.extend_from_slice(&params.memory_kib.to_le_bytes());
+                archive.extend_from_slice(&params.iterations.to_le_bytes());
+                archive.extend_from_slice(&params.parallelism.to_le_bytes());
+                archive.extend_from_slice(salt);
+                params.derive_key(password, salt)?
+            }
+        };
+        let sealed = key.seal_with_nonce(nonce, &self.encode_payload(), &archive)?;
+        archive.extend_from_slice(&sealed);
+        Ok(archive)
+    }
+
+    /// Same as [`Self::seal_with`] with a random nonce and salt.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn seal(&self, protection: BackupProtection) -> Result<Vec<u8>, BackupError> {
+        let random = crate::rand::rand_bytes((NONCE_LENGTH + SALT_LENGTH) as u32);
+        let (nonce, salt) = random.split_at(NONCE_LENGTH);
+        self.seal_with(
+            protection,
+            nonce.try_into().unwrap(),
+            salt.try_into().unwrap(),
+        )
+    }
+
+    /// Seals the secrets in `namespace` into an application message for the caller.
+    #[cfg(all(feature = "contract", target_family = "wasm"))]
+    pub fn export_message<T>(
+        store: &impl SecretStore,
+        namespace: &SecretNamespace<T>,
+        protection: BackupProtection,
+    ) -> Result<ApplicationMessage, BackupError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let archive = Self::from_namespace(store, namespace).seal(protection)?;
+        Ok(Self::archive_message(archive))
+    }
+
+    /// Wraps a sealed archive into the application message returned to the caller.
+    pub fn archive_message(archive: Vec<u8>) -> ApplicationMessage {
+        ApplicationMessage::new(archive).processed(true)
+    }
+
+    /// Decrypts and validates an archive.
+    pub fn open(archive: &[u8], protection: BackupProtection) -> Result<Self, BackupError> {
+        let mut reader = Reader(archive);
+        if reader.take(BACKUP_MAGIC.len())? != BACKUP_MAGIC {
+            return Err(BackupError::Malformed("not a secret backup"));
+        }
+        let version = reader.take(1)?[0];
+        if version != BACKUP_FORMAT_VERSION {
+            return Err(BackupError::UnsupportedVersion(version));
+        }
+        let key = match (reader.take(1)?[0], protection) {
+            (PROTECTION_KEY, BackupProtection::Key(key)) => key.clone(),
+            (PROTECTION_PASSWORD, BackupProtection::Password { password, .. }) => {
+                let params = KdfParams {
+                    memory_kib: reader.u32()?,
+                    iterations: reader.u32()?,
+                    parallelism: reader.u32()?,
+                };
+                let salt = reader.take(SALT_LENGTH)?.try_into().unwrap();
+                params.derive_key(password, salt)?
+            }
+            (PROTECTION_KEY, _) => return Err(BackupError::WrongProtection("key")),
+            (PROTECTION_PASSWORD, _) => return Err(BackupError::WrongProtection("password")),
+            _ => return Err(BackupError::Malformed("unknown protection")),
+        };
+        let header_len = archive.len() - reader.0.len();
+        let payload = key.open(reader.0, &archive[..header_len])?;
+        Self::decode_payload(&payload)
+    }
+
+    /// Writes the secrets back into `store` as a single transaction.
+    ///
+    /// The archive must have been made from `prefix`, so a backup can't overwrite
+    /// secrets outside of the namespace it is restored into. Returns the number of
+    /// restored secrets.
+    pub fn restore(
+        &self,
+        store: &mut impl SecretStore,
+        prefix: &[u8],
+    ) -> Result<usize, BackupError> {
+        if self.prefix != prefix {
+            return Err(BackupError::NamespaceMismatch);
+        }
+        let mut transaction = SecretTransaction::new();
+        for (key, value) in &self.entries {
+            transaction.set(key, value);
+        }
+        store
+            .commit_secrets(&transaction)
+            .map_err(BackupError::Storage)?;
+        Ok(self.entries.len())
+    }
+
+    /// Restores the secrets of `namespace`, checking first that every value decodes.
+    pub fn restore_namespace<T>(
+        &self,
+        store: &mut impl SecretStore,
+        namespace: &SecretNamespace<T>,
+    ) -> Result<usize, BackupError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let prefix = namespace.raw_key(&[]);
+        if self.prefix != prefix {
+            return Err(BackupError::NamespaceMismatch);
+        }
+        for (_, value) in &self.entries {
+            namespace.decode(value)?;
+        }
+        self.restore(store, &prefix)
+    }
+
+    fn encode_payload(&self) -> Vec<u8> {
+        fn put(buf: &mut Vec<u8>, bytes: &[u8]) {
+            buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            buf.extend_from_slice(bytes);
+        }
+        let mut buf = Vec::new();
+        put(&mut buf, &self.prefix);
+        for (key, value) in &self.entries {
+            put(&mut buf, key);
+            put(&mut buf, value);
+        }
+        buf
+    }
+
+    fn decode_payload(payload: &[u8]) -> Result<Self, BackupError> {
+        let mut reader = Reader(payload);
+        let prefix = reader.bytes()?.to_vec();
+        let mut entries = Vec::new();
+        while !reader.0.is_empty() {
+            let key = reader.bytes()?;
+            if !key.starts_with(&prefix) {
+                return Err(BackupError::OutsideNamespace);
+            }
+            entries.push((key.to_vec(), reader.bytes()?.to_vec()));
+        }
+        Ok(Self { prefix, entries })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BackupError> {
+        if self.0.len() < len {
+            return Err(BackupError::Malformed("truncated"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, BackupError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], BackupError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    const FAST: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn store() -> (BTreeMap<Vec<u8>, Vec<u8>>, SecretNamespace<String>) {
+        let mut store = BTreeMap::new();
+        let wallet = SecretNamespace::<String>::new("wallet").unwrap();
+        wallet
+            .set(&mut store, b"main", &"key-1".to_owned())
+            .unwrap();
+        wallet
+            .set(&mut store, b"spare", &"key-2".to_owned())
+            .unwrap();
+        store.set_secret(b"unrelated", b"x");
+        (store, wallet)
+    }
+
+    #[test]
+    fn password_archive_roundtrip() {
+        let (store, wallet) = store();
+        let backup = SecretBackup::from_namespace(&store, &wallet);
+        assert_eq!(backup.len(), 2);
+        let protection = BackupProtection::Password {
+            password: b"hunter2",
+            params: FAST,
+        };
+        let archive = backup.seal_with(protection, &[1; 24], &[2; 16]).unwrap();
+        assert_eq!(&archive[..6], b"FNSB\x01\x01");
+
+        let opened = SecretBackup::open(&archive, protection).unwrap();
+        assert_eq!(opened, backup);
+
+        let mut restored = BTreeMap::new();
+        assert_eq!(opened.restore_namespace(&mut restored, &wallet).unwrap(), 2);
+        assert_eq!(
+            wallet.get(&restored, b"spare").unwrap().as_deref(),
+            Some("key-2")
+        );
+        assert!(!restored.has_secret(b"unrelated"));
+    }
+
+    #[test]
+    fn wrong_password_or_tampering_is_rejected() {
+        let (store, wallet) = store();
+        let backup = SecretBackup::from_namespace(&store, &wallet);
+        let protection = BackupProtection::Password {
+            password: b"hunter2",
+            params: FAST,
+        };
+        let archive = backup.seal_with(protection, &[1; 24], &[2; 16]).unwrap();
+
+        let wrong = BackupProtection::Password {
+            password: b"hunter3",
+            params: FAST,
+        };
+        assert!(matches!(
+            SecretBackup::open(&archive, wrong),
+            Err(BackupError::Crypto(CryptoError::Open))
+        ));
+
+        // the kdf parameters are authenticated
+        let mut tampered = archive.clone();
+        tampered[10] ^= 1;
+        assert!(SecretBackup::open(&tampered, protection).is_err());
+
+        let key = SymmetricKey::from_bytes([7; 32]);
+        assert!(matches!(
+            SecretBackup::open(&archive, BackupProtection::Key(&key)),
+            Err(BackupError::WrongProtection("password"))
+        ));
+        assert!(matches!(
+            SecretBackup::open(&archive[..20], protection),
+            Err(BackupError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn key_archive_is_restored_only_into_its_namespace() {
+        let (store, wallet) = store();
+        let key = SymmetricKey::from_bytes([7; 32]);
+        let archive = SecretBackup::from_namespace(&store, &wallet)
+            .seal_with(BackupProtection::Key(&key), &[3; 24], &[0; 16])
+            .unwrap();
+        let opened = SecretBackup::open(&archive, BackupProtection::Key(&key)).unwrap();
+
+        let other = SecretNamespace::<String>::new("other").unwrap();
+        let mut restored = BTreeMap::new();
+        assert!(matches!(
+            opened.restore_namespace(&mut restored, &other),
+            Err(BackupError::NamespaceMismatch)
+        ));
+        assert!(restored.is_empty());
+
+        let typed_differently = SecretNamespace::<String>::new("wallet")
+            .unwrap()
+            .with_version(1);
+        assert!(matches!(
+            opened.restore_namespace(&mut restored, &typed_differently),
+            Err(BackupError::Secret(SecretError::UnsupportedVersion { .. }))
+        ));
+        assert!(restored.is_empty());
+    }
+}
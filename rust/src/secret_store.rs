@@ -0,0 +1,456 @@
+//! Typed, namespaced access to delegate secrets.
+//!
+//! [`DelegateCtx`] stores secrets as raw bytes under raw byte keys. A
+//! [`SecretNamespace`] groups secrets of one type under a common key prefix and takes care
+//! of the encoding of the values:
+//!
+//! ```ignore
+//! let sessions = SecretNamespace::<Session>::new("sessions")?;
+//! sessions.set(ctx, b"alice", &session)?;
+//! for entry in sessions.iter(ctx) {
+//!     let (user, session) = entry?;
+//!     // ...
+//! }
+//! ```
+//!
+//! # Storage format
+//!
+//! The raw key of a secret is the namespace length as a single byte, the namespace and
+//! the key within the namespace. The length prefix keeps namespaces from overlapping,
+//! e.g. the keys of `"a"` never show up when listing `"ab"`.
+//!
+//! Values are stored as a 4 byte little endian schema version followed by the bincode
+//! encoded value. Namespaces declare the version they write with
+//! [`SecretNamespace::with_version`], values stored by older versions of a delegate are
+//! decoded by the function registered with [`SecretNamespace::with_migration`].
+//!
+//! All operations work over any [`SecretStore`]. Besides [`DelegateCtx`] it is
+//! implemented for a `BTreeMap<Vec<u8>, Vec<u8>>`, to test delegates natively.
+
+use std::{collections::BTreeMap, marker::PhantomData, time::Duration};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    delegate_host::{DelegateCtx, HostError, SecretOp, SecretTransaction},
+    delegate_interface::DelegateError,
+};
+
+/// Storage for delegate secrets.
+pub trait SecretStore {
+    fn get_secret(&self, key: &[u8]) -> Option<Vec<u8>>;
+    /// Returns `true` on success.
+    fn set_secret(&mut self, key: &[u8], value: &[u8]) -> bool;
+    /// Store a secret which expires after `ttl`, see [`DelegateCtx::set_secret_with_ttl`].
+    ///
+    /// Stores without expiry support, like the default implementation, keep the secret
+    /// until it is removed.
+    fn set_secret_with_ttl(&mut self, key: &[u8], value: &[u8], ttl: Duration) -> bool {
+        let _ = ttl;
+        self.set_secret(key, value)
+    }
+    fn has_secret(&self, key: &[u8]) -> bool;
+    /// Returns `true` if the secret existed and was removed.
+    fn remove_secret(&mut self, key: &[u8]) -> bool;
+    /// Keys of the stored secrets starting with `prefix`.
+    fn list_secrets(&self, prefix: &[u8]) -> Vec<Vec<u8>>;
+    /// Applies all the operations of `transaction` or none, see
+    /// [`DelegateCtx::commit_secrets`] for the errors.
+    fn commit_secrets(&mut self, transaction: &SecretTransaction) -> Result<(), HostError>;
+}
+
+impl SecretStore for DelegateCtx {
+    fn get_secret(&self, key: &[u8]) -> Option<Vec<u8>> {
+        DelegateCtx::get_secret(self, key)
+    }
+
+    fn set_secret(&mut self, key: &[u8], value: &[u8]) -> bool {
+        DelegateCtx::set_secret(self, key, value)
+    }
+
+    fn set_secret_with_ttl(&mut self, key: &[u8], value: &[u8], ttl: Duration) -> bool {
+        DelegateCtx::set_secret_with_ttl(self, key, value, ttl)
+    }
+
+    fn has_secret(&self, key: &[u8]) -> bool {
+        DelegateCtx::has_secret(self, key)
+    }
+
+    fn remove_secret(&mut self, key: &[u8]) -> bool {
+        DelegateCtx::remove_secret(self, key)
+    }
+
+    fn list_secrets(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+        DelegateCtx::list_secrets(self, prefix)
+    }
+
+    fn commit_secrets(&mut self, transaction: &SecretTransaction) -> Result<(), HostError> {
+        DelegateCtx::commit_secrets(self, transaction)
+    }
+}
+
+impl SecretStore for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn get_secret(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.get(key).cloned()
+    }
+
+    fn set_secret(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.insert(key.to_vec(), value.to_vec());
+        true
+    }
+
+    fn has_secret(&self, key: &[u8]) -> bool {
+        self.contains_key(key)
+    }
+
+    fn remove_secret(&mut self, key: &[u8]) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn list_secrets(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+        self.range(prefix.to_vec()..)
+            .map(|(k, _)| k)
+            .take_while(|k| k.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn commit_secrets(&mut self, transaction: &SecretTransaction) -> Result<(), HostError> {
+        // writes to the map can't fail, so applying in order is already atomic
+        for op in transaction.ops() {
+            match op {
+                SecretOp::Set { key, value } => {
+                    self.insert(key.clone(), value.clone());
+                }
+                SecretOp::Remove { key } => {
+                    self.remove(key);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum SecretError {
+    #[error("invalid namespace: {0}")]
+    InvalidNamespace(String),
+    #[error("failed to encode or decode secret: {0}")]
+    Encoding(String),
+    #[error("secret stored with schema version {found}, expected {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("failed to store secret")]
+    StorageFailed,
+}
+
+impl From<bincode::Error> for SecretError {
+    fn from(err: bincode::Error) -> Self {
+        SecretError::Encoding(format!("{err}"))
+    }
+}
+
+impl From<SecretError> for DelegateError {
+    fn from(err: SecretError) -> Self {
+        match err {
+            SecretError::Encoding(reason) => DelegateError::Deser(reason),
+            other => DelegateError::Other(format!("{other}")),
+        }
+    }
+}
+
+/// Decodes a value stored with an older schema version, see [`SecretNamespace::with_migration`].
+pub type Migration<T> = fn(version: u32, value: &[u8]) -> Result<T, SecretError>;
+
+/// A group of secrets of type `T` sharing a key prefix.
+pub struct SecretNamespace<T> {
+    prefix: Vec<u8>,
+    version: u32,
+    migration: Option<Migration<T>>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for SecretNamespace<T> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix.clone(),
+            version: self.version,
+            migration: self.migration,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for SecretNamespace<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretNamespace")
+            .field("name", &String::from_utf8_lossy(&self.prefix[1..]))
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> SecretNamespace<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Creates a namespace, names must be between 1 and 255 bytes long.
+    pub fn new(name: impl AsRef<[u8]>) -> Result<Self, SecretError> {
+        let name = name.as_ref();
+        let len = u8::try_from(name.len())
+            .ok()
+            .filter(|len| *len > 0)
+            .ok_or_else(|| {
+                SecretError::InvalidNamespace(format!(
+                    "length must be between 1 and 255 bytes, got {}",
+                    name.len()
+                ))
+            })?;
+        let mut prefix = Vec::with_capacity(name.len() + 1);
+        prefix.push(len);
+        prefix.extend_from_slice(name);
+        Ok(Self {
+            prefix,
+            version: 0,
+            migration: None,
+            _value: PhantomData,
+        })
+    }
+
+    /// Sets the schema version values are written with, `0` by default.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets how values stored with an older schema version are decoded.
+    ///
+    /// Without a migration, reading such values fails with
+    /// [`SecretError::UnsupportedVersion`]. Migrated values are not written back.
+    pub fn with_migration(mut self, migration: Migration<T>) -> Self {
+        self.migration = Some(migration);
+        self
+    }
+
+    pub fn name(&self) -> &[u8] {
+        &self.prefix[1..]
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The raw key of `key` within this namespace.
+    pub fn raw_key(&self, key: &[u8]) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.prefix.len() + key.len());
+        raw.extend_from_slice(&self.prefix);
+        raw.extend_from_slice(key);
+        raw
+    }
+
+    pub fn get(&self, store: &impl SecretStore, key: &[u8]) -> Result<Option<T>, SecretError> {
+        store
+            .get_secret(&self.raw_key(key))
+            .map(|bytes| self.decode(&bytes))
+            .transpose()
+    }
+
+    pub fn set(
+        &self,
+        store: &mut impl SecretStore,
+        key: &[u8],
+        value: &T,
+    ) -> Result<(), SecretError> {
+        let bytes = self.encode(value)?;
+        if store.set_secret(&self.raw_key(key), &bytes) {
+            Ok(())
+        } else {
+            Err(SecretError::StorageFailed)
+        }
+    }
+
+    pub fn contains(&self, store: &impl SecretStore, key: &[u8]) -> bool {
+        store.has_secret(&self.raw_key(key))
+    }
+
+    /// Returns `true` if the secret existed and was removed.
+    pub fn remove(&self, store: &mut impl SecretStore, key: &[u8]) -> bool {
+        store.remove_secret(&self.raw_key(key))
+    }
+
+    /// Stages setting `key` to `value` in `transaction`.
+    pub fn stage_set(
+        &self,
+        transaction: &mut SecretTransaction,
+        key: &[u8],
+        value: &T,
+    ) -> Result<(), SecretError> {
+        transaction.set(&self.raw_key(key), &self.encode(value)?);
+        Ok(())
+    }
+
+    /// Stages removing `key` in `transaction`.
+    pub fn stage_remove(&self, transaction: &mut SecretTransaction, key: &[u8]) {
+        transaction.remove(&self.raw_key(key));
+    }
+
+    /// Keys of the secrets in this namespace, without the namespace prefix.
+    pub fn keys(&self, store: &impl SecretStore) -> Vec<Vec<u8>> {
+        store
+            .list_secrets(&self.prefix)
+            .into_iter()
+            .filter_map(|raw| raw.strip_prefix(self.prefix.as_slice()).map(<[u8]>::to_vec))
+            .collect()
+    }
+
+    /// Iterates over the secrets in this namespace.
+    ///
+    /// Secrets removed while iterating are skipped.
+    pub fn iter<'a, S: SecretStore>(
+        &'a self,
+        store: &'a S,
+    ) -> impl Iterator<Item = Result<(Vec<u8>, T), SecretError>> + 'a {
+        self.keys(store).into_iter().filter_map(move |key| {
+            self.get(store, &key)
+                .transpose()
+                .map(|value| value.map(|value| (key, value)))
+        })
+    }
+
+    fn encode(&self, value: &T) -> Result<Vec<u8>, SecretError> {
+        let mut bytes = self.version.to_le_bytes().to_vec();
+        bincode::serialize_into(&mut bytes, value)?;
+        Ok(bytes)
+    }
+
+    pub(crate) fn decode(&self, bytes: &[u8]) -> Result<T, SecretError> {
+        let Some((version, value)) = bytes.split_first_chunk::<4>() else {
+            return Err(SecretError::Encoding("missing schema version".into()));
+        };
+        let version = u32::from_le_bytes(*version);
+        match self.migration {
+            _ if version == self.version => Ok(bincode::deserialize(value)?),
+            Some(migrate) if version < self.version => migrate(version, value),
+            _ => Err(SecretError::UnsupportedVersion {
+                found: version,
+                expected: self.version,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        token: String,
+        uses: u32,
+    }
+
+    fn session(token: &str) -> Session {
+        Session {
+            token: token.into(),
+            uses: 0,
+        }
+    }
+
+    #[test]
+    fn namespaces_do_not_overlap() {
+        let mut store = BTreeMap::new();
+        let a = SecretNamespace::<Session>::new("a").unwrap();
+        let ab = SecretNamespace::<Session>::new("ab").unwrap();
+        a.set(&mut store, b"b1", &session("x")).unwrap();
+        ab.set(&mut store, b"1", &session("y")).unwrap();
+
+        assert_eq!(a.keys(&store), vec![b"b1".to_vec()]);
+        assert_eq!(ab.keys(&store), vec![b"1".to_vec()]);
+        assert_eq!(a.get(&store, b"b1").unwrap(), Some(session("x")));
+        assert_eq!(ab.get(&store, b"1").unwrap(), Some(session("y")));
+        assert!(a.remove(&mut store, b"b1"));
+        assert!(!a.contains(&store, b"b1"));
+        assert!(ab.contains(&store, b"1"));
+    }
+
+    #[test]
+    fn iterates_over_namespace() {
+        let mut store = BTreeMap::new();
+        let sessions = SecretNamespace::<Session>::new("sessions").unwrap();
+        sessions.set(&mut store, b"alice", &session("1")).unwrap();
+        sessions.set(&mut store, b"bob", &session("2")).unwrap();
+        store.set_secret(b"unrelated", b"");
+
+        let entries = sessions
+            .iter(&store)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (b"alice".to_vec(), session("1")),
+                (b"bob".to_vec(), session("2"))
+            ]
+        );
+    }
+
+    #[test]
+    fn older_versions_are_migrated() {
+        let mut store = BTreeMap::new();
+        let v0 = SecretNamespace::<String>::new("sessions").unwrap();
+        v0.set(&mut store, b"alice", &"token".to_owned()).unwrap();
+
+        let v1 = SecretNamespace::<Session>::new("sessions")
+            .unwrap()
+            .with_version(1);
+        assert!(matches!(
+            v1.get(&store, b"alice"),
+            Err(SecretError::UnsupportedVersion {
+                found: 0,
+                expected: 1
+            })
+        ));
+
+        let v1 = v1.with_migration(|version, bytes| {
+            assert_eq!(version, 0);
+            Ok(session(&bincode::deserialize::<String>(bytes)?))
+        });
+        assert_eq!(v1.get(&store, b"alice").unwrap(), Some(session("token")));
+        assert!(matches!(v0.get(&store, b"missing"), Ok(None)));
+
+        // values from a newer schema are never decoded
+        v1.set(&mut store, b"bob", &session("2")).unwrap();
+        assert!(matches!(
+            v0.get(&store, b"bob"),
+            Err(SecretError::UnsupportedVersion {
+                found: 1,
+                expected: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn staged_operations_apply_on_commit() {
+        let mut store = BTreeMap::new();
+        let keys = SecretNamespace::<Session>::new("keys").unwrap();
+        keys.set(&mut store, b"old", &session("1")).unwrap();
+
+        let mut tx = SecretTransaction::new();
+        keys.stage_set(&mut tx, b"new", &session("2")).unwrap();
+        keys.stage_remove(&mut tx, b"old");
+        assert!(keys.contains(&store, b"old"));
+        assert!(!keys.contains(&store, b"new"));
+
+        store.commit_secrets(&tx).unwrap();
+        assert_eq!(keys.keys(&store), vec![b"new".to_vec()]);
+    }
+
+    #[test]
+    fn namespace_name_length_is_checked() {
+        assert!(SecretNamespace::<()>::new("").is_err());
+        assert!(SecretNamespace::<()>::new([b'a'; 256]).is_err());
+        assert!(SecretNamespace::<()>::new([b'a'; 255]).is_ok());
+    }
+}
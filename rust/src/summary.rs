@@ -0,0 +1,374 @@
+//! Compact summaries of states made of entries, and the helpers to pick the entries a
+//! peer is missing from them.
+//!
+//! - [`BloomFilter`] summarizes the ids of the entries of a state, with a tunable false
+//!   positive rate. Entries whose id is not in a peer's filter are surely missing there.
+//!   A false positive skips an entry the peer lacks, changing the seed between rounds
+//!   lets it through eventually.
+//! - [`AuthorVector`] summarizes states whose entries are numbered by author, holding
+//!   the highest number seen from every author.
+//!
+//! ```
+//! use freenet_stdlib::summary::{BloomFilter, SummaryEntry};
+//!
+//! struct Message(u32);
+//!
+//! impl SummaryEntry for Message {
+//!     fn entry_id(&self) -> Vec<u8> {
+//!         self.0.to_be_bytes().to_vec()
+//!     }
+//! }
+//!
+//! let local: Vec<_> = (0..100).map(Message).collect();
+//! let remote = BloomFilter::from_entries(&local[..90], 0.01, 0);
+//! let missing = remote.missing(&local);
+//! assert!(missing.len() >= 10 - 1 && missing.iter().all(|m| m.0 >= 90));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    contract_composition::Mergeable,
+    contract_interface::{ContractError, StateSummary},
+};
+
+/// An entry of a state, identified by a byte string.
+pub trait SummaryEntry {
+    fn entry_id(&self) -> Vec<u8>;
+}
+
+/// An entry of a state numbered by its author, starting from 1.
+pub trait AuthoredEntry {
+    type Author: Ord + Clone;
+
+    fn author(&self) -> &Self::Author;
+
+    fn sequence(&self) -> u64;
+}
+
+/// Bloom filter of entry ids.
+///
+/// Filters without bits or hash functions are rejected when deserialized.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawBloomFilter")]
+pub struct BloomFilter {
+    #[serde_as(as = "serde_with::Bytes")]
+    bits: Vec<u8>,
+    hashes: u8,
+    seed: u64,
+}
+
+/// [`BloomFilter`] as received, before it is checked.
+#[serde_as]
+#[derive(Deserialize)]
+struct RawBloomFilter {
+    #[serde_as(as = "serde_with::Bytes")]
+    bits: Vec<u8>,
+    hashes: u8,
+    seed: u64,
+}
+
+impl TryFrom<RawBloomFilter> for BloomFilter {
+    type Error = String;
+
+    fn try_from(raw: RawBloomFilter) -> Result<Self, Self::Error> {
+        if raw.bits.is_empty() || raw.hashes == 0 {
+            return Err("bloom filter without bits or hash functions".into());
+        }
+        Ok(Self {
+            bits: raw.bits,
+            hashes: raw.hashes,
+            seed: raw.seed,
+        })
+    }
+}
+
+impl BloomFilter {
+    /// Creates a filter sized for `expected_entries` at `false_positive_rate`.
+    ///
+    /// Filters built with different seeds hash ids differently, so their false positives
+    /// don't overlap.
+    pub fn new(expected_entries: usize, false_positive_rate: f64, seed: u64) -> Self {
+        let entries = expected_entries.max(1) as f64;
+        let rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-entries * rate.ln() / (ln2 * ln2)).ceil().max(8.0);
+        let hashes = (bits / entries * ln2).round().clamp(1.0, 32.0);
+        Self {
+            bits: vec![0; (bits as usize).div_ceil(8)],
+            hashes: hashes as u8,
+            seed,
+        }
+    }
+
+    /// Builds a filter of the ids of `entries`.
+    pub fn from_entries<'a, E>(
+        entries: impl IntoIterator<Item = &'a E, IntoIter: ExactSizeIterator>,
+        false_positive_rate: f64,
+        seed: u64,
+    ) -> Self
+    where
+        E: SummaryEntry + 'a,
+    {
+        let entries = entries.into_iter();
+        let mut filter = Self::new(entries.len(), false_positive_rate, seed);
+        for entry in entries {
+            filter.insert(&entry.entry_id());
+        }
+        filter
+    }
+
+    pub fn insert(&mut self, id: &[u8]) {
+        for bit in self.bit_indexes(id) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Whether `id` may have been inserted, `false` if it surely wasn't.
+    pub fn contains(&self, id: &[u8]) -> bool {
+        self.bit_indexes(id)
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// The entries whose ids are not in the filter.
+    pub fn missing<'a, E>(&self, entries: impl IntoIterator<Item = &'a E>) -> Vec<&'a E>
+    where
+        E: SummaryEntry + 'a,
+    {
+        entries
+            .into_iter()
+            .filter(|entry| !self.contains(&entry.entry_id()))
+            .collect()
+    }
+
+    /// Size of the filter in bytes.
+    pub fn size(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn to_summary(&self) -> Result<StateSummary<'static>, ContractError> {
+        to_summary(self)
+    }
+
+    pub fn from_summary(summary: &StateSummary<'_>) -> Result<Self, ContractError> {
+        from_summary(summary)
+    }
+
+    fn bit_indexes(&self, id: &[u8]) -> impl Iterator<Item = usize> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.seed.to_le_bytes());
+        hasher.update(id);
+        let hash = hasher.finalize();
+        let (first, second) = hash.as_bytes().split_at(8);
+        let first = u64::from_le_bytes(first.try_into().expect("8 bytes"));
+        let second = u64::from_le_bytes(second[..8].try_into().expect("8 bytes")) | 1;
+        let bits = (self.bits.len() * 8) as u64;
+        (0..u64::from(self.hashes))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % bits) as usize)
+    }
+}
+
+/// The highest entry number seen from every author.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "A: Serialize", deserialize = "A: Deserialize<'de> + Ord"))]
+pub struct AuthorVector<A> {
+    #[serde_as(as = "Vec<(_, _)>")]
+    sequences: BTreeMap<A, u64>,
+}
+
+impl<A> Default for AuthorVector<A> {
+    fn default() -> Self {
+        Self {
+            sequences: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A: Ord + Clone> AuthorVector<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the vector of the entries of a state.
+    pub fn from_entries<'a, E>(entries: impl IntoIterator<Item = &'a E>) -> Self
+    where
+        E: AuthoredEntry<Author = A> + 'a,
+    {
+        let mut vector = Self::new();
+        for entry in entries {
+            vector.observe(entry.author(), entry.sequence());
+        }
+        vector
+    }
+
+    /// The highest entry number seen from `author`, 0 if none.
+    pub fn get(&self, author: &A) -> u64 {
+        self.sequences.get(author).copied().unwrap_or_default()
+    }
+
+    pub fn observe(&mut self, author: &A, sequence: u64) {
+        match self.sequences.get_mut(author) {
+            Some(seen) => *seen = (*seen).max(sequence),
+            None => {
+                self.sequences.insert(author.clone(), sequence);
+            }
+        }
+    }
+
+    /// The entries numbered past the ones seen from their authors.
+    pub fn missing<'a, E>(&self, entries: impl IntoIterator<Item = &'a E>) -> Vec<&'a E>
+    where
+        E: AuthoredEntry<Author = A> + 'a,
+    {
+        entries
+            .into_iter()
+            .filter(|entry| entry.sequence() > self.get(entry.author()))
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&A, u64)> {
+        self.sequences.iter().map(|(author, seq)| (author, *seq))
+    }
+}
+
+impl<A: Serialize> AuthorVector<A> {
+    pub fn to_summary(&self) -> Result<StateSummary<'static>, ContractError> {
+        to_summary(self)
+    }
+}
+
+impl<A: DeserializeOwned + Ord> AuthorVector<A> {
+    pub fn from_summary(summary: &StateSummary<'_>) -> Result<Self, ContractError> {
+        from_summary(summary)
+    }
+}
+
+impl<A: Ord + Clone> Mergeable<AuthorVector<A>> for AuthorVector<A> {
+    fn merge(&mut self, other: AuthorVector<A>) {
+        for (author, sequence) in other.sequences {
+            self.observe(&author, sequence);
+        }
+    }
+}
+
+fn to_summary<T: Serialize>(value: &T) -> Result<StateSummary<'static>, ContractError> {
+    Ok(bincode::serialize(value)?.into())
+}
+
+fn from_summary<T: DeserializeOwned>(summary: &StateSummary<'_>) -> Result<T, ContractError> {
+    Ok(bincode::deserialize(summary.as_ref())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Post {
+        author: [u8; 32],
+        sequence: u64,
+    }
+
+    impl SummaryEntry for Post {
+        fn entry_id(&self) -> Vec<u8> {
+            let mut id = self.author.to_vec();
+            id.extend(self.sequence.to_be_bytes());
+            id
+        }
+    }
+
+    impl AuthoredEntry for Post {
+        type Author = [u8; 32];
+
+        fn author(&self) -> &[u8; 32] {
+            &self.author
+        }
+
+        fn sequence(&self) -> u64 {
+            self.sequence
+        }
+    }
+
+    fn posts(authors: u8, per_author: u64) -> Vec<Post> {
+        (0..authors)
+            .flat_map(|author| {
+                (1..=per_author).map(move |sequence| Post {
+                    author: [author; 32],
+                    sequence,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bloom_filter_false_positive_rate() {
+        let entries = posts(10, 100);
+        let filter = BloomFilter::from_entries(&entries, 0.01, 7);
+        assert!(filter.missing(&entries).is_empty());
+        // roughly 9.6 bits per entry
+        assert!(filter.size() < 1300);
+
+        let others = posts(20, 1000);
+        let false_positives = others[10_000..]
+            .iter()
+            .filter(|post| filter.contains(&post.entry_id()))
+            .count();
+        assert!(false_positives < 200, "{false_positives} false positives");
+
+        let decoded = BloomFilter::from_summary(&filter.to_summary().unwrap()).unwrap();
+        assert_eq!(decoded, filter);
+    }
+
+    #[test]
+    fn degenerate_bloom_filters_are_rejected() {
+        let mut filter = BloomFilter::new(10, 0.01, 0);
+        filter.bits.clear();
+        assert!(matches!(
+            BloomFilter::from_summary(&filter.to_summary().unwrap()),
+            Err(ContractError::Deser(_))
+        ));
+
+        let mut filter = BloomFilter::new(10, 0.01, 0);
+        filter.hashes = 0;
+        assert!(matches!(
+            BloomFilter::from_summary(&filter.to_summary().unwrap()),
+            Err(ContractError::Deser(_))
+        ));
+
+        // also when nested in the summary of a typed contract
+        #[derive(Serialize, Deserialize)]
+        struct Summary {
+            version: u32,
+            seen: BloomFilter,
+        }
+        let mut seen = BloomFilter::new(10, 0.01, 0);
+        seen.bits.clear();
+        let encoded = bincode::serialize(&Summary { version: 1, seen }).unwrap();
+        assert!(bincode::deserialize::<Summary>(&encoded).is_err());
+    }
+
+    #[test]
+    fn author_vector_picks_newer_entries() {
+        let local = posts(3, 10);
+        let remote: Vec<_> = posts(3, 10)
+            .into_iter()
+            .filter(|post| post.author[0] != 1 || post.sequence <= 4)
+            .collect();
+        let summary = AuthorVector::from_entries(&remote);
+        assert_eq!(summary.get(&[1; 32]), 4);
+
+        let missing = summary.missing(&local);
+        assert_eq!(missing.len(), 6);
+        assert!(missing.iter().all(|post| post.author == [1; 32]));
+
+        let mut merged = summary.clone();
+        merged.merge(AuthorVector::from_entries(&local));
+        assert!(merged.missing(&local).is_empty());
+        let decoded = AuthorVector::from_summary(&merged.to_summary().unwrap()).unwrap();
+        assert_eq!(decoded, merged);
+    }
+}
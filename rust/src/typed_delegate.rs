@@ -0,0 +1,413 @@
+//! Typed counterpart of [`DelegateInterface`](crate::prelude::DelegateInterface), analogous to what
+//! [`TypedContract`](crate::typed_contract::TypedContract) is for contracts.
+//!
+//! A [`TypedDelegate`] works with decoded requests, responses, parameters and context
+//! instead of raw bytes. Application messages are decoded into
+//! [`TypedDelegate::Request`] and every [`TypedOutbound::Response`] is encoded back into
+//! an [`ApplicationMessage`]. The context is kept in the host managed context
+//! (see [`DelegateCtx::read`]), decoded before processing a message and written back
+//! afterwards if it changed. Contexts too large for the host are spilled to the secrets
+//! with [`DelegateContext::spill`] and restored on the next message, the previous spill
+//! is released whenever the context changes.
+//!
+//! The encoders for each of the types are picked by a [`DelegateEncodingAdapter`]. The
+//! `#[delegate]` macro implements it when given an encoder, and wires
+//! the typed implementation to `DelegateInterface` and the wasm entry point:
+//!
+//! ```ignore
+//! use freenet_stdlib::{prelude::*, typed_delegate::*};
+//!
+//! struct Signer;
+//!
+//! #[delegate(encoder = BincodeEncoder)]
+//! impl TypedDelegate for Signer {
+//!     type Parameters = ();
+//!     type Request = SignRequest;
+//!     type Response = SignResponse;
+//!     type Context = ();
+//!
+//!     fn process(
+//!         ctx: &mut DelegateCtx,
+//!         _params: (),
+//!         _origin: Option<MessageOrigin>,
+//!         _context: &mut (),
+//!         request: SignRequest,
+//!     ) -> Result<Vec<TypedOutbound<SignResponse>>, DelegateError> {
+//!         let key = ctx.get_secret(b"key").ok_or_else(|| DelegateError::Other("no key".into()))?;
+//!         Ok(vec![TypedOutbound::Response(sign(&key, request))])
+//!     }
+//! }
+//! ```
+//!
+//! Without an encoder argument [`DelegateEncodingAdapter`] must be implemented by hand.
+
+use crate::{
+    delegate_host::DelegateCtx,
+    delegate_interface::{
+        ApplicationMessage, DelegateContext, DelegateError, InboundDelegateMsg, MessageOrigin,
+        OutboundDelegateMsg,
+    },
+    parameters::Parameters,
+    prelude::ContractError,
+    secret_store::SecretStore,
+    typed_contract::Encoder,
+};
+
+/// A delegate working with typed messages.
+pub trait TypedDelegate {
+    type Parameters;
+    /// Payload of the application messages received by the delegate.
+    type Request;
+    /// Payload of the application messages sent back by the delegate.
+    type Response;
+    /// State kept across the messages of a batch. Starts as the default value.
+    type Context: Default;
+
+    /// Process an application message request.
+    fn process(
+        ctx: &mut DelegateCtx,
+        parameters: Self::Parameters,
+        origin: Option<MessageOrigin>,
+        context: &mut Self::Context,
+        request: Self::Request,
+    ) -> Result<Vec<TypedOutbound<Self::Response>>, DelegateError>;
+
+    /// Process any inbound message other than an application message, like the responses
+    /// to contract requests or user input.
+    ///
+    /// By default these are rejected.
+    fn process_other(
+        _ctx: &mut DelegateCtx,
+        _parameters: Self::Parameters,
+        _origin: Option<MessageOrigin>,
+        _context: &mut Self::Context,
+        message: InboundDelegateMsg<'static>,
+    ) -> Result<Vec<TypedOutbound<Self::Response>>, DelegateError> {
+        Err(DelegateError::Other(format!(
+            "unexpected inbound message: {message:?}"
+        )))
+    }
+}
+
+/// The encoders used for each of the types of a [`TypedDelegate`].
+pub trait DelegateEncodingAdapter: TypedDelegate {
+    type ParametersEncoder: Encoder<Self::Parameters>;
+    type RequestEncoder: Encoder<Self::Request>;
+    type ResponseEncoder: Encoder<Self::Response>;
+    type ContextEncoder: Encoder<Self::Context>;
+}
+
+/// Message produced by a [`TypedDelegate`].
+#[derive(Debug)]
+pub enum TypedOutbound<R> {
+    /// A response, sent as a processed application message.
+    Response(R),
+    /// Any other outbound message, like contract requests.
+    Raw(OutboundDelegateMsg),
+}
+
+impl<R> From<OutboundDelegateMsg> for TypedOutbound<R> {
+    fn from(msg: OutboundDelegateMsg) -> Self {
+        TypedOutbound::Raw(msg)
+    }
+}
+
+fn encoding_err(err: impl Into<ContractError>) -> DelegateError {
+    match err.into() {
+        ContractError::Deser(reason) => DelegateError::Deser(reason),
+        other => DelegateError::Deser(other.to_string()),
+    }
+}
+
+/// Host side of a delegate as seen by [`inner_process`]: the managed context and the
+/// secrets its spills go to.
+trait ContextHost: SecretStore {
+    fn read_context(&self) -> Vec<u8>;
+
+    fn write_context(&mut self, bytes: &[u8]) -> bool;
+
+    fn delegate_ctx(&mut self) -> &mut DelegateCtx;
+}
+
+impl ContextHost for DelegateCtx {
+    fn read_context(&self) -> Vec<u8> {
+        self.read()
+    }
+
+    fn write_context(&mut self, bytes: &[u8]) -> bool {
+        self.write(bytes)
+    }
+
+    fn delegate_ctx(&mut self) -> &mut DelegateCtx {
+        self
+    }
+}
+
+/// Implementation of `DelegateInterface::process` for a [`TypedDelegate`].
+pub fn inner_process<T>(
+    ctx: &mut DelegateCtx,
+    parameters: Parameters<'static>,
+    origin: Option<MessageOrigin>,
+    message: InboundDelegateMsg,
+) -> Result<Vec<OutboundDelegateMsg>, DelegateError>
+where
+    T: TypedDelegate + DelegateEncodingAdapter,
+{
+    process_in::<T>(ctx, parameters, origin, message)
+}
+
+fn process_in<T>(
+    host: &mut impl ContextHost,
+    parameters: Parameters<'static>,
+    origin: Option<MessageOrigin>,
+    message: InboundDelegateMsg,
+) -> Result<Vec<OutboundDelegateMsg>, DelegateError>
+where
+    T: TypedDelegate + DelegateEncodingAdapter,
+{
+    let typed_params =
+        <T::ParametersEncoder>::deserialize(parameters.as_ref()).map_err(encoding_err)?;
+    let stored_context = DelegateContext::try_new(host.read_context())?;
+    let restored_context = stored_context.restore(host)?;
+    let mut context = if restored_context.is_empty() {
+        T::Context::default()
+    } else {
+        <T::ContextEncoder>::deserialize(&restored_context).map_err(encoding_err)?
+    };
+
+    let ctx = host.delegate_ctx();
+    let outbound = match message {
+        InboundDelegateMsg::ApplicationMessage(msg) => {
+            let request = <T::RequestEncoder>::deserialize(&msg.payload).map_err(encoding_err)?;
+            T::process(ctx, typed_params, origin, &mut context, request)?
+        }
+        other => T::process_other(ctx, typed_params, origin, &mut context, other.into_owned())?,
+    };
+
+    let encoded_context = <T::ContextEncoder>::serialize(&context).map_err(encoding_err)?;
+    if encoded_context != restored_context {
+        let updated = if encoded_context.is_empty() {
+            DelegateContext::default()
+        } else {
+            DelegateContext::spill(host, encoded_context)?
+        };
+        if !host.write_context(updated.as_ref()) {
+            updated.release(host);
+            return Err(DelegateError::Other(
+                "failed to write the delegate context".into(),
+            ));
+        }
+        stored_context.release(host);
+    }
+
+    outbound
+        .into_iter()
+        .map(|msg| match msg {
+            TypedOutbound::Response(response) => {
+                let payload = <T::ResponseEncoder>::serialize(&response).map_err(encoding_err)?;
+                Ok(OutboundDelegateMsg::ApplicationMessage(
+                    ApplicationMessage::new(payload).processed(true),
+                ))
+            }
+            TypedOutbound::Raw(msg) => Ok(msg),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use crate::delegate_host::{HostError, SecretTransaction};
+    use crate::delegate_interface::GetContractResponse;
+    use crate::prelude::ContractInstanceId;
+    use crate::typed_contract::BincodeEncoder;
+
+    struct Length;
+
+    impl TypedDelegate for Length {
+        type Parameters = u32;
+        type Request = String;
+        type Response = u32;
+        type Context = ();
+
+        fn process(
+            _ctx: &mut DelegateCtx,
+            offset: u32,
+            _origin: Option<MessageOrigin>,
+            _context: &mut (),
+            request: String,
+        ) -> Result<Vec<TypedOutbound<u32>>, DelegateError> {
+            Ok(vec![
+                TypedOutbound::Response(request.len() as u32 + offset),
+                OutboundDelegateMsg::ContextUpdated(DelegateContext::default()).into(),
+            ])
+        }
+    }
+
+    impl DelegateEncodingAdapter for Length {
+        type ParametersEncoder = BincodeEncoder<u32>;
+        type RequestEncoder = BincodeEncoder<String>;
+        type ResponseEncoder = BincodeEncoder<u32>;
+        type ContextEncoder = BincodeEncoder<()>;
+    }
+
+    fn process(
+        message: InboundDelegateMsg<'static>,
+    ) -> Result<Vec<OutboundDelegateMsg>, DelegateError> {
+        let params = Parameters::from(bincode::serialize(&1u32).unwrap());
+        inner_process::<Length>(&mut DelegateCtx::default(), params, None, message)
+    }
+
+    #[test]
+    fn request_is_decoded_and_response_encoded() {
+        let payload = bincode::serialize("hello").unwrap();
+        let outbound = process(ApplicationMessage::new(payload).into()).unwrap();
+        assert_eq!(outbound.len(), 2);
+        let OutboundDelegateMsg::ApplicationMessage(response) = &outbound[0] else {
+            panic!("unexpected message: {:?}", outbound[0]);
+        };
+        assert!(response.processed);
+        assert_eq!(bincode::deserialize::<u32>(&response.payload).unwrap(), 6);
+        assert!(matches!(
+            outbound[1],
+            OutboundDelegateMsg::ContextUpdated(_)
+        ));
+    }
+
+    /// Appends the requested number of bytes to its context, answers with its length.
+    struct Notes;
+
+    impl TypedDelegate for Notes {
+        type Parameters = ();
+        type Request = usize;
+        type Response = usize;
+        type Context = Vec<u8>;
+
+        fn process(
+            _ctx: &mut DelegateCtx,
+            _params: (),
+            _origin: Option<MessageOrigin>,
+            context: &mut Vec<u8>,
+            request: usize,
+        ) -> Result<Vec<TypedOutbound<usize>>, DelegateError> {
+            context.extend(std::iter::repeat_n(7, request));
+            Ok(vec![TypedOutbound::Response(context.len())])
+        }
+    }
+
+    impl DelegateEncodingAdapter for Notes {
+        type ParametersEncoder = BincodeEncoder<()>;
+        type RequestEncoder = BincodeEncoder<usize>;
+        type ResponseEncoder = BincodeEncoder<usize>;
+        type ContextEncoder = BincodeEncoder<Vec<u8>>;
+    }
+
+    #[derive(Default)]
+    struct Host {
+        ctx: DelegateCtx,
+        context: Vec<u8>,
+        secrets: BTreeMap<Vec<u8>, Vec<u8>>,
+    }
+
+    impl SecretStore for Host {
+        fn get_secret(&self, key: &[u8]) -> Option<Vec<u8>> {
+            self.secrets.get_secret(key)
+        }
+
+        fn set_secret(&mut self, key: &[u8], value: &[u8]) -> bool {
+            self.secrets.set_secret(key, value)
+        }
+
+        fn has_secret(&self, key: &[u8]) -> bool {
+            self.secrets.has_secret(key)
+        }
+
+        fn remove_secret(&mut self, key: &[u8]) -> bool {
+            self.secrets.remove_secret(key)
+        }
+
+        fn list_secrets(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+            self.secrets.list_secrets(prefix)
+        }
+
+        fn commit_secrets(&mut self, transaction: &SecretTransaction) -> Result<(), HostError> {
+            self.secrets.commit_secrets(transaction)
+        }
+    }
+
+    impl ContextHost for Host {
+        fn read_context(&self) -> Vec<u8> {
+            self.context.clone()
+        }
+
+        fn write_context(&mut self, bytes: &[u8]) -> bool {
+            self.context = bytes.to_vec();
+            true
+        }
+
+        fn delegate_ctx(&mut self) -> &mut DelegateCtx {
+            &mut self.ctx
+        }
+    }
+
+    fn append(host: &mut Host, len: usize) -> usize {
+        let request = ApplicationMessage::new(bincode::serialize(&len).unwrap());
+        let outbound =
+            process_in::<Notes>(host, Parameters::from(vec![]), None, request.into()).unwrap();
+        let [OutboundDelegateMsg::ApplicationMessage(response)] = outbound.as_slice() else {
+            panic!("unexpected messages: {outbound:?}");
+        };
+        bincode::deserialize(&response.payload).unwrap()
+    }
+
+    #[test]
+    fn oversized_context_is_spilled_across_messages() {
+        let mut host = Host::default();
+        let spills = |host: &Host| host.list_secrets(DelegateContext::SPILL_SECRET_PREFIX);
+
+        assert_eq!(append(&mut host, 10), 10);
+        assert!(spills(&host).is_empty(), "small contexts stay inline");
+
+        assert_eq!(
+            append(&mut host, DelegateContext::MAX_SIZE),
+            DelegateContext::MAX_SIZE + 10
+        );
+        assert!(
+            host.context.len() < 64,
+            "only a reference is kept by the host"
+        );
+        let first_spill = spills(&host);
+        assert_eq!(first_spill.len(), 1);
+
+        // restored on the next message, the outdated spill is released
+        assert_eq!(append(&mut host, 1), DelegateContext::MAX_SIZE + 11);
+        let second_spill = spills(&host);
+        assert_eq!(second_spill.len(), 1);
+        assert_ne!(second_spill, first_spill);
+
+        // an unchanged context is not spilled again
+        let reference = host.context.clone();
+        assert_eq!(append(&mut host, 0), DelegateContext::MAX_SIZE + 11);
+        assert_eq!(host.context, reference);
+        assert_eq!(spills(&host), second_spill);
+    }
+
+    #[test]
+    fn malformed_request_is_a_deser_error() {
+        let err = process(ApplicationMessage::new(vec![0xff]).into()).unwrap_err();
+        assert!(matches!(err, DelegateError::Deser(_)), "{err}");
+    }
+
+    #[test]
+    fn other_messages_are_rejected_by_default() {
+        let msg = InboundDelegateMsg::GetContractResponse(GetContractResponse {
+            contract_id: ContractInstanceId::new([0; 32]),
+            state: None,
+            context: DelegateContext::default(),
+        });
+        let err = process(msg).unwrap_err();
+        assert!(matches!(err, DelegateError::Other(_)), "{err}");
+    }
+}
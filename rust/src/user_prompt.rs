@@ -0,0 +1,373 @@
+//! Standard schema for the prompts delegates show to the user through
+//! [`OutboundDelegateMsg::RequestUserInput`].
+//!
+//! [`UserInputRequest`] only carries opaque bytes, this module gives them a shared format
+//! so any UI can render the prompts of any delegate:
+//!
+//! - the [`NotificationMessage`] is the JSON encoded [`Prompt`],
+//! - each of the `responses` is the JSON encoded [`PromptChoice`] the user can pick,
+//! - the [`ClientResponse`] sent back is the JSON encoded [`PromptAnswer`].
+//!
+//! JSON is used so web UIs can produce and consume them without the stdlib.
+//!
+//! On the delegate side:
+//!
+//! ```ignore
+//! let prompt = Prompt::new("Sign message", "Allow app to sign with your key?")
+//!     .severity(Severity::Warning)
+//!     .choice("allow", "Allow")
+//!     .choice("deny", "Deny");
+//! let request: OutboundDelegateMsg = prompt.to_request(request_id)?.into();
+//!
+//! // later, on InboundDelegateMsg::UserResponse(response)
+//! let answer = prompt.match_response(request_id, &response)?;
+//! if answer.choice == "allow" { /* ... */ }
+//! ```
+//!
+//! On the client side, [`Prompt::from_request`] decodes the prompt and
+//! [`PromptAnswer::to_response`] encodes the user's answer.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::delegate_interface::{
+    ClientResponse, DelegateContext, DelegateError, NotificationMessage, UserInputRequest,
+    UserInputResponse,
+};
+
+/// Current version of the prompt schema.
+pub const PROMPT_SCHEMA_VERSION: u32 = 1;
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum PromptError {
+    #[error("malformed prompt data: {0}")]
+    Encoding(#[from] serde_json::Error),
+    #[error("unsupported prompt schema version {0}")]
+    UnsupportedVersion(u32),
+    #[error("response for request {found} does not match request {expected}")]
+    RequestMismatch { expected: u32, found: u32 },
+    #[error("unknown choice `{0}`")]
+    UnknownChoice(String),
+    #[error("required field `{0}` is missing")]
+    MissingField(String),
+    #[error("unknown field `{0}`")]
+    UnknownField(String),
+    #[error("the prompt expired at {0}")]
+    Expired(DateTime<Utc>),
+}
+
+impl From<PromptError> for DelegateError {
+    fn from(err: PromptError) -> Self {
+        match err {
+            PromptError::Encoding(err) => DelegateError::Deser(format!("{err}")),
+            other => DelegateError::Other(format!("{other}")),
+        }
+    }
+}
+
+/// How the UI should present a prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+/// An option the user can pick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptChoice {
+    pub id: String,
+    pub label: String,
+}
+
+/// A free text input shown along with the choices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptField {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub required: bool,
+    /// Whether the UI should hide the input, e.g. for passwords.
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// A question for the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prompt {
+    pub version: u32,
+    pub title: String,
+    pub body: String,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default)]
+    pub choices: Vec<PromptChoice>,
+    #[serde(default)]
+    pub fields: Vec<PromptField>,
+    /// After this instant the UI should stop showing the prompt and answers are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Prompt {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            version: PROMPT_SCHEMA_VERSION,
+            title: title.into(),
+            body: body.into(),
+            severity: Severity::default(),
+            choices: vec![],
+            fields: vec![],
+            expires_at: None,
+        }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn choice(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.choices.push(PromptChoice {
+            id: id.into(),
+            label: label.into(),
+        });
+        self
+    }
+
+    pub fn field(mut self, field: PromptField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Encodes the prompt into a request to be sent by the delegate.
+    pub fn to_request(&self, request_id: u32) -> Result<UserInputRequest<'static>, PromptError> {
+        let message = NotificationMessage::try_from(&serde_json::to_value(self)?)
+            .expect("infallible conversion");
+        let responses = self
+            .choices
+            .iter()
+            .map(|choice| Ok(ClientResponse::new(serde_json::to_vec(choice)?)))
+            .collect::<Result<_, PromptError>>()?;
+        Ok(UserInputRequest {
+            request_id,
+            message,
+            responses,
+        })
+    }
+
+    /// Decodes the prompt of a request received by the client.
+    pub fn from_request(request: &UserInputRequest) -> Result<Self, PromptError> {
+        let prompt: Prompt = serde_json::from_slice(request.message.bytes())?;
+        if prompt.version > PROMPT_SCHEMA_VERSION {
+            return Err(PromptError::UnsupportedVersion(prompt.version));
+        }
+        Ok(prompt)
+    }
+
+    /// Checks that `response` answers this prompt, sent as `request_id`, and decodes the answer.
+    ///
+    /// The choice must be one of the prompt choices, or empty if the prompt has none, and
+    /// the answer must contain every required field and no unknown ones. Use
+    /// [`Self::match_response_at`] to reject answers to expired prompts.
+    pub fn match_response(
+        &self,
+        request_id: u32,
+        response: &UserInputResponse,
+    ) -> Result<PromptAnswer, PromptError> {
+        if response.request_id != request_id {
+            return Err(PromptError::RequestMismatch {
+                expected: request_id,
+                found: response.request_id,
+            });
+        }
+        let answer: PromptAnswer = serde_json::from_slice(response.response.bytes())?;
+        let known_choice = if self.choices.is_empty() {
+            answer.choice.is_empty()
+        } else {
+            self.choices.iter().any(|c| c.id == answer.choice)
+        };
+        if !known_choice {
+            return Err(PromptError::UnknownChoice(answer.choice));
+        }
+        if let Some(field) = self
+            .fields
+            .iter()
+            .find(|f| f.required && !answer.fields.contains_key(&f.id))
+        {
+            return Err(PromptError::MissingField(field.id.clone()));
+        }
+        if let Some(id) = answer
+            .fields
+            .keys()
+            .find(|id| !self.fields.iter().any(|f| &f.id == *id))
+        {
+            return Err(PromptError::UnknownField(id.clone()));
+        }
+        Ok(answer)
+    }
+
+    /// Same as [`Self::match_response`], also rejecting the answer if the prompt expired by `now`.
+    pub fn match_response_at(
+        &self,
+        request_id: u32,
+        response: &UserInputResponse,
+        now: DateTime<Utc>,
+    ) -> Result<PromptAnswer, PromptError> {
+        if let Some(expiry) = self.expires_at.filter(|_| self.is_expired(now)) {
+            return Err(PromptError::Expired(expiry));
+        }
+        self.match_response(request_id, response)
+    }
+}
+
+impl PromptField {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            required: false,
+            secret: false,
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+}
+
+/// The user's answer to a [`Prompt`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptAnswer {
+    /// Id of the picked [`PromptChoice`].
+    pub choice: String,
+    /// Values of the [`PromptField`]s, by id.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl PromptAnswer {
+    pub fn new(choice: impl Into<String>) -> Self {
+        Self {
+            choice: choice.into(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    pub fn field(mut self, id: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.insert(id.into(), value.into());
+        self
+    }
+
+    /// Encodes the answer to be sent back by the client for the request `request_id`.
+    pub fn to_response(
+        &self,
+        request_id: u32,
+        context: DelegateContext,
+    ) -> Result<UserInputResponse<'static>, PromptError> {
+        Ok(UserInputResponse {
+            request_id,
+            response: ClientResponse::new(serde_json::to_vec(self)?),
+            context,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> Prompt {
+        Prompt::new("Sign message", "Allow the app to sign?")
+            .severity(Severity::Warning)
+            .choice("allow", "Allow")
+            .choice("deny", "Deny")
+            .field(PromptField::new("pin", "PIN").required().secret())
+    }
+
+    #[test]
+    fn prompt_roundtrips_through_request() {
+        let prompt = prompt().expires_at(DateTime::from_timestamp(1_000, 0).unwrap());
+        let request = prompt.to_request(7).unwrap();
+        assert_eq!(request.request_id, 7);
+        assert_eq!(request.responses.len(), 2);
+        let choice: PromptChoice = serde_json::from_slice(request.responses[1].bytes()).unwrap();
+        assert_eq!(choice.id, "deny");
+
+        let json: serde_json::Value = serde_json::from_slice(request.message.bytes()).unwrap();
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(Prompt::from_request(&request).unwrap(), prompt);
+    }
+
+    #[test]
+    fn answer_is_matched_to_request() {
+        let prompt = prompt();
+        let answer = PromptAnswer::new("allow").field("pin", "1234");
+        let response = answer.to_response(7, DelegateContext::default()).unwrap();
+        assert_eq!(prompt.match_response(7, &response).unwrap(), answer);
+        assert!(matches!(
+            prompt.match_response(8, &response),
+            Err(PromptError::RequestMismatch {
+                expected: 8,
+                found: 7
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_answers_are_rejected() {
+        let prompt = prompt();
+        let check = |answer: PromptAnswer| {
+            let response = answer.to_response(1, DelegateContext::default()).unwrap();
+            prompt.match_response(1, &response).unwrap_err()
+        };
+        assert!(matches!(
+            check(PromptAnswer::new("maybe").field("pin", "1")),
+            PromptError::UnknownChoice(c) if c == "maybe"
+        ));
+        assert!(matches!(
+            check(PromptAnswer::new("allow")),
+            PromptError::MissingField(f) if f == "pin"
+        ));
+        assert!(matches!(
+            check(PromptAnswer::new("allow").field("pin", "1").field("x", "")),
+            PromptError::UnknownField(f) if f == "x"
+        ));
+    }
+
+    #[test]
+    fn expired_prompt_rejects_answers() {
+        let expiry = DateTime::from_timestamp(1_000, 0).unwrap();
+        let prompt = prompt().expires_at(expiry);
+        let response = PromptAnswer::new("deny")
+            .field("pin", "1")
+            .to_response(1, DelegateContext::default())
+            .unwrap();
+        let before = DateTime::from_timestamp(999, 0).unwrap();
+        assert!(prompt.match_response_at(1, &response, before).is_ok());
+        assert!(matches!(
+            prompt.match_response_at(1, &response, expiry),
+            Err(PromptError::Expired(_))
+        ));
+    }
+}
@@ -0,0 +1,254 @@
+//! Behaviour of the code generated by `#[derive(ContractComponent)]`, driven through the
+//! exported `ContractInterface` like the node does.
+
+use freenet_stdlib::{contract_composition::*, prelude::*, typed_contract::MergeResult};
+use serde::{Deserialize, Serialize};
+
+/// A value which only grows, up to the maximum set in its parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Bounded(u64);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BoundedParams {
+    max: u64,
+}
+
+impl ComponentParameter for BoundedParams {
+    fn contract_id(&self) -> Option<ContractInstanceId> {
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct BoundedSummary(u64);
+
+impl Mergeable<BoundedSummary> for BoundedSummary {
+    fn merge(&mut self, other: BoundedSummary) {
+        self.0 = self.0.max(other.0);
+    }
+}
+
+/// The new value, `None` if the other side is up to date.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct BoundedDelta(Option<u64>);
+
+impl Mergeable<BoundedDelta> for BoundedDelta {
+    fn merge(&mut self, other: BoundedDelta) {
+        self.0 = self.0.max(other.0);
+    }
+}
+
+impl ContractComponent for Bounded {
+    type Context = NoContext;
+    type Parameters = BoundedParams;
+    type Delta = BoundedDelta;
+    type Summary = BoundedSummary;
+
+    fn verify<Child, Ctx>(
+        &self,
+        parameters: &BoundedParams,
+        _: &Ctx,
+        _: &RelatedContractsContainer,
+    ) -> Result<ValidateResult, ContractError>
+    where
+        Child: ContractComponent,
+        Self::Context: for<'x> From<&'x Ctx>,
+    {
+        if self.0 > parameters.max {
+            return Ok(ValidateResult::Invalid);
+        }
+        Ok(ValidateResult::Valid)
+    }
+
+    fn merge(
+        &mut self,
+        parameters: &BoundedParams,
+        update: &TypedUpdateData<Self>,
+        _: &RelatedContractsContainer,
+    ) -> MergeResult {
+        let other = match update {
+            TypedUpdateData::RelatedState { state } => Some(state.0),
+            TypedUpdateData::RelatedDelta { delta } => delta.0,
+            TypedUpdateData::RelatedStateAndDelta { state, delta } => delta.0.max(Some(state.0)),
+        };
+        let value = self.0.max(other.unwrap_or(0));
+        if value > parameters.max {
+            return MergeResult::Error(ContractError::InvalidUpdate);
+        }
+        self.0 = value;
+        MergeResult::Success
+    }
+
+    fn summarize<ParentSummary>(
+        &self,
+        _: &BoundedParams,
+        summary: &mut ParentSummary,
+    ) -> Result<(), ContractError>
+    where
+        ParentSummary: Mergeable<BoundedSummary>,
+    {
+        summary.merge(BoundedSummary(self.0));
+        Ok(())
+    }
+
+    fn delta(
+        &self,
+        _: &BoundedParams,
+        summary: &BoundedSummary,
+    ) -> Result<BoundedDelta, ContractError> {
+        Ok(BoundedDelta((self.0 > summary.0).then_some(self.0)))
+    }
+
+    fn is_unchanged(delta: &BoundedDelta) -> bool {
+        delta.0.is_none()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ContractComponent)]
+#[component(parameters = ProfileParams, contract(encoder = BincodeEncoder))]
+struct Profile {
+    #[component(params = level)]
+    level: Bounded,
+    #[component(params = score, merge = "replace")]
+    score: Bounded,
+    // parameters converted from `ProfileParams`
+    bonus: Bounded,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ProfileParams {
+    level: BoundedParams,
+    score: BoundedParams,
+}
+
+impl ComponentParameter for ProfileParams {
+    fn contract_id(&self) -> Option<ContractInstanceId> {
+        None
+    }
+}
+
+impl<'x> From<&'x ProfileParams> for BoundedParams {
+    fn from(params: &'x ProfileParams) -> Self {
+        BoundedParams {
+            max: params.level.max + params.score.max,
+        }
+    }
+}
+
+fn params() -> Parameters<'static> {
+    let params = ProfileParams {
+        level: BoundedParams { max: 10 },
+        score: BoundedParams { max: 1000 },
+    };
+    Parameters::from(bincode::serialize(&params).unwrap())
+}
+
+fn profile(level: u64, score: u64, bonus: u64) -> Profile {
+    Profile {
+        level: Bounded(level),
+        score: Bounded(score),
+        bonus: Bounded(bonus),
+    }
+}
+
+fn state(profile: &Profile) -> State<'static> {
+    State::from(bincode::serialize(profile).unwrap())
+}
+
+fn summary(profile: &Profile) -> StateSummary<'static> {
+    Profile::summarize_state(params(), state(profile)).unwrap()
+}
+
+fn delta_between(from: &Profile, to: &Profile) -> StateDelta<'static> {
+    Profile::get_state_delta(params(), state(from), summary(to)).unwrap()
+}
+
+fn apply(profile: &Profile, delta: StateDelta<'static>) -> Profile {
+    let modification =
+        Profile::update_state(params(), state(profile), vec![UpdateData::Delta(delta)]).unwrap();
+    bincode::deserialize(modification.unwrap_valid().as_ref()).unwrap()
+}
+
+#[test]
+fn verify_checks_each_field_with_its_own_parameters() {
+    let validate = |profile: &Profile| {
+        Profile::validate_state(params(), state(profile), RelatedContracts::new()).unwrap()
+    };
+    assert_eq!(validate(&profile(10, 1000, 1010)), ValidateResult::Valid);
+    assert_eq!(validate(&profile(11, 0, 0)), ValidateResult::Invalid);
+    // within the level bound, so only the score parameters can reject it
+    assert_eq!(validate(&profile(0, 1001, 0)), ValidateResult::Invalid);
+    assert_eq!(validate(&profile(0, 0, 1011)), ValidateResult::Invalid);
+}
+
+#[test]
+fn summary_holds_every_field() {
+    let local = profile(3, 40, 2);
+    let summary: ProfileSummary = bincode::deserialize(summary(&local).as_ref()).unwrap();
+    assert_eq!(summary.level, Some(BoundedSummary(3)));
+    assert_eq!(summary.score, Some(BoundedSummary(40)));
+    assert_eq!(summary.bonus, Some(BoundedSummary(2)));
+}
+
+#[test]
+fn delta_roundtrips_and_leaves_out_unchanged_fields() {
+    let local = profile(5, 40, 2);
+    let remote = profile(5, 30, 2);
+
+    let delta = delta_between(&local, &remote);
+    let decoded: ProfileDelta = bincode::deserialize(delta.as_ref()).unwrap();
+    assert_eq!(decoded.level, None, "the level did not change");
+    assert_eq!(decoded.score, Some(BoundedDelta(Some(40))));
+    assert_eq!(decoded.bonus, None, "the bonus did not change");
+
+    assert_eq!(apply(&remote, delta), local);
+}
+
+#[test]
+fn up_to_date_peer_gets_an_empty_delta() {
+    let local = profile(5, 40, 2);
+    let delta = ProfileDelta::default();
+    assert!(delta.is_empty());
+    assert!(Profile::is_unchanged(&delta));
+
+    assert!(delta_between(&local, &local).as_ref().is_empty());
+    // a peer ahead of us has nothing to receive either
+    let ahead = profile(5, 40, 3);
+    let delta = delta_between(&local, &ahead);
+    assert!(delta.as_ref().is_empty());
+    assert_eq!(apply(&ahead, delta), ahead);
+}
+
+#[test]
+fn merge_applies_each_field_and_reports_errors() {
+    let local = profile(2, 40, 1);
+    let update = UpdateData::State(state(&profile(4, 10, 3)));
+    let merged = Profile::update_state(params(), state(&local), vec![update]).unwrap();
+    let merged: Profile = bincode::deserialize(merged.unwrap_valid().as_ref()).unwrap();
+    assert_eq!(merged.level, Bounded(4));
+    assert_eq!(merged.score, Bounded(40));
+    assert_eq!(merged.bonus, Bounded(3));
+
+    let over_limit = UpdateData::State(state(&profile(11, 0, 1)));
+    assert!(Profile::update_state(params(), state(&local), vec![over_limit]).is_err());
+}
+
+#[test]
+fn replace_fields_keep_the_newer_delta() {
+    let mut delta = ProfileDelta {
+        level: Some(BoundedDelta(Some(7))),
+        score: Some(BoundedDelta(Some(70))),
+        bonus: None,
+    };
+    Mergeable::merge(
+        &mut delta,
+        ProfileDelta {
+            level: Some(BoundedDelta(Some(3))),
+            score: Some(BoundedDelta(Some(30))),
+            bonus: None,
+        },
+    );
+    // `level` merges through its `Mergeable` impl, `score` is replaced
+    assert_eq!(delta.level, Some(BoundedDelta(Some(7))));
+    assert_eq!(delta.score, Some(BoundedDelta(Some(30))));
+}
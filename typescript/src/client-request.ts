@@ -21,6 +21,7 @@ export { Get, GetT } from './client-request/get.js';
 export { InboundDelegateMsg, InboundDelegateMsgT } from './client-request/inbound-delegate-msg.js';
 export { InboundDelegateMsgType } from './client-request/inbound-delegate-msg-type.js';
 export { Put, PutT } from './client-request/put.js';
+export { Query, QueryT } from './client-request/query.js';
 export { RegisterDelegate, RegisterDelegateT } from './client-request/register-delegate.js';
 export { RelatedContract, RelatedContractT } from './client-request/related-contract.js';
 export { RelatedContracts, RelatedContractsT } from './client-request/related-contracts.js';
@@ -4,6 +4,7 @@
 
 import { Get, GetT } from '../client-request/get.js';
 import { Put, PutT } from '../client-request/put.js';
+import { Query, QueryT } from '../client-request/query.js';
 import { Subscribe, SubscribeT } from '../client-request/subscribe.js';
 import { Update, UpdateT } from '../client-request/update.js';
 
@@ -13,34 +14,37 @@ export enum ContractRequestType {
   Put = 1,
   Update = 2,
   Get = 3,
-  Subscribe = 4
+  Subscribe = 4,
+  Query = 5
 }
 
 export function unionToContractRequestType(
   type: ContractRequestType,
-  accessor: (obj:Get|Put|Subscribe|Update) => Get|Put|Subscribe|Update|null
-): Get|Put|Subscribe|Update|null {
+  accessor: (obj:Get|Put|Query|Subscribe|Update) => Get|Put|Query|Subscribe|Update|null
+): Get|Put|Query|Subscribe|Update|null {
   switch(ContractRequestType[type]) {
     case 'NONE': return null; 
     case 'Put': return accessor(new Put())! as Put;
     case 'Update': return accessor(new Update())! as Update;
     case 'Get': return accessor(new Get())! as Get;
     case 'Subscribe': return accessor(new Subscribe())! as Subscribe;
+    case 'Query': return accessor(new Query())! as Query;
     default: return null;
   }
 }
 
 export function unionListToContractRequestType(
   type: ContractRequestType, 
-  accessor: (index: number, obj:Get|Put|Subscribe|Update) => Get|Put|Subscribe|Update|null, 
+  accessor: (index: number, obj:Get|Put|Query|Subscribe|Update) => Get|Put|Query|Subscribe|Update|null, 
   index: number
-): Get|Put|Subscribe|Update|null {
+): Get|Put|Query|Subscribe|Update|null {
   switch(ContractRequestType[type]) {
     case 'NONE': return null; 
     case 'Put': return accessor(index, new Put())! as Put;
     case 'Update': return accessor(index, new Update())! as Update;
     case 'Get': return accessor(index, new Get())! as Get;
     case 'Subscribe': return accessor(index, new Subscribe())! as Subscribe;
+    case 'Query': return accessor(index, new Query())! as Query;
     default: return null;
   }
 }
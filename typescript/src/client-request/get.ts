@@ -45,8 +45,23 @@ blockingSubscribe():boolean {
   return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
 }
 
+metadataOnly():boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+}
+
+rangeOffset():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+rangeLen():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
 static startGet(builder:flatbuffers.Builder) {
-  builder.startObject(4);
+  builder.startObject(7);
 }
 
 static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
@@ -65,18 +80,33 @@ static addBlockingSubscribe(builder:flatbuffers.Builder, blockingSubscribe:boole
   builder.addFieldInt8(3, +blockingSubscribe, +false);
 }
 
+static addMetadataOnly(builder:flatbuffers.Builder, metadataOnly:boolean) {
+  builder.addFieldInt8(4, +metadataOnly, +false);
+}
+
+static addRangeOffset(builder:flatbuffers.Builder, rangeOffset:bigint) {
+  builder.addFieldInt64(5, rangeOffset, BigInt('0'));
+}
+
+static addRangeLen(builder:flatbuffers.Builder, rangeLen:bigint) {
+  builder.addFieldInt64(6, rangeLen, BigInt('0'));
+}
+
 static endGet(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   builder.requiredField(offset, 4) // key
   return offset;
 }
 
-static createGet(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, fetchContract:boolean, subscribe:boolean, blockingSubscribe:boolean):flatbuffers.Offset {
+static createGet(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, fetchContract:boolean, subscribe:boolean, blockingSubscribe:boolean, metadataOnly:boolean, rangeOffset:bigint, rangeLen:bigint):flatbuffers.Offset {
   Get.startGet(builder);
   Get.addKey(builder, keyOffset);
   Get.addFetchContract(builder, fetchContract);
   Get.addSubscribe(builder, subscribe);
   Get.addBlockingSubscribe(builder, blockingSubscribe);
+  Get.addMetadataOnly(builder, metadataOnly);
+  Get.addRangeOffset(builder, rangeOffset);
+  Get.addRangeLen(builder, rangeLen);
   return Get.endGet(builder);
 }
 
@@ -85,7 +115,10 @@ unpack(): GetT {
     (this.key() !== null ? this.key()!.unpack() : null),
     this.fetchContract(),
     this.subscribe(),
-    this.blockingSubscribe()
+    this.blockingSubscribe(),
+    this.metadataOnly(),
+    this.rangeOffset(),
+    this.rangeLen()
   );
 }
 
@@ -95,6 +128,9 @@ unpackTo(_o: GetT): void {
   _o.fetchContract = this.fetchContract();
   _o.subscribe = this.subscribe();
   _o.blockingSubscribe = this.blockingSubscribe();
+  _o.metadataOnly = this.metadataOnly();
+  _o.rangeOffset = this.rangeOffset();
+  _o.rangeLen = this.rangeLen();
 }
 }
 
@@ -103,7 +139,10 @@ constructor(
   public key: ContractKeyT|null = null,
   public fetchContract: boolean = false,
   public subscribe: boolean = false,
-  public blockingSubscribe: boolean = false
+  public blockingSubscribe: boolean = false,
+  public metadataOnly: boolean = false,
+  public rangeOffset: bigint = BigInt('0'),
+  public rangeLen: bigint = BigInt('0')
 ){}
 
 
@@ -114,7 +153,10 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
     key,
     this.fetchContract,
     this.subscribe,
-    this.blockingSubscribe
+    this.blockingSubscribe,
+    this.metadataOnly,
+    this.rangeOffset,
+    this.rangeLen
   );
 }
 }
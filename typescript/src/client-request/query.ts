@@ -0,0 +1,116 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ContractKey, ContractKeyT } from '../common/contract-key.js';
+
+
+export class Query implements flatbuffers.IUnpackableObject<QueryT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):Query {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsQuery(bb:flatbuffers.ByteBuffer, obj?:Query):Query {
+  return (obj || new Query()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsQuery(bb:flatbuffers.ByteBuffer, obj?:Query):Query {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new Query()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+key(obj?:ContractKey):ContractKey|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? (obj || new ContractKey()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
+}
+
+query(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+queryLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+queryArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startQuery(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+}
+
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+}
+
+static addQuery(builder:flatbuffers.Builder, queryOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, queryOffset, 0);
+}
+
+static createQueryVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startQueryVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static endQuery(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  builder.requiredField(offset, 4) // key
+  builder.requiredField(offset, 6) // query
+  return offset;
+}
+
+static createQuery(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, queryOffset:flatbuffers.Offset):flatbuffers.Offset {
+  Query.startQuery(builder);
+  Query.addKey(builder, keyOffset);
+  Query.addQuery(builder, queryOffset);
+  return Query.endQuery(builder);
+}
+
+unpack(): QueryT {
+  return new QueryT(
+    (this.key() !== null ? this.key()!.unpack() : null),
+    this.bb!.createScalarList<number>(this.query.bind(this), this.queryLength())
+  );
+}
+
+
+unpackTo(_o: QueryT): void {
+  _o.key = (this.key() !== null ? this.key()!.unpack() : null);
+  _o.query = this.bb!.createScalarList<number>(this.query.bind(this), this.queryLength());
+}
+}
+
+export class QueryT implements flatbuffers.IGeneratedObject {
+constructor(
+  public key: ContractKeyT|null = null,
+  public query: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const key = (this.key !== null ? this.key!.pack(builder) : 0);
+  const query = Query.createQueryVector(builder, this.query);
+
+  return Query.createQuery(builder,
+    key,
+    query
+  );
+}
+}
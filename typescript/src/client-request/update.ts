@@ -36,8 +36,38 @@ data(obj?:UpdateData):UpdateData|null {
   return offset ? (obj || new UpdateData()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
 }
 
+expectedSummary(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+expectedSummaryLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+expectedSummaryArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+expectedStateHash(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+expectedStateHashLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+expectedStateHashArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
 static startUpdate(builder:flatbuffers.Builder) {
-  builder.startObject(2);
+  builder.startObject(4);
 }
 
 static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
@@ -48,6 +78,38 @@ static addData(builder:flatbuffers.Builder, dataOffset:flatbuffers.Offset) {
   builder.addFieldOffset(1, dataOffset, 0);
 }
 
+static addExpectedSummary(builder:flatbuffers.Builder, expectedSummaryOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, expectedSummaryOffset, 0);
+}
+
+static createExpectedSummaryVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startExpectedSummaryVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addExpectedStateHash(builder:flatbuffers.Builder, expectedStateHashOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, expectedStateHashOffset, 0);
+}
+
+static createExpectedStateHashVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startExpectedStateHashVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
 static endUpdate(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   builder.requiredField(offset, 4) // key
@@ -59,7 +121,9 @@ static endUpdate(builder:flatbuffers.Builder):flatbuffers.Offset {
 unpack(): UpdateT {
   return new UpdateT(
     (this.key() !== null ? this.key()!.unpack() : null),
-    (this.data() !== null ? this.data()!.unpack() : null)
+    (this.data() !== null ? this.data()!.unpack() : null),
+    this.bb!.createScalarList<number>(this.expectedSummary.bind(this), this.expectedSummaryLength()),
+    this.bb!.createScalarList<number>(this.expectedStateHash.bind(this), this.expectedStateHashLength())
   );
 }
 
@@ -67,23 +131,31 @@ unpack(): UpdateT {
 unpackTo(_o: UpdateT): void {
   _o.key = (this.key() !== null ? this.key()!.unpack() : null);
   _o.data = (this.data() !== null ? this.data()!.unpack() : null);
+  _o.expectedSummary = this.bb!.createScalarList<number>(this.expectedSummary.bind(this), this.expectedSummaryLength());
+  _o.expectedStateHash = this.bb!.createScalarList<number>(this.expectedStateHash.bind(this), this.expectedStateHashLength());
 }
 }
 
 export class UpdateT implements flatbuffers.IGeneratedObject {
 constructor(
   public key: ContractKeyT|null = null,
-  public data: UpdateDataT|null = null
+  public data: UpdateDataT|null = null,
+  public expectedSummary: (number)[] = [],
+  public expectedStateHash: (number)[] = []
 ){}
 
 
 pack(builder:flatbuffers.Builder): flatbuffers.Offset {
   const key = (this.key !== null ? this.key!.pack(builder) : 0);
   const data = (this.data !== null ? this.data!.pack(builder) : 0);
+  const expectedSummary = Update.createExpectedSummaryVector(builder, this.expectedSummary);
+  const expectedStateHash = Update.createExpectedStateHashVector(builder, this.expectedStateHash);
 
   Update.startUpdate(builder);
   Update.addKey(builder, key);
   Update.addData(builder, data);
+  Update.addExpectedSummary(builder, expectedSummary);
+  Update.addExpectedStateHash(builder, expectedStateHash);
 
   return Update.endUpdate(builder);
 }
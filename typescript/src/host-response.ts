@@ -10,6 +10,8 @@ export { DelegateKey, DelegateKeyT } from './host-response/delegate-key.js';
 export { DelegateResponse, DelegateResponseT } from './host-response/delegate-response.js';
 export { Error, ErrorT } from './host-response/error.js';
 export { GenerateRandData, GenerateRandDataT } from './host-response/generate-rand-data.js';
+export { GetMetadataResponse, GetMetadataResponseT } from './host-response/get-metadata-response.js';
+export { GetRangeResponse, GetRangeResponseT } from './host-response/get-range-response.js';
 export { GetResponse, GetResponseT } from './host-response/get-response.js';
 export { HostResponse, HostResponseT } from './host-response/host-response.js';
 export { HostResponseType } from './host-response/host-response-type.js';
@@ -18,9 +20,11 @@ export { Ok, OkT } from './host-response/ok.js';
 export { OutboundDelegateMsg, OutboundDelegateMsgT } from './host-response/outbound-delegate-msg.js';
 export { OutboundDelegateMsgType } from './host-response/outbound-delegate-msg-type.js';
 export { PutResponse, PutResponseT } from './host-response/put-response.js';
+export { QueryResponse, QueryResponseT } from './host-response/query-response.js';
 export { RequestUserInput, RequestUserInputT } from './host-response/request-user-input.js';
 export { StreamChunk, StreamChunkT } from './host-response/stream-chunk.js';
 export { SubscribeResponse, SubscribeResponseT } from './host-response/subscribe-response.js';
 export { UpdateNotification, UpdateNotificationT } from './host-response/update-notification.js';
+export { UpdatePreconditionFailed, UpdatePreconditionFailedT } from './host-response/update-precondition-failed.js';
 export { UpdateResponse, UpdateResponseT } from './host-response/update-response.js';
 export { UserInputRequest, UserInputRequestT } from './host-response/user-input-request.js';
@@ -2,11 +2,15 @@
 
 /* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
 
+import { GetMetadataResponse, GetMetadataResponseT } from '../host-response/get-metadata-response.js';
+import { GetRangeResponse, GetRangeResponseT } from '../host-response/get-range-response.js';
 import { GetResponse, GetResponseT } from '../host-response/get-response.js';
 import { NotFound, NotFoundT } from '../host-response/not-found.js';
 import { PutResponse, PutResponseT } from '../host-response/put-response.js';
+import { QueryResponse, QueryResponseT } from '../host-response/query-response.js';
 import { SubscribeResponse, SubscribeResponseT } from '../host-response/subscribe-response.js';
 import { UpdateNotification, UpdateNotificationT } from '../host-response/update-notification.js';
+import { UpdatePreconditionFailed, UpdatePreconditionFailedT } from '../host-response/update-precondition-failed.js';
 import { UpdateResponse, UpdateResponseT } from '../host-response/update-response.js';
 
 
@@ -17,13 +21,17 @@ export enum ContractResponseType {
   UpdateNotification = 3,
   UpdateResponse = 4,
   NotFound = 5,
-  SubscribeResponse = 6
+  SubscribeResponse = 6,
+  QueryResponse = 7,
+  UpdatePreconditionFailed = 8,
+  GetMetadataResponse = 9,
+  GetRangeResponse = 10
 }
 
 export function unionToContractResponseType(
   type: ContractResponseType,
-  accessor: (obj:GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse) => GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse|null
-): GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse|null {
+  accessor: (obj:GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse) => GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse|null
+): GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse|null {
   switch(ContractResponseType[type]) {
     case 'NONE': return null; 
     case 'GetResponse': return accessor(new GetResponse())! as GetResponse;
@@ -32,15 +40,19 @@ export function unionToContractResponseType(
     case 'UpdateResponse': return accessor(new UpdateResponse())! as UpdateResponse;
     case 'NotFound': return accessor(new NotFound())! as NotFound;
     case 'SubscribeResponse': return accessor(new SubscribeResponse())! as SubscribeResponse;
+    case 'QueryResponse': return accessor(new QueryResponse())! as QueryResponse;
+    case 'UpdatePreconditionFailed': return accessor(new UpdatePreconditionFailed())! as UpdatePreconditionFailed;
+    case 'GetMetadataResponse': return accessor(new GetMetadataResponse())! as GetMetadataResponse;
+    case 'GetRangeResponse': return accessor(new GetRangeResponse())! as GetRangeResponse;
     default: return null;
   }
 }
 
 export function unionListToContractResponseType(
   type: ContractResponseType, 
-  accessor: (index: number, obj:GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse) => GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse|null, 
+  accessor: (index: number, obj:GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse) => GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse|null, 
   index: number
-): GetResponse|NotFound|PutResponse|SubscribeResponse|UpdateNotification|UpdateResponse|null {
+): GetMetadataResponse|GetRangeResponse|GetResponse|NotFound|PutResponse|QueryResponse|SubscribeResponse|UpdateNotification|UpdatePreconditionFailed|UpdateResponse|null {
   switch(ContractResponseType[type]) {
     case 'NONE': return null; 
     case 'GetResponse': return accessor(index, new GetResponse())! as GetResponse;
@@ -49,6 +61,10 @@ export function unionListToContractResponseType(
     case 'UpdateResponse': return accessor(index, new UpdateResponse())! as UpdateResponse;
     case 'NotFound': return accessor(index, new NotFound())! as NotFound;
     case 'SubscribeResponse': return accessor(index, new SubscribeResponse())! as SubscribeResponse;
+    case 'QueryResponse': return accessor(index, new QueryResponse())! as QueryResponse;
+    case 'UpdatePreconditionFailed': return accessor(index, new UpdatePreconditionFailed())! as UpdatePreconditionFailed;
+    case 'GetMetadataResponse': return accessor(index, new GetMetadataResponse())! as GetMetadataResponse;
+    case 'GetRangeResponse': return accessor(index, new GetRangeResponse())! as GetRangeResponse;
     default: return null;
   }
 }
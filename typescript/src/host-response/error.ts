@@ -31,49 +31,80 @@ msg(optionalEncoding?:any):string|Uint8Array|null {
   return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
 }
 
+code():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint32(this.bb_pos + offset) : 0;
+}
+
+field():string|null
+field(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+field(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
 static startError(builder:flatbuffers.Builder) {
-  builder.startObject(1);
+  builder.startObject(3);
 }
 
 static addMsg(builder:flatbuffers.Builder, msgOffset:flatbuffers.Offset) {
   builder.addFieldOffset(0, msgOffset, 0);
 }
 
+static addCode(builder:flatbuffers.Builder, code:number) {
+  builder.addFieldInt32(1, code, 0);
+}
+
+static addField(builder:flatbuffers.Builder, fieldOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, fieldOffset, 0);
+}
+
 static endError(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   builder.requiredField(offset, 4) // msg
   return offset;
 }
 
-static createError(builder:flatbuffers.Builder, msgOffset:flatbuffers.Offset):flatbuffers.Offset {
+static createError(builder:flatbuffers.Builder, msgOffset:flatbuffers.Offset, code:number, fieldOffset:flatbuffers.Offset):flatbuffers.Offset {
   Error.startError(builder);
   Error.addMsg(builder, msgOffset);
+  Error.addCode(builder, code);
+  Error.addField(builder, fieldOffset);
   return Error.endError(builder);
 }
 
 unpack(): ErrorT {
   return new ErrorT(
-    this.msg()
+    this.msg(),
+    this.code(),
+    this.field()
   );
 }
 
 
 unpackTo(_o: ErrorT): void {
   _o.msg = this.msg();
+  _o.code = this.code();
+  _o.field = this.field();
 }
 }
 
 export class ErrorT implements flatbuffers.IGeneratedObject {
 constructor(
-  public msg: string|Uint8Array|null = null
+  public msg: string|Uint8Array|null = null,
+  public code: number = 0,
+  public field: string|Uint8Array|null = null
 ){}
 
 
 pack(builder:flatbuffers.Builder): flatbuffers.Offset {
   const msg = (this.msg !== null ? builder.createString(this.msg!) : 0);
+  const field = (this.field !== null ? builder.createString(this.field!) : 0);
 
   return Error.createError(builder,
-    msg
+    msg,
+    this.code,
+    field
   );
 }
 }
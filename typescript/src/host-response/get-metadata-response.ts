@@ -0,0 +1,129 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ContractKey, ContractKeyT } from '../common/contract-key.js';
+
+
+export class GetMetadataResponse implements flatbuffers.IUnpackableObject<GetMetadataResponseT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):GetMetadataResponse {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsGetMetadataResponse(bb:flatbuffers.ByteBuffer, obj?:GetMetadataResponse):GetMetadataResponse {
+  return (obj || new GetMetadataResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsGetMetadataResponse(bb:flatbuffers.ByteBuffer, obj?:GetMetadataResponse):GetMetadataResponse {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new GetMetadataResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+key(obj?:ContractKey):ContractKey|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? (obj || new ContractKey()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
+}
+
+stateSize():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+summary(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+summaryLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+summaryArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startGetMetadataResponse(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+}
+
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+}
+
+static addStateSize(builder:flatbuffers.Builder, stateSize:bigint) {
+  builder.addFieldInt64(1, stateSize, BigInt('0'));
+}
+
+static addSummary(builder:flatbuffers.Builder, summaryOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, summaryOffset, 0);
+}
+
+static createSummaryVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startSummaryVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static endGetMetadataResponse(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  builder.requiredField(offset, 4) // key
+  return offset;
+}
+
+static createGetMetadataResponse(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, stateSize:bigint, summaryOffset:flatbuffers.Offset):flatbuffers.Offset {
+  GetMetadataResponse.startGetMetadataResponse(builder);
+  GetMetadataResponse.addKey(builder, keyOffset);
+  GetMetadataResponse.addStateSize(builder, stateSize);
+  GetMetadataResponse.addSummary(builder, summaryOffset);
+  return GetMetadataResponse.endGetMetadataResponse(builder);
+}
+
+unpack(): GetMetadataResponseT {
+  return new GetMetadataResponseT(
+    (this.key() !== null ? this.key()!.unpack() : null),
+    this.stateSize(),
+    this.bb!.createScalarList<number>(this.summary.bind(this), this.summaryLength())
+  );
+}
+
+
+unpackTo(_o: GetMetadataResponseT): void {
+  _o.key = (this.key() !== null ? this.key()!.unpack() : null);
+  _o.stateSize = this.stateSize();
+  _o.summary = this.bb!.createScalarList<number>(this.summary.bind(this), this.summaryLength());
+}
+}
+
+export class GetMetadataResponseT implements flatbuffers.IGeneratedObject {
+constructor(
+  public key: ContractKeyT|null = null,
+  public stateSize: bigint = BigInt('0'),
+  public summary: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const key = (this.key !== null ? this.key!.pack(builder) : 0);
+  const summary = GetMetadataResponse.createSummaryVector(builder, this.summary);
+
+  return GetMetadataResponse.createGetMetadataResponse(builder,
+    key,
+    this.stateSize,
+    summary
+  );
+}
+}
@@ -0,0 +1,144 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ContractKey, ContractKeyT } from '../common/contract-key.js';
+
+
+export class GetRangeResponse implements flatbuffers.IUnpackableObject<GetRangeResponseT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):GetRangeResponse {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsGetRangeResponse(bb:flatbuffers.ByteBuffer, obj?:GetRangeResponse):GetRangeResponse {
+  return (obj || new GetRangeResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsGetRangeResponse(bb:flatbuffers.ByteBuffer, obj?:GetRangeResponse):GetRangeResponse {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new GetRangeResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+key(obj?:ContractKey):ContractKey|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? (obj || new ContractKey()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
+}
+
+offset():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+totalSize():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+data(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+dataLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+dataArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startGetRangeResponse(builder:flatbuffers.Builder) {
+  builder.startObject(4);
+}
+
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+}
+
+static addOffset(builder:flatbuffers.Builder, offset:bigint) {
+  builder.addFieldInt64(1, offset, BigInt('0'));
+}
+
+static addTotalSize(builder:flatbuffers.Builder, totalSize:bigint) {
+  builder.addFieldInt64(2, totalSize, BigInt('0'));
+}
+
+static addData(builder:flatbuffers.Builder, dataOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, dataOffset, 0);
+}
+
+static createDataVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startDataVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static endGetRangeResponse(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  builder.requiredField(offset, 4) // key
+  builder.requiredField(offset, 10) // data
+  return offset;
+}
+
+static createGetRangeResponse(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, offset:bigint, totalSize:bigint, dataOffset:flatbuffers.Offset):flatbuffers.Offset {
+  GetRangeResponse.startGetRangeResponse(builder);
+  GetRangeResponse.addKey(builder, keyOffset);
+  GetRangeResponse.addOffset(builder, offset);
+  GetRangeResponse.addTotalSize(builder, totalSize);
+  GetRangeResponse.addData(builder, dataOffset);
+  return GetRangeResponse.endGetRangeResponse(builder);
+}
+
+unpack(): GetRangeResponseT {
+  return new GetRangeResponseT(
+    (this.key() !== null ? this.key()!.unpack() : null),
+    this.offset(),
+    this.totalSize(),
+    this.bb!.createScalarList<number>(this.data.bind(this), this.dataLength())
+  );
+}
+
+
+unpackTo(_o: GetRangeResponseT): void {
+  _o.key = (this.key() !== null ? this.key()!.unpack() : null);
+  _o.offset = this.offset();
+  _o.totalSize = this.totalSize();
+  _o.data = this.bb!.createScalarList<number>(this.data.bind(this), this.dataLength());
+}
+}
+
+export class GetRangeResponseT implements flatbuffers.IGeneratedObject {
+constructor(
+  public key: ContractKeyT|null = null,
+  public offset: bigint = BigInt('0'),
+  public totalSize: bigint = BigInt('0'),
+  public data: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const key = (this.key !== null ? this.key!.pack(builder) : 0);
+  const data = GetRangeResponse.createDataVector(builder, this.data);
+
+  return GetRangeResponse.createGetRangeResponse(builder,
+    key,
+    this.offset,
+    this.totalSize,
+    data
+  );
+}
+}
@@ -0,0 +1,116 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ContractKey, ContractKeyT } from '../common/contract-key.js';
+
+
+export class QueryResponse implements flatbuffers.IUnpackableObject<QueryResponseT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):QueryResponse {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsQueryResponse(bb:flatbuffers.ByteBuffer, obj?:QueryResponse):QueryResponse {
+  return (obj || new QueryResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsQueryResponse(bb:flatbuffers.ByteBuffer, obj?:QueryResponse):QueryResponse {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new QueryResponse()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+key(obj?:ContractKey):ContractKey|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? (obj || new ContractKey()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
+}
+
+result(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+resultLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+resultArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startQueryResponse(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+}
+
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+}
+
+static addResult(builder:flatbuffers.Builder, resultOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, resultOffset, 0);
+}
+
+static createResultVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startResultVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static endQueryResponse(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  builder.requiredField(offset, 4) // key
+  builder.requiredField(offset, 6) // result
+  return offset;
+}
+
+static createQueryResponse(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, resultOffset:flatbuffers.Offset):flatbuffers.Offset {
+  QueryResponse.startQueryResponse(builder);
+  QueryResponse.addKey(builder, keyOffset);
+  QueryResponse.addResult(builder, resultOffset);
+  return QueryResponse.endQueryResponse(builder);
+}
+
+unpack(): QueryResponseT {
+  return new QueryResponseT(
+    (this.key() !== null ? this.key()!.unpack() : null),
+    this.bb!.createScalarList<number>(this.result.bind(this), this.resultLength())
+  );
+}
+
+
+unpackTo(_o: QueryResponseT): void {
+  _o.key = (this.key() !== null ? this.key()!.unpack() : null);
+  _o.result = this.bb!.createScalarList<number>(this.result.bind(this), this.resultLength());
+}
+}
+
+export class QueryResponseT implements flatbuffers.IGeneratedObject {
+constructor(
+  public key: ContractKeyT|null = null,
+  public result: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const key = (this.key !== null ? this.key!.pack(builder) : 0);
+  const result = QueryResponse.createResultVector(builder, this.result);
+
+  return QueryResponse.createQueryResponse(builder,
+    key,
+    result
+  );
+}
+}
@@ -0,0 +1,152 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ContractKey, ContractKeyT } from '../common/contract-key.js';
+
+
+export class UpdatePreconditionFailed implements flatbuffers.IUnpackableObject<UpdatePreconditionFailedT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):UpdatePreconditionFailed {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsUpdatePreconditionFailed(bb:flatbuffers.ByteBuffer, obj?:UpdatePreconditionFailed):UpdatePreconditionFailed {
+  return (obj || new UpdatePreconditionFailed()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsUpdatePreconditionFailed(bb:flatbuffers.ByteBuffer, obj?:UpdatePreconditionFailed):UpdatePreconditionFailed {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new UpdatePreconditionFailed()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+key(obj?:ContractKey):ContractKey|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? (obj || new ContractKey()).__init(this.bb!.__indirect(this.bb_pos + offset), this.bb!) : null;
+}
+
+currentSummary(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+currentSummaryLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+currentSummaryArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+currentStateHash(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+currentStateHashLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+currentStateHashArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startUpdatePreconditionFailed(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+}
+
+static addKey(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, keyOffset, 0);
+}
+
+static addCurrentSummary(builder:flatbuffers.Builder, currentSummaryOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, currentSummaryOffset, 0);
+}
+
+static createCurrentSummaryVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startCurrentSummaryVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addCurrentStateHash(builder:flatbuffers.Builder, currentStateHashOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, currentStateHashOffset, 0);
+}
+
+static createCurrentStateHashVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startCurrentStateHashVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static endUpdatePreconditionFailed(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  builder.requiredField(offset, 4) // key
+  return offset;
+}
+
+static createUpdatePreconditionFailed(builder:flatbuffers.Builder, keyOffset:flatbuffers.Offset, currentSummaryOffset:flatbuffers.Offset, currentStateHashOffset:flatbuffers.Offset):flatbuffers.Offset {
+  UpdatePreconditionFailed.startUpdatePreconditionFailed(builder);
+  UpdatePreconditionFailed.addKey(builder, keyOffset);
+  UpdatePreconditionFailed.addCurrentSummary(builder, currentSummaryOffset);
+  UpdatePreconditionFailed.addCurrentStateHash(builder, currentStateHashOffset);
+  return UpdatePreconditionFailed.endUpdatePreconditionFailed(builder);
+}
+
+unpack(): UpdatePreconditionFailedT {
+  return new UpdatePreconditionFailedT(
+    (this.key() !== null ? this.key()!.unpack() : null),
+    this.bb!.createScalarList<number>(this.currentSummary.bind(this), this.currentSummaryLength()),
+    this.bb!.createScalarList<number>(this.currentStateHash.bind(this), this.currentStateHashLength())
+  );
+}
+
+
+unpackTo(_o: UpdatePreconditionFailedT): void {
+  _o.key = (this.key() !== null ? this.key()!.unpack() : null);
+  _o.currentSummary = this.bb!.createScalarList<number>(this.currentSummary.bind(this), this.currentSummaryLength());
+  _o.currentStateHash = this.bb!.createScalarList<number>(this.currentStateHash.bind(this), this.currentStateHashLength());
+}
+}
+
+export class UpdatePreconditionFailedT implements flatbuffers.IGeneratedObject {
+constructor(
+  public key: ContractKeyT|null = null,
+  public currentSummary: (number)[] = [],
+  public currentStateHash: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const key = (this.key !== null ? this.key!.pack(builder) : 0);
+  const currentSummary = UpdatePreconditionFailed.createCurrentSummaryVector(builder, this.currentSummary);
+  const currentStateHash = UpdatePreconditionFailed.createCurrentStateHashVector(builder, this.currentStateHash);
+
+  return UpdatePreconditionFailed.createUpdatePreconditionFailed(builder,
+    key,
+    currentSummary,
+    currentStateHash
+  );
+}
+}